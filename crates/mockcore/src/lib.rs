@@ -85,16 +85,28 @@ pub struct ListDescriptorsResult {
 }
 
 pub fn builder() -> Builder {
-	Builder { fail_lock_unspent: false, network: Network::Bitcoin, version: 250000 }
+	Builder {
+		datacarriersize: None,
+		fail_lock_unspent: false,
+		network: Network::Bitcoin,
+		version: 250000,
+	}
 }
 
 pub struct Builder {
+	datacarriersize: Option<usize>,
 	fail_lock_unspent: bool,
 	network: Network,
 	version: usize,
 }
 
 impl Builder {
+	/// Rejects transactions submitted with `sendrawtransaction` whose OP_RETURN outputs add up to
+	/// more than `datacarriersize` bytes, like Bitcoin Core's `-datacarriersize` option.
+	pub fn datacarriersize(self, datacarriersize: usize) -> Self {
+		Self { datacarriersize: Some(datacarriersize), ..self }
+	}
+
 	pub fn fail_lock_unspent(self, fail_lock_unspent: bool) -> Self {
 		Self { fail_lock_unspent, ..self }
 	}
@@ -108,8 +120,12 @@ impl Builder {
 	}
 
 	pub fn build(self) -> Handle {
-		let state =
-			Arc::new(Mutex::new(State::new(self.network, self.version, self.fail_lock_unspent)));
+		let state = Arc::new(Mutex::new(State::new(
+			self.network,
+			self.version,
+			self.fail_lock_unspent,
+			self.datacarriersize,
+		)));
 		let server = Server::new(state.clone());
 		let mut io = IoHandler::default();
 		io.extend_with(server.to_delegate());
@@ -209,6 +225,35 @@ impl Default for TransactionTemplate<'_> {
 	}
 }
 
+/// Builds an unsigned transaction spending `inputs` whose first output carries `op_return`,
+/// followed by an output paying each `(address, value)` in `outputs`, as BRC-721 commands are
+/// laid out on chain.
+pub fn brc721_tx(
+	inputs: &[OutPoint],
+	op_return: ScriptBuf,
+	outputs: &[(&Address, u64)],
+) -> Transaction {
+	Transaction {
+		version: Version(2),
+		lock_time: LockTime::ZERO,
+		input: inputs
+			.iter()
+			.map(|outpoint| TxIn {
+				previous_output: *outpoint,
+				script_sig: ScriptBuf::new(),
+				sequence: Sequence::MAX,
+				witness: Witness::new(),
+			})
+			.collect(),
+		output: std::iter::once(TxOut { value: Amount::ZERO, script_pubkey: op_return })
+			.chain(outputs.iter().map(|(address, value)| TxOut {
+				value: Amount::from_sat(*value),
+				script_pubkey: address.script_pubkey(),
+			}))
+			.collect(),
+	}
+}
+
 pub struct Handle {
 	close_handle: Option<CloseHandle>,
 	port: u16,
@@ -283,6 +328,17 @@ impl Handle {
 		self.state().broadcast_tx(template)
 	}
 
+	/// Adds an arbitrary transaction to the mempool, bypassing the `datacarriersize` policy, as a
+	/// miner including a non-standard transaction would.
+	pub fn broadcast_raw_tx(&self, tx: Transaction) -> Txid {
+		self.state().broadcast_raw_tx(tx)
+	}
+
+	#[track_caller]
+	pub fn outpoint(&self, block: usize, transaction: usize, vout: u32) -> OutPoint {
+		OutPoint { txid: self.tx(block, transaction).compute_txid(), vout }
+	}
+
 	pub fn height(&self) -> u64 {
 		u64::try_from(self.state().blocks.len()).unwrap() - 1
 	}
@@ -390,7 +390,9 @@ impl Api for Server {
 
 		if output_value > input_value {
 			for (value, outpoint) in utxos {
-				if state.locked.contains(&outpoint) {
+				if state.locked.contains(&outpoint) ||
+					transaction.input.iter().any(|txin| txin.previous_output == outpoint)
+				{
 					continue;
 				}
 
@@ -519,6 +521,14 @@ impl Api for Server {
 
 		let mut state = self.state.lock().unwrap();
 
+		if state.exceeds_datacarriersize(&tx) {
+			return Err(jsonrpc_core::Error {
+				code: jsonrpc_core::types::error::ErrorCode::ServerError(-26),
+				message: "scriptpubkey".into(),
+				data: None,
+			});
+		}
+
 		for tx_in in &tx.input {
 			if let Some(lock_time) = tx_in.sequence.to_relative_lock_time() {
 				match lock_time {
@@ -658,6 +668,10 @@ impl Api for Server {
 		.unwrap())
 	}
 
+	fn get_raw_mempool(&self) -> Result<Vec<Txid>, jsonrpc_core::Error> {
+		Ok(self.state().mempool().iter().map(Transaction::compute_txid).collect())
+	}
+
 	fn get_raw_transaction(
 		&self,
 		txid: Txid,
@@ -715,7 +729,11 @@ impl Api for Server {
 				None => Err(Self::not_found()),
 			}
 		} else {
-			match state.transactions.get(&txid) {
+			match state
+				.transactions
+				.get(&txid)
+				.or_else(|| state.mempool().iter().find(|tx| tx.compute_txid() == txid))
+			{
 				Some(tx) => Ok(Value::String(hex::encode(serialize(tx)))),
 				None => Err(Self::not_found()),
 			}
@@ -19,6 +19,7 @@ use super::*;
 #[derive(Debug)]
 pub struct State {
 	pub blocks: BTreeMap<BlockHash, Block>,
+	pub datacarriersize: Option<usize>,
 	pub descriptors: Vec<(String, bitcoincore_rpc::json::Timestamp)>,
 	pub fail_lock_unspent: bool,
 	pub hashes: Vec<BlockHash>,
@@ -38,7 +39,12 @@ pub struct State {
 }
 
 impl State {
-	pub(crate) fn new(network: Network, version: usize, fail_lock_unspent: bool) -> Self {
+	pub(crate) fn new(
+		network: Network,
+		version: usize,
+		fail_lock_unspent: bool,
+		datacarriersize: Option<usize>,
+	) -> Self {
 		let mut hashes = Vec::new();
 		let mut blocks = BTreeMap::new();
 
@@ -50,6 +56,7 @@ impl State {
 		Self {
 			blocks,
 			change_addresses: Vec::new(),
+			datacarriersize,
 			descriptors: Vec::new(),
 			fail_lock_unspent,
 			hashes,
@@ -82,7 +89,7 @@ impl State {
 	}
 
 	pub(crate) fn clear(&mut self) {
-		*self = Self::new(self.network, self.version, self.fail_lock_unspent);
+		*self = Self::new(self.network, self.version, self.fail_lock_unspent, self.datacarriersize);
 	}
 
 	#[track_caller]
@@ -278,6 +285,38 @@ impl State {
 		txid
 	}
 
+	pub(crate) fn broadcast_raw_tx(&mut self, tx: Transaction) -> Txid {
+		for tx_in in &tx.input {
+			let spendable = self.utxos.contains_key(&tx_in.previous_output) ||
+				self.mempool.iter().any(|tx| tx.compute_txid() == tx_in.previous_output.txid);
+
+			assert!(spendable, "input {} is not spendable", tx_in.previous_output);
+		}
+
+		let txid = tx.compute_txid();
+
+		self.mempool.push(tx);
+
+		txid
+	}
+
+	/// Returns whether the OP_RETURN outputs of `tx` add up to more bytes than allowed by the
+	/// `datacarriersize` policy.
+	pub(crate) fn exceeds_datacarriersize(&self, tx: &Transaction) -> bool {
+		let Some(datacarriersize) = self.datacarriersize else {
+			return false;
+		};
+
+		let size = tx
+			.output
+			.iter()
+			.filter(|tx_out| tx_out.script_pubkey.is_op_return())
+			.map(|tx_out| tx_out.script_pubkey.len())
+			.sum::<usize>();
+
+		size > datacarriersize
+	}
+
 	pub(crate) fn mempool(&self) -> &[Transaction] {
 		&self.mempool
 	}
@@ -0,0 +1,49 @@
+// Copyright 2023-2024 Freeverse.io
+// This file is part of LAOS.
+
+// LAOS is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// LAOS is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Just enough of the Solidity ABI to encode the calls and events of LAOS collections.
+
+use super::*;
+
+pub(crate) fn address(address: H160) -> [u8; 32] {
+	let mut word = [0; 32];
+	word[12..].copy_from_slice(address.as_bytes());
+	word
+}
+
+pub(crate) fn uint(value: U256) -> [u8; 32] {
+	value.to_big_endian()
+}
+
+/// Encodes the tail of a dynamic `string`, that is its length followed by its zero padded bytes.
+pub(crate) fn string_tail(string: &str) -> Vec<u8> {
+	let mut tail = uint(U256::from(string.len())).to_vec();
+	tail.extend_from_slice(string.as_bytes());
+	tail.resize(32 + string.len().div_ceil(32) * 32, 0);
+	tail
+}
+
+/// Encodes a single `string` return value.
+pub(crate) fn string(string: &str) -> Vec<u8> {
+	let mut encoded = uint(U256::from(32u64)).to_vec();
+	encoded.extend(string_tail(string));
+	encoded
+}
+
+/// Decodes the single `uint256` argument of a call, following its selector.
+pub(crate) fn uint_argument(data: &[u8]) -> Option<U256> {
+	data.get(4..36).map(U256::from_big_endian)
+}
@@ -0,0 +1,38 @@
+// Copyright 2023-2024 Freeverse.io
+// This file is part of LAOS.
+
+// LAOS is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// LAOS is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+
+#[doc(hidden)]
+#[jsonrpc_derive::rpc(server)]
+pub trait Api {
+	#[rpc(name = "eth_chainId")]
+	fn chain_id(&self) -> Result<U256, jsonrpc_core::Error>;
+
+	#[rpc(name = "eth_blockNumber")]
+	fn block_number(&self) -> Result<U256, jsonrpc_core::Error>;
+
+	#[rpc(name = "eth_getCode")]
+	fn get_code(&self, address: H160, block: Option<String>)
+		-> Result<String, jsonrpc_core::Error>;
+
+	#[rpc(name = "eth_call")]
+	fn call(&self, call: CallRequest, block: Option<String>)
+		-> Result<String, jsonrpc_core::Error>;
+
+	#[rpc(name = "eth_getLogs")]
+	fn get_logs(&self, filter: Filter) -> Result<Vec<Log>, jsonrpc_core::Error>;
+}
@@ -0,0 +1,288 @@
+// Copyright 2023-2024 Freeverse.io
+// This file is part of LAOS.
+
+// LAOS is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// LAOS is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Mock of a LAOS node's Ethereum JSON-RPC API, emulating the sibling collections that BRC721
+//! collections point to with their LAOS address.
+
+use api::Api;
+use jsonrpc_core::IoHandler;
+use jsonrpc_http_server::{CloseHandle, ServerBuilder};
+use serde::{Deserialize, Serialize};
+use server::Server;
+use sha3::{Digest, Keccak256};
+use sp_core::{H160, H256, U256};
+use state::State;
+use std::{
+	collections::BTreeMap,
+	sync::{Arc, Mutex, MutexGuard},
+	thread,
+	time::Duration,
+};
+
+pub use state::Collection;
+
+mod abi;
+mod api;
+mod server;
+mod state;
+
+/// Chain id of LAOS mainnet.
+pub const LAOS_CHAIN_ID: u64 = 6283;
+
+/// Widest block range `eth_getLogs` accepts by default, as on Frontier based nodes like LAOS.
+pub const MAX_LOG_BLOCK_RANGE: u64 = 1024;
+
+/// Bytecode of LAOS collection addresses. Collections are implemented natively, so their code is
+/// only there to make them look like contracts, and reverts if executed.
+pub const REVERT_BYTECODE: [u8; 5] = [0x60, 0x00, 0x60, 0x00, 0xfd];
+
+pub const MINTED_WITH_EXTERNAL_URI: &str = "MintedWithExternalURI(address,uint96,uint256,string)";
+
+pub const EVOLVED_WITH_EXTERNAL_URI: &str = "EvolvedWithExternalURI(uint256,string)";
+
+/// Returns the topic identifying an event with `signature`.
+pub fn event_topic(signature: &str) -> H256 {
+	H256::from_slice(&Keccak256::digest(signature.as_bytes()))
+}
+
+/// Returns the selector of a function with `signature`.
+pub fn selector(signature: &str) -> [u8; 4] {
+	Keccak256::digest(signature.as_bytes())[..4].try_into().unwrap()
+}
+
+/// Returns the address of the collection with `id`, a fixed prefix followed by the id.
+pub fn collection_address(id: u64) -> H160 {
+	let mut address = [0xff; 20];
+	address[11] = 0xfe;
+	address[12..].copy_from_slice(&id.to_be_bytes());
+	H160::from(address)
+}
+
+/// Returns the id of the token of `slot` whose initial owner is `initial_owner`.
+pub fn token_id(slot: u128, initial_owner: H160) -> U256 {
+	(U256::from(slot) << 160) | U256::from_big_endian(initial_owner.as_bytes())
+}
+
+pub fn builder() -> Builder {
+	Builder { chain_id: LAOS_CHAIN_ID, max_log_block_range: MAX_LOG_BLOCK_RANGE }
+}
+
+pub struct Builder {
+	chain_id: u64,
+	max_log_block_range: u64,
+}
+
+impl Builder {
+	pub fn chain_id(self, chain_id: u64) -> Self {
+		Self { chain_id, ..self }
+	}
+
+	pub fn max_log_block_range(self, max_log_block_range: u64) -> Self {
+		Self { max_log_block_range, ..self }
+	}
+
+	pub fn build(self) -> Handle {
+		let state = Arc::new(Mutex::new(State::new(self.chain_id, self.max_log_block_range)));
+		let server = Server::new(state.clone());
+		let mut io = IoHandler::default();
+		io.extend_with(server.to_delegate());
+
+		let rpc_server = ServerBuilder::new(io)
+			.threads(1)
+			.start_http(&"127.0.0.1:0".parse().unwrap())
+			.unwrap();
+
+		let close_handle = rpc_server.close_handle();
+		let port = rpc_server.address().port();
+
+		thread::spawn(|| rpc_server.wait());
+
+		for i in 0.. {
+			match reqwest::blocking::get(format!("http://127.0.0.1:{port}/")) {
+				Ok(_) => break,
+				Err(err) =>
+					if i == 400 {
+						panic!("mock LAOS server failed to start: {err}");
+					},
+			}
+
+			thread::sleep(Duration::from_millis(25));
+		}
+
+		Handle { close_handle: Some(close_handle), port, state }
+	}
+}
+
+pub fn spawn() -> Handle {
+	builder().build()
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CallRequest {
+	pub to: Option<H160>,
+	#[serde(alias = "input")]
+	pub data: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum OneOrMany<T> {
+	One(T),
+	Many(Vec<T>),
+}
+
+impl<T: PartialEq> OneOrMany<T> {
+	fn matches(&self, value: &T) -> bool {
+		match self {
+			Self::One(one) => one == value,
+			Self::Many(many) => many.contains(value),
+		}
+	}
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Filter {
+	pub address: Option<OneOrMany<H160>>,
+	pub from_block: Option<String>,
+	pub to_block: Option<String>,
+	pub topics: Option<Vec<Option<OneOrMany<H256>>>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Log {
+	pub address: H160,
+	pub topics: Vec<H256>,
+	pub data: String,
+	pub block_number: U256,
+	pub transaction_hash: H256,
+	pub transaction_index: U256,
+	pub log_index: U256,
+	pub removed: bool,
+}
+
+pub struct Handle {
+	close_handle: Option<CloseHandle>,
+	port: u16,
+	state: Arc<Mutex<State>>,
+}
+
+impl Handle {
+	pub fn url(&self) -> String {
+		format!("http://127.0.0.1:{}", self.port)
+	}
+
+	pub fn state(&self) -> MutexGuard<State> {
+		self.state.lock().unwrap()
+	}
+
+	pub fn block_number(&self) -> u64 {
+		self.state().block_number
+	}
+
+	/// Produces `n` empty blocks.
+	pub fn mine_blocks(&self, n: u64) {
+		self.state().block_number += n;
+	}
+
+	/// Creates a collection owned by `owner`, returning its address.
+	pub fn create_collection(&self, owner: H160) -> H160 {
+		self.state().create_collection(owner)
+	}
+
+	/// Deploys `code` at `address`, which is not a collection.
+	pub fn deploy(&self, address: H160, code: &[u8]) {
+		self.state().code.insert(address, code.to_vec());
+	}
+
+	/// Deploys a contract at `address` that is not a collection but answers `owner()` with
+	/// `owner`, like any `Ownable` contract.
+	pub fn deploy_ownable(&self, address: H160, owner: H160) {
+		let mut state = self.state();
+		state.code.insert(address, REVERT_BYTECODE.to_vec());
+		state.owners.insert(address, owner);
+	}
+
+	/// Mints the token of `slot` to `to` in a new block, returning its token id.
+	#[track_caller]
+	pub fn mint(&self, collection: H160, to: H160, slot: u128, token_uri: &str) -> U256 {
+		self.state().mint(collection, to, slot, token_uri)
+	}
+
+	/// Points `token_id` to `token_uri` in a new block.
+	#[track_caller]
+	pub fn evolve(&self, collection: H160, token_id: U256, token_uri: &str) {
+		self.state().evolve(collection, token_id, token_uri)
+	}
+
+	pub fn logs(&self) -> Vec<Log> {
+		self.state().logs.clone()
+	}
+}
+
+impl Drop for Handle {
+	fn drop(&mut self) {
+		self.close_handle.take().unwrap().close();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn token_id_packs_slot_above_initial_owner() {
+		assert_eq!(token_id(0, H160::zero()), U256::zero());
+
+		assert_eq!(
+			token_id(0, H160::repeat_byte(0xff)),
+			U256::from_big_endian(H160::repeat_byte(0xff).as_bytes()),
+		);
+
+		assert_eq!(token_id(1, H160::zero()), U256::one() << 160);
+
+		let token_id = token_id(u128::from(u64::MAX) + 2, H160::repeat_byte(0x11));
+
+		assert_eq!(
+			hex::encode(token_id.to_big_endian()),
+			format!("000000010000000000000001{}", "11".repeat(20)),
+		);
+	}
+
+	#[test]
+	fn collection_addresses() {
+		assert_eq!(
+			format!("{:x}", collection_address(0x47)),
+			"fffffffffffffffffffffffe0000000000000047",
+		);
+	}
+
+	#[test]
+	fn event_topics() {
+		assert_eq!(
+			hex::encode(event_topic("Transfer(address,address,uint256)")),
+			"ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+		);
+	}
+
+	#[test]
+	fn selectors() {
+		assert_eq!(hex::encode(selector("tokenURI(uint256)")), "c87b56dd");
+		assert_eq!(hex::encode(selector("ownerOf(uint256)")), "6352211e");
+		assert_eq!(hex::encode(selector("owner()")), "8da5cb5b");
+	}
+}
@@ -0,0 +1,226 @@
+// Copyright 2023-2024 Freeverse.io
+// This file is part of LAOS.
+
+// LAOS is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// LAOS is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+
+pub(crate) struct Server {
+	pub(crate) state: Arc<Mutex<State>>,
+}
+
+impl Server {
+	pub(crate) fn new(state: Arc<Mutex<State>>) -> Self {
+		Self { state }
+	}
+
+	fn state(&self) -> MutexGuard<State> {
+		self.state.lock().unwrap()
+	}
+
+	fn reverted() -> jsonrpc_core::Error {
+		jsonrpc_core::Error {
+			code: jsonrpc_core::types::error::ErrorCode::ServerError(3),
+			message: "execution reverted".into(),
+			data: None,
+		}
+	}
+}
+
+impl Api for Server {
+	fn chain_id(&self) -> Result<U256, jsonrpc_core::Error> {
+		Ok(self.state().chain_id.into())
+	}
+
+	fn block_number(&self) -> Result<U256, jsonrpc_core::Error> {
+		Ok(self.state().block_number.into())
+	}
+
+	fn get_code(
+		&self,
+		address: H160,
+		_block: Option<String>,
+	) -> Result<String, jsonrpc_core::Error> {
+		let code = self.state().code.get(&address).cloned().unwrap_or_default();
+
+		Ok(format!("0x{}", hex::encode(code)))
+	}
+
+	fn call(
+		&self,
+		call: CallRequest,
+		_block: Option<String>,
+	) -> Result<String, jsonrpc_core::Error> {
+		let to = call.to.ok_or_else(|| jsonrpc_core::Error::invalid_params("missing `to`"))?;
+
+		let data = hex::decode(call.data.unwrap_or_default().trim_start_matches("0x"))
+			.map_err(|err| jsonrpc_core::Error::invalid_params(format!("invalid data: {err}")))?;
+
+		let state = self.state();
+
+		let Some(function) = data.get(..4) else {
+			return Err(Self::reverted());
+		};
+
+		// only collections and `owner()` of ownable contracts are emulated, calls to anything
+		// else return no data
+		let Some(collection) = state.collections.get(&to) else {
+			return match state.owners.get(&to) {
+				Some(owner) if function == selector("owner()") =>
+					Ok(format!("0x{}", hex::encode(abi::address(*owner)))),
+				_ => Ok("0x".into()),
+			};
+		};
+
+		let output = if function == selector("tokenURI(uint256)") {
+			let token_id = abi::uint_argument(&data).ok_or_else(Self::reverted)?;
+
+			abi::string(collection.token_uris.get(&token_id).ok_or_else(Self::reverted)?)
+		} else if function == selector("ownerOf(uint256)") {
+			// the owner of a token on LAOS is always its initial owner, encoded in its token id
+			let token_id = abi::uint_argument(&data).ok_or_else(Self::reverted)?;
+
+			abi::address(H160::from_slice(&abi::uint(token_id)[12..])).to_vec()
+		} else if function == selector("owner()") {
+			abi::address(collection.owner).to_vec()
+		} else {
+			return Err(Self::reverted());
+		};
+
+		Ok(format!("0x{}", hex::encode(output)))
+	}
+
+	fn get_logs(&self, filter: Filter) -> Result<Vec<Log>, jsonrpc_core::Error> {
+		self.state().logs(&filter).map_err(jsonrpc_core::Error::invalid_params)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn call(server: &Server, to: H160, function: &str, argument: Option<U256>) -> String {
+		let mut data = selector(function).to_vec();
+		data.extend(argument.map(abi::uint).unwrap_or_default());
+
+		server
+			.call(
+				CallRequest { to: Some(to), data: Some(format!("0x{}", hex::encode(data))) },
+				None,
+			)
+			.unwrap()
+	}
+
+	fn server() -> (Server, H160, U256) {
+		let mut state = State::new(LAOS_CHAIN_ID, MAX_LOG_BLOCK_RANGE);
+
+		let collection = state.create_collection(H160::repeat_byte(0xcc));
+
+		let token_id = state.mint(collection, H160::repeat_byte(0x11), 7, "ipfs://token");
+
+		(Server::new(Arc::new(Mutex::new(state))), collection, token_id)
+	}
+
+	#[test]
+	fn token_uri_is_abi_encoded_string() {
+		let (server, collection, token_id) = server();
+
+		assert_eq!(
+			call(&server, collection, "tokenURI(uint256)", Some(token_id)),
+			format!(
+				"0x{:064x}{:064x}{}{}",
+				0x20,
+				"ipfs://token".len(),
+				hex::encode("ipfs://token"),
+				"0".repeat(64 - "ipfs://token".len() * 2),
+			),
+		);
+	}
+
+	#[test]
+	fn owner_of_is_initial_owner_encoded_in_token_id() {
+		let (server, collection, token_id) = server();
+
+		assert_eq!(
+			call(&server, collection, "ownerOf(uint256)", Some(token_id)),
+			format!("0x{}{}", "00".repeat(12), "11".repeat(20)),
+		);
+
+		// unminted tokens are owned by the initial owner as well
+		assert_eq!(
+			call(
+				&server,
+				collection,
+				"ownerOf(uint256)",
+				Some(crate::token_id(3, H160::repeat_byte(0x22)))
+			),
+			format!("0x{}{}", "00".repeat(12), "22".repeat(20)),
+		);
+	}
+
+	#[test]
+	fn owner_is_collection_owner() {
+		let (server, collection, _) = server();
+
+		assert_eq!(
+			call(&server, collection, "owner()", None),
+			format!("0x{}{}", "00".repeat(12), "cc".repeat(20)),
+		);
+	}
+
+	#[test]
+	fn unminted_token_uri_reverts() {
+		let (server, collection, _) = server();
+
+		let data = [selector("tokenURI(uint256)").as_slice(), &abi::uint(U256::one())].concat();
+
+		let err = server
+			.call(
+				CallRequest {
+					to: Some(collection),
+					data: Some(format!("0x{}", hex::encode(data))),
+				},
+				None,
+			)
+			.unwrap_err();
+
+		assert_eq!(err.message, "execution reverted");
+	}
+
+	#[test]
+	fn ownable_contracts_answer_owner() {
+		let (server, _, token_id) = server();
+
+		let ownable = H160::repeat_byte(0xaa);
+
+		server.state().owners.insert(ownable, H160::repeat_byte(0xbb));
+
+		assert_eq!(
+			call(&server, ownable, "owner()", None),
+			format!("0x{}{}", "00".repeat(12), "bb".repeat(20)),
+		);
+
+		assert_eq!(call(&server, ownable, "tokenURI(uint256)", Some(token_id)), "0x");
+	}
+
+	#[test]
+	fn calls_to_non_collections_return_no_data() {
+		let (server, _, token_id) = server();
+
+		assert_eq!(
+			call(&server, H160::repeat_byte(0xaa), "ownerOf(uint256)", Some(token_id)),
+			"0x"
+		);
+	}
+}
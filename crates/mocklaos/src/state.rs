@@ -0,0 +1,320 @@
+// Copyright 2023-2024 Freeverse.io
+// This file is part of LAOS.
+
+// LAOS is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// LAOS is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+
+#[derive(Debug, Default)]
+pub struct Collection {
+	pub owner: H160,
+	pub token_uris: BTreeMap<U256, String>,
+}
+
+#[derive(Debug)]
+pub struct State {
+	pub block_number: u64,
+	pub chain_id: u64,
+	pub code: BTreeMap<H160, Vec<u8>>,
+	pub collections: BTreeMap<H160, Collection>,
+	pub logs: Vec<Log>,
+	pub max_log_block_range: u64,
+	pub owners: BTreeMap<H160, H160>,
+}
+
+impl State {
+	pub(crate) fn new(chain_id: u64, max_log_block_range: u64) -> Self {
+		Self {
+			block_number: 0,
+			chain_id,
+			code: BTreeMap::new(),
+			collections: BTreeMap::new(),
+			logs: Vec::new(),
+			max_log_block_range,
+			owners: BTreeMap::new(),
+		}
+	}
+
+	pub(crate) fn create_collection(&mut self, owner: H160) -> H160 {
+		let address = collection_address(self.collections.len().try_into().unwrap());
+
+		self.code.insert(address, REVERT_BYTECODE.to_vec());
+		self.collections.insert(address, Collection { owner, ..Default::default() });
+
+		address
+	}
+
+	#[track_caller]
+	pub(crate) fn mint(&mut self, collection: H160, to: H160, slot: u128, token_uri: &str) -> U256 {
+		let token_id = token_id(slot, to);
+
+		let token_uris = &mut self.collection_mut(collection).token_uris;
+
+		assert!(!token_uris.contains_key(&token_id), "token {token_id} already minted");
+
+		token_uris.insert(token_id, token_uri.into());
+
+		let mut data = abi::uint(U256::from(slot)).to_vec();
+		data.extend(abi::uint(token_id));
+		data.extend(abi::uint(U256::from(96u64)));
+		data.extend(abi::string_tail(token_uri));
+
+		self.push_log(
+			collection,
+			vec![event_topic(MINTED_WITH_EXTERNAL_URI), H256::from(abi::address(to))],
+			data,
+		);
+
+		token_id
+	}
+
+	#[track_caller]
+	pub(crate) fn evolve(&mut self, collection: H160, token_id: U256, token_uri: &str) {
+		let token_uris = &mut self.collection_mut(collection).token_uris;
+
+		assert!(token_uris.contains_key(&token_id), "token {token_id} not minted");
+
+		token_uris.insert(token_id, token_uri.into());
+
+		self.push_log(
+			collection,
+			vec![event_topic(EVOLVED_WITH_EXTERNAL_URI), H256::from(abi::uint(token_id))],
+			abi::string(token_uri),
+		);
+	}
+
+	#[track_caller]
+	fn collection_mut(&mut self, collection: H160) -> &mut Collection {
+		self.collections.get_mut(&collection).expect("unknown collection")
+	}
+
+	/// Emits a log in a new block containing a single transaction.
+	fn push_log(&mut self, address: H160, topics: Vec<H256>, data: Vec<u8>) {
+		self.block_number += 1;
+
+		let block_number = U256::from(self.block_number);
+
+		self.logs.push(Log {
+			address,
+			topics,
+			data: format!("0x{}", hex::encode(data)),
+			block_number,
+			transaction_hash: H256::from(abi::uint(block_number)),
+			transaction_index: U256::zero(),
+			log_index: U256::zero(),
+			removed: false,
+		});
+	}
+
+	pub(crate) fn logs(&self, filter: &Filter) -> Result<Vec<Log>, String> {
+		let from_block = self.block(filter.from_block.as_deref(), 0)?;
+		let to_block = self.block(filter.to_block.as_deref(), self.block_number)?;
+
+		if to_block.saturating_sub(from_block) >= self.max_log_block_range {
+			return Err(format!("block range is too wide (maximum {})", self.max_log_block_range));
+		}
+
+		Ok(self
+			.logs
+			.iter()
+			.filter(|log| {
+				let block_number = log.block_number.low_u64();
+
+				let address =
+					filter.address.as_ref().map_or(true, |address| address.matches(&log.address));
+
+				let topics = filter.topics.iter().flatten().enumerate().all(|(i, topic)| {
+					topic.as_ref().map_or(true, |topic| {
+						log.topics.get(i).is_some_and(|log_topic| topic.matches(log_topic))
+					})
+				});
+
+				(from_block..=to_block).contains(&block_number) && address && topics
+			})
+			.cloned()
+			.collect())
+	}
+
+	fn block(&self, block: Option<&str>, default: u64) -> Result<u64, String> {
+		match block {
+			None => Ok(default),
+			Some("earliest") => Ok(0),
+			Some("latest" | "pending" | "safe" | "finalized") => Ok(self.block_number),
+			Some(number) => u64::from_str_radix(number.trim_start_matches("0x"), 16)
+				.map_err(|err| format!("invalid block number `{number}`: {err}")),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn filter(
+		address: Option<OneOrMany<H160>>,
+		from_block: Option<&str>,
+		to_block: Option<&str>,
+		topics: Option<Vec<Option<OneOrMany<H256>>>>,
+	) -> Filter {
+		Filter {
+			address,
+			from_block: from_block.map(Into::into),
+			to_block: to_block.map(Into::into),
+			topics,
+		}
+	}
+
+	fn block_numbers(state: &State, filter: Filter) -> Vec<u64> {
+		state
+			.logs(&filter)
+			.unwrap()
+			.iter()
+			.map(|log| log.block_number.low_u64())
+			.collect()
+	}
+
+	fn state() -> (State, H160, H160) {
+		let mut state = State::new(LAOS_CHAIN_ID, MAX_LOG_BLOCK_RANGE);
+
+		let a = state.create_collection(H160::zero());
+		let b = state.create_collection(H160::zero());
+
+		let token_id = state.mint(a, H160::repeat_byte(1), 0, "ipfs://a/0");
+		state.mint(b, H160::repeat_byte(2), 0, "ipfs://b/0");
+		state.evolve(a, token_id, "ipfs://a/0/evolved");
+
+		(state, a, b)
+	}
+
+	#[test]
+	fn logs_are_filtered_by_block_range() {
+		let (state, _, _) = state();
+
+		assert_eq!(block_numbers(&state, filter(None, None, None, None)), [1, 2, 3]);
+
+		assert_eq!(
+			block_numbers(&state, filter(None, Some("earliest"), Some("latest"), None)),
+			[1, 2, 3],
+		);
+
+		assert_eq!(block_numbers(&state, filter(None, Some("0x2"), None, None)), [2, 3]);
+
+		assert_eq!(block_numbers(&state, filter(None, Some("0x1"), Some("0x2"), None)), [1, 2]);
+
+		assert_eq!(block_numbers(&state, filter(None, Some("0x3"), Some("0x2"), None)), [0; 0]);
+	}
+
+	#[test]
+	fn logs_are_filtered_by_address() {
+		let (state, a, b) = state();
+
+		assert_eq!(
+			block_numbers(&state, filter(Some(OneOrMany::One(a)), None, None, None)),
+			[1, 3]
+		);
+
+		assert_eq!(block_numbers(&state, filter(Some(OneOrMany::One(b)), None, None, None)), [2]);
+
+		assert_eq!(
+			block_numbers(&state, filter(Some(OneOrMany::Many(vec![a, b])), None, None, None)),
+			[1, 2, 3],
+		);
+
+		assert_eq!(
+			block_numbers(
+				&state,
+				filter(Some(OneOrMany::One(H160::repeat_byte(0xaa))), None, None, None)
+			),
+			[0; 0],
+		);
+	}
+
+	#[test]
+	fn logs_are_filtered_by_topics() {
+		let (state, _, _) = state();
+
+		let minted = event_topic(MINTED_WITH_EXTERNAL_URI);
+		let evolved = event_topic(EVOLVED_WITH_EXTERNAL_URI);
+
+		assert_eq!(
+			block_numbers(
+				&state,
+				filter(None, None, None, Some(vec![Some(OneOrMany::One(minted))]))
+			),
+			[1, 2],
+		);
+
+		assert_eq!(
+			block_numbers(
+				&state,
+				filter(None, None, None, Some(vec![Some(OneOrMany::Many(vec![minted, evolved]))]))
+			),
+			[1, 2, 3],
+		);
+
+		assert_eq!(
+			block_numbers(
+				&state,
+				filter(
+					None,
+					None,
+					None,
+					Some(vec![
+						None,
+						Some(OneOrMany::One(H256::from(abi::address(H160::repeat_byte(2)))))
+					])
+				)
+			),
+			[2],
+		);
+
+		// logs without a topic at a filtered position never match
+		assert_eq!(
+			block_numbers(
+				&state,
+				filter(None, None, None, Some(vec![None, None, Some(OneOrMany::One(minted))]))
+			),
+			[0; 0],
+		);
+	}
+
+	#[test]
+	fn wide_block_ranges_are_rejected() {
+		let (mut state, _, _) = state();
+
+		state.max_log_block_range = 2;
+
+		assert_eq!(block_numbers(&state, filter(None, Some("0x1"), Some("0x2"), None)), [1, 2]);
+
+		assert_eq!(
+			state.logs(&filter(None, Some("0x1"), Some("0x3"), None)).unwrap_err(),
+			"block range is too wide (maximum 2)",
+		);
+
+		assert_eq!(
+			state.logs(&filter(None, Some("earliest"), Some("latest"), None)).unwrap_err(),
+			"block range is too wide (maximum 2)",
+		);
+	}
+
+	#[test]
+	fn invalid_block_numbers_are_rejected() {
+		let (state, _, _) = state();
+
+		assert_eq!(
+			state.logs(&filter(None, Some("0xzz"), None, None)).unwrap_err(),
+			"invalid block number `0xzz`: invalid digit found in string",
+		);
+	}
+}
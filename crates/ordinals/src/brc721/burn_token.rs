@@ -0,0 +1,159 @@
+use bitcoin::{opcodes, script::PushBytes, ScriptBuf};
+use sp_core::H160;
+
+use crate::{
+	varint::{self},
+	Brc721CollectionId,
+};
+
+use super::{
+	bitcoin_script::{expect_opcode, expect_push_bytes, BitcoinScriptError},
+	operations::Brc721Operation,
+	token_id::Brc721TokenId,
+	BRC721_INIT_CODE,
+};
+
+/// Retires a token, so it can be bridged out to LAOS.
+#[derive(Clone, PartialEq, Debug)]
+pub struct BurnToken {
+	pub collection_id: Brc721CollectionId,
+	pub token_id: Brc721TokenId,
+}
+
+/// Operation byte, collection id, slot and initial owner.
+const MIN_BUFFER_SIZE: usize = 24;
+
+impl From<BurnToken> for ScriptBuf {
+	fn from(burn_token: BurnToken) -> Self {
+		let mut buffer = Vec::<u8>::new();
+		buffer.push(Brc721Operation::BurnToken as u8);
+		buffer.extend_from_slice(&burn_token.collection_id.to_leb128());
+		varint::encode_to_vec(burn_token.token_id.slot, &mut buffer);
+		buffer.extend_from_slice(burn_token.token_id.initial_owner.as_bytes());
+
+		let mut script = ScriptBuf::new();
+		script.push_opcode(opcodes::all::OP_RETURN);
+		script.push_opcode(BRC721_INIT_CODE);
+		let buffer: &PushBytes = buffer.as_slice().try_into().unwrap();
+		script.push_slice(buffer);
+		script
+	}
+}
+
+impl TryFrom<ScriptBuf> for BurnToken {
+	type Error = BitcoinScriptError;
+	fn try_from(payload: ScriptBuf) -> Result<Self, BitcoinScriptError> {
+		let mut instructions = payload.instructions();
+
+		expect_opcode(&mut instructions, opcodes::all::OP_RETURN, "OP_RETURN")?;
+		expect_opcode(&mut instructions, BRC721_INIT_CODE, "BRC721_INIT_CODE")?;
+
+		let buffer = expect_push_bytes(&mut instructions, "Burn token operation")?;
+
+		if buffer.len() < MIN_BUFFER_SIZE {
+			return Err(BitcoinScriptError::InvalidLength("script is too short".to_string()));
+		}
+
+		if buffer[0] != Brc721Operation::BurnToken as u8 {
+			return Err(BitcoinScriptError::UnexpectedInstruction);
+		}
+
+		let mut buffer = buffer[1..].to_vec();
+		let collection_id = Brc721CollectionId::from_leb128(&mut buffer).map_err(
+			|e: super::collection_id::Error| {
+				BitcoinScriptError::Decode(format!("{} while extracting collection_id", e))
+			},
+		)?;
+
+		let (slot, consumed) = varint::decode(&buffer)
+			.map_err(|e| BitcoinScriptError::Decode(format!("{} while extracting slot", e)))?;
+		buffer.drain(0..consumed);
+
+		if buffer.len() != 20 {
+			return Err(BitcoinScriptError::InvalidLength(
+				"initial owner must be 20 bytes".to_string(),
+			));
+		}
+
+		let initial_owner = H160::from_slice(&buffer);
+
+		let token_id = Brc721TokenId::new(slot, initial_owner).ok_or_else(|| {
+			BitcoinScriptError::Decode(format!("{} while extracting slot", varint::Error::Overflow))
+		})?;
+
+		Ok(BurnToken { collection_id, token_id })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::str::FromStr;
+
+	fn burn_token() -> BurnToken {
+		BurnToken {
+			collection_id: Brc721CollectionId::from_str("5:7").unwrap(),
+			token_id: Brc721TokenId { slot: 3, initial_owner: H160::repeat_byte(0x2a) },
+		}
+	}
+
+	#[test]
+	fn script_from_burn_token_and_back() {
+		let command = burn_token();
+		let encoded = ScriptBuf::from(command.clone());
+
+		assert_eq!(
+			"6a5f1803050703\
+			2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a",
+			encoded.to_hex_string()
+		);
+
+		assert_eq!(BurnToken::try_from(encoded).unwrap(), command);
+	}
+
+	#[test]
+	fn burn_token_from_script_fails_short_script() {
+		let mut script = ScriptBuf::new();
+		script.push_opcode(opcodes::all::OP_RETURN);
+		script.push_opcode(BRC721_INIT_CODE);
+		let buffer = vec![Brc721Operation::BurnToken as u8];
+		let buffer: &PushBytes = buffer.as_slice().try_into().unwrap();
+		script.push_slice(buffer);
+
+		assert_eq!(
+			BurnToken::try_from(script).unwrap_err().to_string(),
+			"Invalid length: `script is too short`"
+		);
+	}
+
+	#[test]
+	fn burn_token_from_script_fails_trailing_bytes() {
+		let mut buffer = ScriptBuf::from(burn_token()).into_bytes()[3..].to_vec();
+		buffer.push(0);
+
+		let mut script = ScriptBuf::new();
+		script.push_opcode(opcodes::all::OP_RETURN);
+		script.push_opcode(BRC721_INIT_CODE);
+		let buffer: &PushBytes = buffer.as_slice().try_into().unwrap();
+		script.push_slice(buffer);
+
+		assert_eq!(
+			BurnToken::try_from(script).unwrap_err().to_string(),
+			"Invalid length: `initial owner must be 20 bytes`"
+		);
+	}
+
+	#[test]
+	fn burn_token_from_script_fails_other_operation() {
+		let script = ScriptBuf::from(crate::brc721::rebase_token::RebaseToken {
+			collection_id: Brc721CollectionId::from_str("5:7").unwrap(),
+			token_id: Brc721TokenId { slot: 3, initial_owner: H160::repeat_byte(0x2a) },
+			token_uri: String::new(),
+		});
+
+		assert_eq!(
+			BurnToken::try_from(script).unwrap_err(),
+			BitcoinScriptError::UnexpectedInstruction
+		);
+	}
+}
@@ -1,9 +1,118 @@
+use bitcoin::{opcodes, script::Instruction, ScriptBuf};
+
+use super::BRC721_INIT_CODE;
+
 /// Byte to identify which operation of brc721 is used. Just a fancy name to don't remember the
 /// numerical byte of each operation. Only fieldless variants are allowed, leading to a compile
 /// error otherwise.
 #[derive(Debug, Clone, PartialEq)]
 #[repr(u8)]
-pub(crate) enum Brc721Operation {
+pub enum Brc721Operation {
 	RegisterCollection = 0x00,
 	RegisterOwnership = 0x01,
+	RebaseToken = 0x02,
+	BurnToken = 0x03,
+}
+
+impl Brc721Operation {
+	/// Returns the operation encoded in a BRC721 script, without decoding its payload.
+	///
+	/// Returns `None` if the script is not a BRC721 script or the operation byte is unknown.
+	pub fn from_script(script: &ScriptBuf) -> Option<Self> {
+		let mut instructions = script.instructions();
+
+		match instructions.next() {
+			Some(Ok(Instruction::Op(opcodes::all::OP_RETURN))) => {},
+			_ => return None,
+		}
+
+		match instructions.next() {
+			Some(Ok(Instruction::Op(op))) if op == BRC721_INIT_CODE => {},
+			_ => return None,
+		}
+
+		match instructions.next() {
+			Some(Ok(Instruction::PushBytes(bytes))) =>
+				bytes.as_bytes().first().and_then(|byte| Self::try_from(*byte).ok()),
+			_ => None,
+		}
+	}
+}
+
+impl TryFrom<u8> for Brc721Operation {
+	type Error = u8;
+
+	fn try_from(byte: u8) -> Result<Self, Self::Error> {
+		match byte {
+			0x00 => Ok(Self::RegisterCollection),
+			0x01 => Ok(Self::RegisterOwnership),
+			0x02 => Ok(Self::RebaseToken),
+			0x03 => Ok(Self::BurnToken),
+			other => Err(other),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		brc721::{
+			burn_token::BurnToken,
+			rebase_token::RebaseToken,
+			register_ownership::{RegisterOwnership, SlotsBundle},
+		},
+		Brc721CollectionId, Brc721TokenId, RegisterCollection,
+	};
+
+	#[test]
+	fn from_script_register_collection() {
+		let script = RegisterCollection::default().as_script();
+		assert_eq!(
+			Brc721Operation::from_script(&script),
+			Some(Brc721Operation::RegisterCollection)
+		);
+	}
+
+	#[test]
+	fn from_script_register_ownership() {
+		let script = ScriptBuf::from(RegisterOwnership {
+			collection_id: Brc721CollectionId { block: 1, tx: 1 },
+			slots_bundles: vec![SlotsBundle(vec![0..=0])],
+		});
+		assert_eq!(Brc721Operation::from_script(&script), Some(Brc721Operation::RegisterOwnership));
+	}
+
+	#[test]
+	fn from_script_rebase_token() {
+		let script = ScriptBuf::from(RebaseToken {
+			collection_id: Brc721CollectionId { block: 1, tx: 1 },
+			token_id: Brc721TokenId::default(),
+			token_uri: String::new(),
+		});
+		assert_eq!(Brc721Operation::from_script(&script), Some(Brc721Operation::RebaseToken));
+	}
+
+	#[test]
+	fn from_script_burn_token() {
+		let script = ScriptBuf::from(BurnToken {
+			collection_id: Brc721CollectionId { block: 1, tx: 1 },
+			token_id: Brc721TokenId::default(),
+		});
+		assert_eq!(Brc721Operation::from_script(&script), Some(Brc721Operation::BurnToken));
+	}
+
+	#[test]
+	fn from_script_unknown_operation() {
+		let script = ScriptBuf::from_bytes(hex::decode("6a5f01ff").unwrap());
+		assert_eq!(Brc721Operation::from_script(&script), None);
+	}
+
+	#[test]
+	fn from_script_not_brc721() {
+		let script = ScriptBuf::from_bytes(hex::decode("6a5d01ff").unwrap());
+		assert_eq!(Brc721Operation::from_script(&script), None);
+
+		assert_eq!(Brc721Operation::from_script(&ScriptBuf::new()), None);
+	}
 }
@@ -0,0 +1,176 @@
+use bitcoin::{opcodes, script::PushBytes, ScriptBuf};
+use sp_core::H160;
+
+use crate::{
+	varint::{self},
+	Brc721CollectionId,
+};
+
+use super::{
+	bitcoin_script::{expect_opcode, expect_push_bytes, BitcoinScriptError},
+	operations::Brc721Operation,
+	token_id::Brc721TokenId,
+	BRC721_INIT_CODE,
+};
+
+/// Points a token of a rebaseable collection to new metadata.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RebaseToken {
+	pub collection_id: Brc721CollectionId,
+	pub token_id: Brc721TokenId,
+	pub token_uri: String,
+}
+
+/// Operation byte, collection id, slot and initial owner.
+const MIN_BUFFER_SIZE: usize = 24;
+
+impl From<RebaseToken> for ScriptBuf {
+	fn from(rebase_token: RebaseToken) -> Self {
+		let mut buffer = Vec::<u8>::new();
+		buffer.push(Brc721Operation::RebaseToken as u8);
+		buffer.extend_from_slice(&rebase_token.collection_id.to_leb128());
+		varint::encode_to_vec(rebase_token.token_id.slot, &mut buffer);
+		buffer.extend_from_slice(rebase_token.token_id.initial_owner.as_bytes());
+		buffer.extend_from_slice(rebase_token.token_uri.as_bytes());
+
+		let mut script = ScriptBuf::new();
+		script.push_opcode(opcodes::all::OP_RETURN);
+		script.push_opcode(BRC721_INIT_CODE);
+		let buffer: &PushBytes = buffer.as_slice().try_into().unwrap();
+		script.push_slice(buffer);
+		script
+	}
+}
+
+impl TryFrom<ScriptBuf> for RebaseToken {
+	type Error = BitcoinScriptError;
+	fn try_from(payload: ScriptBuf) -> Result<Self, BitcoinScriptError> {
+		let mut instructions = payload.instructions();
+
+		expect_opcode(&mut instructions, opcodes::all::OP_RETURN, "OP_RETURN")?;
+		expect_opcode(&mut instructions, BRC721_INIT_CODE, "BRC721_INIT_CODE")?;
+
+		let buffer = expect_push_bytes(&mut instructions, "Rebase token operation")?;
+
+		if buffer.len() < MIN_BUFFER_SIZE {
+			return Err(BitcoinScriptError::InvalidLength("script is too short".to_string()));
+		}
+
+		if buffer[0] != Brc721Operation::RebaseToken as u8 {
+			return Err(BitcoinScriptError::UnexpectedInstruction);
+		}
+
+		let mut buffer = buffer[1..].to_vec();
+		let collection_id = Brc721CollectionId::from_leb128(&mut buffer).map_err(
+			|e: super::collection_id::Error| {
+				BitcoinScriptError::Decode(format!("{} while extracting collection_id", e))
+			},
+		)?;
+
+		let (slot, consumed) = varint::decode(&buffer)
+			.map_err(|e| BitcoinScriptError::Decode(format!("{} while extracting slot", e)))?;
+		buffer.drain(0..consumed);
+
+		if buffer.len() < 20 {
+			return Err(BitcoinScriptError::InvalidLength("initial owner is too short".to_string()));
+		}
+
+		let initial_owner = H160::from_slice(&buffer[..20]);
+
+		let token_id = Brc721TokenId::new(slot, initial_owner).ok_or_else(|| {
+			BitcoinScriptError::Decode(format!("{} while extracting slot", varint::Error::Overflow))
+		})?;
+
+		let token_uri = String::from_utf8(buffer[20..].to_vec())
+			.map_err(|e| BitcoinScriptError::Decode(format!("{} while extracting token_uri", e)))?;
+
+		Ok(RebaseToken { collection_id, token_id, token_uri })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::str::FromStr;
+
+	fn rebase_token() -> RebaseToken {
+		RebaseToken {
+			collection_id: Brc721CollectionId::from_str("5:7").unwrap(),
+			token_id: Brc721TokenId { slot: 3, initial_owner: H160::repeat_byte(0x2a) },
+			token_uri: "ipfs://foo".into(),
+		}
+	}
+
+	#[test]
+	fn script_from_rebase_token_and_back() {
+		let command = rebase_token();
+		let encoded = ScriptBuf::from(command.clone());
+
+		assert_eq!(
+			"6a5f2202050703\
+			2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+			697066733a2f2f666f6f",
+			encoded.to_hex_string()
+		);
+
+		assert_eq!(RebaseToken::try_from(encoded).unwrap(), command);
+	}
+
+	#[test]
+	fn rebase_token_with_empty_token_uri() {
+		let command = RebaseToken { token_uri: String::new(), ..rebase_token() };
+		let encoded = ScriptBuf::from(command.clone());
+		assert_eq!(RebaseToken::try_from(encoded).unwrap(), command);
+	}
+
+	#[test]
+	fn rebase_token_from_script_fails_short_script() {
+		let mut script = ScriptBuf::new();
+		script.push_opcode(opcodes::all::OP_RETURN);
+		script.push_opcode(BRC721_INIT_CODE);
+		let buffer = vec![Brc721Operation::RebaseToken as u8];
+		let buffer: &PushBytes = buffer.as_slice().try_into().unwrap();
+		script.push_slice(buffer);
+
+		assert_eq!(
+			RebaseToken::try_from(script).unwrap_err().to_string(),
+			"Invalid length: `script is too short`"
+		);
+	}
+
+	#[test]
+	fn rebase_token_from_script_fails_other_operation() {
+		let script = ScriptBuf::from(crate::brc721::register_ownership::RegisterOwnership {
+			collection_id: Brc721CollectionId::from_str("5:7").unwrap(),
+			slots_bundles: vec![crate::brc721::register_ownership::SlotsBundle(vec![
+				0..=1,
+				2..=3,
+				4..=5,
+				6..=7,
+				8..=9,
+				10..=11,
+				12..=13,
+				14..=15,
+				16..=17,
+				18..=19,
+			])],
+		});
+
+		assert_eq!(
+			RebaseToken::try_from(script).unwrap_err(),
+			BitcoinScriptError::UnexpectedInstruction
+		);
+	}
+
+	#[test]
+	fn rebase_token_from_script_fails_invalid_token_uri() {
+		let mut script = ScriptBuf::from(rebase_token()).into_bytes();
+		// replace the last character of the token uri with an invalid utf-8 byte
+		*script.last_mut().unwrap() = 0xff;
+
+		assert!(RebaseToken::try_from(ScriptBuf::from_bytes(script))
+			.unwrap_err()
+			.to_string()
+			.ends_with("while extracting token_uri`"));
+	}
+}
@@ -0,0 +1,154 @@
+// Copyright 2023-2024 Freeverse.io
+// This file is part of LAOS.
+
+// LAOS is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// LAOS is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use sp_core::{H160, U256};
+
+/// Identifier of a BRC721 token within its collection.
+///
+/// As in LAOS, the token id is a 256-bit number whose 96 most significant bits are the slot and
+/// whose 160 least significant bits are the initial owner. It is displayed as a decimal number.
+#[derive(
+	Debug,
+	PartialEq,
+	Copy,
+	Clone,
+	Hash,
+	Eq,
+	Ord,
+	PartialOrd,
+	Default,
+	DeserializeFromStr,
+	SerializeDisplay,
+)]
+pub struct Brc721TokenId {
+	pub slot: u128,
+	pub initial_owner: H160,
+}
+
+impl Brc721TokenId {
+	/// Number of bits of the slot.
+	pub const SLOT_BITS: u32 = 96;
+
+	pub fn new(slot: u128, initial_owner: H160) -> Option<Brc721TokenId> {
+		if slot >= 1 << Self::SLOT_BITS {
+			return None;
+		}
+
+		Some(Brc721TokenId { slot, initial_owner })
+	}
+
+	pub fn to_u256(self) -> U256 {
+		(U256::from(self.slot) << 160) | U256::from_big_endian(self.initial_owner.as_bytes())
+	}
+
+	pub fn from_u256(value: U256) -> Self {
+		let bytes = value.to_big_endian();
+
+		Brc721TokenId {
+			slot: (value >> 160).low_u128(),
+			initial_owner: H160::from_slice(&bytes[12..]),
+		}
+	}
+}
+
+impl Display for Brc721TokenId {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "{}", self.to_u256())
+	}
+}
+
+impl FromStr for Brc721TokenId {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		U256::from_dec_str(s).map(Self::from_u256).map_err(|_| Error::Decimal)
+	}
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+	Decimal,
+}
+
+impl Display for Error {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Decimal => write!(f, "invalid token id: expected a 256-bit decimal number"),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn display() {
+		assert_eq!(Brc721TokenId { slot: 0, initial_owner: H160::zero() }.to_string(), "0");
+		assert_eq!(
+			Brc721TokenId { slot: 0, initial_owner: H160::from_low_u64_be(1) }.to_string(),
+			"1"
+		);
+		assert_eq!(
+			Brc721TokenId { slot: 1, initial_owner: H160::zero() }.to_string(),
+			"1461501637330902918203684832716283019655932542976"
+		);
+		assert_eq!(
+			Brc721TokenId { slot: (1 << 96) - 1, initial_owner: H160::repeat_byte(0xff) }
+				.to_string(),
+			U256::MAX.to_string()
+		);
+	}
+
+	#[test]
+	fn from_str() {
+		assert_eq!(
+			"1461501637330902918203684832716283019655932542977"
+				.parse::<Brc721TokenId>()
+				.unwrap(),
+			Brc721TokenId { slot: 1, initial_owner: H160::from_low_u64_be(1) }
+		);
+		assert_eq!("foo".parse::<Brc721TokenId>().unwrap_err(), Error::Decimal);
+	}
+
+	#[test]
+	fn round_trip() {
+		let token_id = Brc721TokenId {
+			slot: 79228162514264337593543950335,
+			initial_owner: H160::from_slice(
+				&hex::decode("4e7b5ee0272b429056a8c7de8d464c67aa17facf").unwrap(),
+			),
+		};
+		assert_eq!(token_id.to_string().parse::<Brc721TokenId>().unwrap(), token_id);
+	}
+
+	#[test]
+	fn new_rejects_slot_overflow() {
+		assert!(Brc721TokenId::new((1 << 96) - 1, H160::zero()).is_some());
+		assert!(Brc721TokenId::new(1 << 96, H160::zero()).is_none());
+	}
+
+	#[test]
+	fn serde() {
+		let token_id = Brc721TokenId { slot: 0, initial_owner: H160::from_low_u64_be(1) };
+		let json = "\"1\"";
+		assert_eq!(serde_json::to_string(&token_id).unwrap(), json);
+		assert_eq!(serde_json::from_str::<Brc721TokenId>(json).unwrap(), token_id);
+	}
+}
@@ -41,6 +41,7 @@ pub use brc721::{
 	collection::Brc721Collection,
 	collection_id::Brc721CollectionId,
 	register_collection::{RegisterCollection, COLLECTION_ADDRESS_LENGTH},
+	token_id::Brc721TokenId,
 };
 pub use cenotaph::Cenotaph;
 pub use charm::Charm;
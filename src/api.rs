@@ -16,6 +16,7 @@
 
 use super::*;
 use serde_hex::{SerHex, Strict};
+use sp_core::H160;
 
 pub use crate::{
 	subcommand::decode::RawOutput as Decode,
@@ -56,6 +57,14 @@ impl Block {
 	}
 }
 
+/// Events of an indexed block, as delivered to webhooks.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlockEvents {
+	pub block_hash: BlockHash,
+	pub block_height: u32,
+	pub events: Vec<Event>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct BlockInfo {
 	pub average_fee: u64,
@@ -255,3 +264,130 @@ pub struct AddressInfo {
 	pub sat_balance: u64,
 	pub runes_balances: Option<Vec<(SpacedRune, Decimal, Option<char>)>>,
 }
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Brc721CollectionOutput {
+	pub collection_id: Brc721CollectionId,
+	pub output: OutPoint,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Brc721RegisteredSlots {
+	pub collection_id: Brc721CollectionId,
+	pub initial_owner: H160,
+	pub slots: Vec<std::ops::RangeInclusive<u128>>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Brc721Token {
+	pub address: Option<Address<NetworkUnchecked>>,
+	/// Height the token was burned at, if it was burned.
+	pub burned: Option<u32>,
+	pub collection_id: Brc721CollectionId,
+	pub initial_owner: H160,
+	/// Output holding the token, `null` once the token is burned. Before burns were recorded
+	/// this was always an outpoint.
+	pub output: Option<OutPoint>,
+	pub pending: Vec<Brc721PendingOperation>,
+	pub slot: u128,
+	pub token_id: Brc721TokenId,
+	pub token_uri: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Brc721InitialOwner {
+	pub address: Address<NetworkUnchecked>,
+	pub initial_owner: H160,
+	pub registrations: Vec<Brc721RegisteredSlots>,
+}
+
+/// Tokens of an initial owner in a collection, held by the same output.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Brc721TokenRange {
+	pub collection_id: Brc721CollectionId,
+	pub initial_owner: H160,
+	pub output: OutPoint,
+	pub slots: std::ops::RangeInclusive<u128>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Brc721OwnerTokens {
+	pub address: Address<NetworkUnchecked>,
+	pub more: bool,
+	pub page_index: usize,
+	pub tokens: Vec<Brc721TokenRange>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Brc721Collection {
+	#[serde(flatten)]
+	pub collection: ordinals::Brc721Collection,
+	pub pending: Vec<Brc721PendingOperation>,
+}
+
+/// A BRC721 operation of an unconfirmed transaction.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Brc721PendingOperation {
+	RegisterCollection {
+		txid: Txid,
+		#[serde(rename = "LAOS_address")]
+		laos_address: H160,
+		rebaseable: bool,
+	},
+	RegisterOwnership {
+		txid: Txid,
+		collection_id: Brc721CollectionId,
+		slots_bundles: Vec<Vec<std::ops::RangeInclusive<u128>>>,
+	},
+	RebaseToken {
+		txid: Txid,
+		collection_id: Brc721CollectionId,
+		token_id: Brc721TokenId,
+		token_uri: String,
+	},
+	BurnToken {
+		txid: Txid,
+		collection_id: Brc721CollectionId,
+		token_id: Brc721TokenId,
+	},
+	Transfer {
+		txid: Txid,
+		collection_id: Brc721CollectionId,
+		initial_owner: H160,
+		slots: std::ops::RangeInclusive<u128>,
+		output: OutPoint,
+		destination: Option<OutPoint>,
+	},
+}
+
+impl Brc721PendingOperation {
+	pub fn collection_id(&self) -> Option<Brc721CollectionId> {
+		match self {
+			Self::RegisterCollection { .. } => None,
+			Self::RegisterOwnership { collection_id, .. } |
+			Self::RebaseToken { collection_id, .. } |
+			Self::BurnToken { collection_id, .. } |
+			Self::Transfer { collection_id, .. } => Some(*collection_id),
+		}
+	}
+
+	/// Returns whether the operation rebases, burns or transfers a token.
+	pub fn affects_token(
+		&self,
+		collection_id: Brc721CollectionId,
+		token_id: Brc721TokenId,
+	) -> bool {
+		match self {
+			Self::RebaseToken { collection_id: id, token_id: rebased, .. } =>
+				*id == collection_id && *rebased == token_id,
+			Self::BurnToken { collection_id: id, token_id: burned, .. } =>
+				*id == collection_id && *burned == token_id,
+			Self::Transfer { collection_id: id, initial_owner, slots, .. } =>
+				*id == collection_id &&
+					*initial_owner == token_id.initial_owner &&
+					slots.contains(&token_id.slot),
+			_ => false,
+		}
+	}
+}
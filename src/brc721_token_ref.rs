@@ -0,0 +1,69 @@
+// Copyright 2023-2024 Freeverse.io
+// This file is part of LAOS.
+
+// LAOS is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// LAOS is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+
+/// A brc721 token, written as `<COLLECTION_ID>:<TOKEN_ID>`, for example `874123:5:42`.
+#[derive(Debug, PartialEq, Clone, Copy, DeserializeFromStr)]
+pub(crate) struct Brc721TokenRef {
+	pub(crate) collection_id: Brc721CollectionId,
+	pub(crate) token_id: Brc721TokenId,
+}
+
+impl FromStr for Brc721TokenRef {
+	type Err = SnafuError;
+
+	fn from_str(input: &str) -> Result<Self, Self::Err> {
+		let error = || SnafuError::Brc721TokenParse { input: input.to_string() };
+
+		let (collection_id, token_id) = input.rsplit_once(':').ok_or_else(error)?;
+
+		Ok(Self {
+			collection_id: collection_id.parse().map_err(|_| error())?,
+			token_id: token_id.parse().map_err(|_| error())?,
+		})
+	}
+}
+
+impl Display for Brc721TokenRef {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "{}:{}", self.collection_id, self.token_id)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn from_str() {
+		let token = "874123:5:42".parse::<Brc721TokenRef>().unwrap();
+
+		assert_eq!(token.collection_id, Brc721CollectionId { block: 874123, tx: 5 });
+		assert_eq!(token.token_id, "42".parse().unwrap());
+		assert_eq!(token.to_string(), "874123:5:42");
+	}
+
+	#[test]
+	fn from_str_errors() {
+		for input in ["", "42", "874123:42", "foo:5:42", "874123:5:foo"] {
+			assert_eq!(
+				input.parse::<Brc721TokenRef>().unwrap_err().to_string(),
+				format!("Failed to parse brc721 token `{input}`"),
+			);
+		}
+	}
+}
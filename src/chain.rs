@@ -62,6 +62,23 @@ impl Chain {
 		}
 	}
 
+	/// Height of the first block whose brc721 operations are indexed. A network without a
+	/// published activation height is indexed from genesis until its height is added here.
+	pub(crate) fn first_brc721_height(self) -> u32 {
+		match self {
+			// no activation height has been published for mainnet yet
+			Self::Mainnet => 0,
+			// regtest chains are created locally, so operations may appear in any block
+			Self::Regtest => 0,
+			// no activation height has been published for signet yet
+			Self::Signet => 0,
+			// no activation height has been published for testnet3 yet
+			Self::Testnet => 0,
+			// no activation height has been published for testnet4 yet
+			Self::Testnet4 => 0,
+		}
+	}
+
 	pub(crate) fn first_rune_height(self) -> u32 {
 		Rune::first_rune_height(self.into())
 	}
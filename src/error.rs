@@ -21,6 +21,8 @@ use super::*;
 pub enum SnafuError {
 	#[snafu(display("Failed to parse address `{}`", input))]
 	AddressParse { source: bitcoin::address::error::ParseError, input: String },
+	#[snafu(display("Failed to parse brc721 token `{}`", input))]
+	Brc721TokenParse { input: String },
 	#[snafu(display("Failed to parse hash `{}`", input))]
 	HashParse { source: bitcoin::hex::HexToArrayError, input: String },
 	#[snafu(display("Failed to parse inscription ID `{}`", input))]
@@ -23,7 +23,12 @@ use self::{
 	event::Event,
 	lot::Lot,
 	reorg::Reorg,
-	updater::{RegisterCollectionValue, Updater},
+	snapshot::Snapshot,
+	updater::{
+		Brc721BurnValue, Brc721RegistrationKey, Brc721RegistrationValue, Brc721TokenKey,
+		Brc721TokenLocationValue, Brc721TokenRangeValue, RegisterCollectionValue, SlotRangeValue,
+		Updater,
+	},
 	utxo_entry::{ParsedUtxoEntry, UtxoEntry, UtxoEntryBuf},
 };
 use super::*;
@@ -52,22 +57,28 @@ use std::{
 	sync::Once,
 };
 
-pub use self::entry::RuneEntry;
+pub use self::{entry::RuneEntry, snapshot::SnapshotArchive};
 
+mod brc721_mempool;
 pub(crate) mod entry;
 pub mod event;
 mod fetcher;
 mod lot;
 mod reorg;
 mod rtx;
+mod snapshot;
 mod updater;
 mod utxo_entry;
 
 #[cfg(test)]
 pub(crate) mod testing;
 
-const SCHEMA_VERSION: u64 = 31;
+const SCHEMA_VERSION: u64 = 39;
 
+define_multimap_table! { BRC721_HEIGHT_TO_REGISTRATION, u32, Brc721RegistrationValue }
+define_multimap_table! { BRC721_INITIAL_OWNER_TO_SLOT_RANGE, Brc721RegistrationKey, SlotRangeValue }
+define_multimap_table! { BRC721_OUTPOINT_TO_TOKEN_RANGE, OutPointValue, Brc721TokenRangeValue }
+define_multimap_table! { BRC721_SCRIPT_PUBKEY_TO_OUTPOINT, &[u8], OutPointValue }
 define_multimap_table! { SAT_TO_SEQUENCE_NUMBER, u64, u32 }
 define_multimap_table! { SEQUENCE_NUMBER_TO_CHILDREN, u32, u32 }
 define_multimap_table! { SCRIPT_PUBKEY_TO_OUTPOINT, &[u8], OutPointValue }
@@ -87,8 +98,83 @@ define_table! { SEQUENCE_NUMBER_TO_SATPOINT, u32, &SatPointValue }
 define_table! { STATISTIC_TO_COUNT, u64, u64 }
 define_table! { TRANSACTION_ID_TO_RUNE, &TxidValue, u128 }
 define_table! { TRANSACTION_ID_TO_TRANSACTION, &TxidValue, &[u8] }
+define_table! { WEBHOOK_SEQUENCE_NUMBER_TO_PAYLOAD, u64, &str }
 define_table! { WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP, u32, u128 }
 define_table! { BRC721_COLLECTION_ID_TO_BRC721_COLLECTION_VALUE, Brc721CollectionIdValue, RegisterCollectionValue }
+define_table! { BRC721_OUTPOINT_TO_SCRIPT_PUBKEY, OutPointValue, &[u8] }
+define_table! { BRC721_TOKEN_TO_BURN, Brc721TokenKey, Brc721BurnValue }
+define_table! { BRC721_TOKEN_TO_LOCATION, Brc721TokenKey, Brc721TokenLocationValue }
+define_table! { BRC721_TOKEN_TO_TOKEN_URI, Brc721TokenKey, String }
+define_table! { BRC721_TXID_TO_COLLECTION_ID, &TxidValue, Brc721CollectionIdValue }
+
+/// Receives the definition of every index table from `visit_tables`.
+trait TableVisitor {
+	fn table<K: redb::Key + 'static, V: redb::Value + 'static>(
+		&mut self,
+		definition: TableDefinition<K, V>,
+	) -> Result;
+
+	fn multimap_table<K: redb::Key + 'static, V: redb::Key + 'static>(
+		&mut self,
+		definition: MultimapTableDefinition<K, V>,
+	) -> Result;
+}
+
+/// Creates the visited tables.
+impl TableVisitor for WriteTransaction {
+	fn table<K: redb::Key + 'static, V: redb::Value + 'static>(
+		&mut self,
+		definition: TableDefinition<K, V>,
+	) -> Result {
+		self.open_table(definition)?;
+		Ok(())
+	}
+
+	fn multimap_table<K: redb::Key + 'static, V: redb::Key + 'static>(
+		&mut self,
+		definition: MultimapTableDefinition<K, V>,
+	) -> Result {
+		self.open_multimap_table(definition)?;
+		Ok(())
+	}
+}
+
+/// Passes every index table to `visitor`. New tables must be added here.
+fn visit_tables(visitor: &mut impl TableVisitor) -> Result {
+	visitor.multimap_table(BRC721_HEIGHT_TO_REGISTRATION)?;
+	visitor.multimap_table(BRC721_INITIAL_OWNER_TO_SLOT_RANGE)?;
+	visitor.multimap_table(BRC721_OUTPOINT_TO_TOKEN_RANGE)?;
+	visitor.multimap_table(BRC721_SCRIPT_PUBKEY_TO_OUTPOINT)?;
+	visitor.multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+	visitor.multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
+	visitor.multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+	visitor.table(BRC721_COLLECTION_ID_TO_BRC721_COLLECTION_VALUE)?;
+	visitor.table(BRC721_OUTPOINT_TO_SCRIPT_PUBKEY)?;
+	visitor.table(BRC721_TOKEN_TO_BURN)?;
+	visitor.table(BRC721_TOKEN_TO_LOCATION)?;
+	visitor.table(BRC721_TOKEN_TO_TOKEN_URI)?;
+	visitor.table(BRC721_TXID_TO_COLLECTION_ID)?;
+	visitor.table(HEIGHT_TO_BLOCK_HEADER)?;
+	visitor.table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+	visitor.table(HOME_INSCRIPTIONS)?;
+	visitor.table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+	visitor.table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+	visitor.table(OUTPOINT_TO_RUNE_BALANCES)?;
+	visitor.table(OUTPOINT_TO_UTXO_ENTRY)?;
+	visitor.table(RUNE_ID_TO_RUNE_ENTRY)?;
+	visitor.table(RUNE_TO_RUNE_ID)?;
+	visitor.table(SAT_TO_SATPOINT)?;
+	visitor.table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+	visitor.table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
+	visitor.table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+	visitor.table(STATISTIC_TO_COUNT)?;
+	visitor.table(TRANSACTION_ID_TO_RUNE)?;
+	visitor.table(TRANSACTION_ID_TO_TRANSACTION)?;
+	visitor.table(WEBHOOK_SEQUENCE_NUMBER_TO_PAYLOAD)?;
+	visitor.table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?;
+
+	Ok(())
+}
 
 #[derive(Copy, Clone)]
 pub(crate) enum Statistic {
@@ -110,6 +196,9 @@ pub(crate) enum Statistic {
 	UnboundInscriptions = 16,
 	LastSavepointHeight = 17,
 	IndexBrc721 = 18,
+	WebhookCursor = 19,
+	IndexBrc721Only = 20,
+	FirstBrc721Height = 21,
 }
 
 impl Statistic {
@@ -128,14 +217,17 @@ impl From<Statistic> for u64 {
 pub struct Info {
 	blocks_indexed: u32,
 	branch_pages: u64,
+	chain_tip_distance: u32,
 	fragmented_bytes: u64,
 	index_file_size: u64,
 	index_path: PathBuf,
 	leaf_pages: u64,
+	max_savepoints: u32,
 	metadata_bytes: u64,
 	outputs_traversed: u64,
 	page_size: usize,
 	sat_ranges: u64,
+	savepoint_interval: u32,
 	stored_bytes: u64,
 	tables: BTreeMap<String, TableInfo>,
 	total_bytes: u64,
@@ -200,6 +292,7 @@ impl<T> BitcoinCoreRpcResultExt<T> for Result<T, bitcoincore_rpc::Error> {
 }
 
 pub struct Index {
+	brc721_mempool: Mutex<brc721_mempool::Brc721Mempool>,
 	pub(crate) client: Client,
 	database: Database,
 	durability: redb::Durability,
@@ -209,6 +302,7 @@ pub struct Index {
 	height_limit: Option<u32>,
 	index_addresses: bool,
 	index_brc721: bool,
+	index_brc721_only: bool,
 	index_inscriptions: bool,
 	index_runes: bool,
 	index_sats: bool,
@@ -217,6 +311,8 @@ pub struct Index {
 	settings: Settings,
 	started: DateTime<Utc>,
 	first_index_height: u32,
+	snapshot_recovery: Mutex<Option<u32>>,
+	snapshot_thread: Mutex<Option<thread::JoinHandle<Result>>>,
 	unrecoverably_reorged: AtomicBool,
 }
 
@@ -312,24 +408,7 @@ impl Index {
 				tx.set_durability(durability);
 				tx.set_quick_repair(true);
 
-				tx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
-				tx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
-				tx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
-				tx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
-				tx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
-				tx.open_table(HOME_INSCRIPTIONS)?;
-				tx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
-				tx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
-				tx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
-				tx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
-				tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
-				tx.open_table(RUNE_TO_RUNE_ID)?;
-				tx.open_table(SAT_TO_SATPOINT)?;
-				tx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
-				tx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
-				tx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
-				tx.open_table(TRANSACTION_ID_TO_RUNE)?;
-				tx.open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?;
+				visit_tables(&mut tx)?;
 
 				{
 					let mut statistics = tx.open_table(STATISTIC_TO_COUNT)?;
@@ -346,6 +425,18 @@ impl Index {
 						u64::from(settings.index_brc721_raw()),
 					)?;
 
+					Self::set_statistic(
+						&mut statistics,
+						Statistic::IndexBrc721Only,
+						u64::from(settings.index_brc721_only()),
+					)?;
+
+					Self::set_statistic(
+						&mut statistics,
+						Statistic::FirstBrc721Height,
+						settings.first_brc721_height().into(),
+					)?;
+
 					Self::set_statistic(
 						&mut statistics,
 						Statistic::IndexInscriptions,
@@ -425,6 +516,7 @@ impl Index {
 
 		let index_addresses;
 		let index_brc721;
+		let index_brc721_only;
 		let index_runes;
 		let index_sats;
 		let index_transactions;
@@ -435,10 +527,23 @@ impl Index {
 			let statistics = tx.open_table(STATISTIC_TO_COUNT)?;
 			index_addresses = Self::is_statistic_set(&statistics, Statistic::IndexAddresses)?;
 			index_brc721 = Self::is_statistic_set(&statistics, Statistic::IndexBrc721)?;
+			index_brc721_only = Self::is_statistic_set(&statistics, Statistic::IndexBrc721Only)?;
 			index_inscriptions = Self::is_statistic_set(&statistics, Statistic::IndexInscriptions)?;
 			index_runes = Self::is_statistic_set(&statistics, Statistic::IndexRunes)?;
 			index_sats = Self::is_statistic_set(&statistics, Statistic::IndexSats)?;
 			index_transactions = Self::is_statistic_set(&statistics, Statistic::IndexTransactions)?;
+
+			let first_brc721_height = statistics
+				.get(&Statistic::FirstBrc721Height.key())?
+				.map(|x| x.value())
+				.unwrap_or_default();
+
+			ensure!(
+				!index_brc721 || first_brc721_height == u64::from(settings.first_brc721_height()),
+				"index at `{}` was built with first brc721 height {first_brc721_height}, not {}",
+				path.display(),
+				settings.first_brc721_height(),
+			);
 		}
 
 		let genesis_block_coinbase_transaction =
@@ -446,16 +551,21 @@ impl Index {
 
 		let first_index_height = if index_sats || index_addresses {
 			0
-		} else if index_inscriptions {
-			settings.first_inscription_height()
-		} else if index_runes {
-			settings.first_rune_height()
 		} else {
-			u32::MAX
+			[
+				(index_brc721, settings.first_brc721_height()),
+				(index_inscriptions, settings.first_inscription_height()),
+				(index_runes, settings.first_rune_height()),
+			]
+			.into_iter()
+			.filter_map(|(enabled, height)| enabled.then_some(height))
+			.min()
+			.unwrap_or(u32::MAX)
 		};
 
 		Ok(Self {
 			genesis_block_coinbase_txid: genesis_block_coinbase_transaction.compute_txid(),
+			brc721_mempool: Mutex::new(default()),
 			client,
 			database,
 			durability,
@@ -465,6 +575,7 @@ impl Index {
 			height_limit: settings.height_limit(),
 			index_addresses,
 			index_brc721,
+			index_brc721_only,
 			index_runes,
 			index_sats,
 			index_transactions,
@@ -472,6 +583,8 @@ impl Index {
 			settings: settings.clone(),
 			path,
 			started: Utc::now(),
+			snapshot_recovery: Mutex::new(None),
+			snapshot_thread: Mutex::new(None),
 			unrecoverably_reorged: AtomicBool::new(false),
 		})
 	}
@@ -567,6 +680,7 @@ impl Index {
 			rune_index: self.has_rune_index(),
 			runes: statistic(Statistic::Runes)?,
 			sat_index: self.has_sat_index(),
+			snapshot_recovery: *self.snapshot_recovery.lock().unwrap(),
 			started: self.started,
 			transaction_index: statistic(Statistic::IndexTransactions)? != 0,
 			unrecoverably_reorged: self.unrecoverably_reorged.load(atomic::Ordering::Relaxed),
@@ -627,11 +741,14 @@ impl Index {
 					.map(|(height, _header)| height.value() + 1)
 					.unwrap_or(0),
 				branch_pages: stats.branch_pages(),
+				chain_tip_distance: self.settings.chain_tip_distance(),
 				fragmented_bytes: stats.fragmented_bytes(),
 				index_file_size: fs::metadata(&self.path)?.len(),
 				leaf_pages: stats.leaf_pages(),
+				max_savepoints: self.settings.max_savepoints(),
 				metadata_bytes: stats.metadata_bytes(),
 				sat_ranges,
+				savepoint_interval: self.settings.savepoint_interval(),
 				outputs_traversed,
 				page_size: stats.page_size(),
 				stored_bytes: stats.stored_bytes(),
@@ -660,6 +777,7 @@ impl Index {
 			let wtx = self.begin_write()?;
 
 			let mut updater = Updater {
+				events: Vec::new(),
 				height: wtx
 					.open_table(HEIGHT_TO_BLOCK_HEADER)?
 					.range(0..)?
@@ -678,21 +796,35 @@ impl Index {
 				Err(err) => {
 					log::info!("{}", err.to_string());
 
-					match err.downcast_ref() {
-						Some(&reorg::Error::Recoverable { height, depth }) => {
-							Reorg::handle_reorg(self, height, depth)?;
-						},
-						Some(&reorg::Error::Unrecoverable) => {
-							self.unrecoverably_reorged.store(true, atomic::Ordering::Relaxed);
-							return Err(anyhow!(reorg::Error::Unrecoverable));
-						},
+					let recovered = match err.downcast_ref() {
+						Some(&reorg::Error::Recoverable { height, depth }) =>
+							Reorg::handle_reorg(self, height, depth)?,
+						Some(&reorg::Error::Unrecoverable) => false,
 						_ => return Err(err),
 					};
+
+					if !recovered {
+						let Some(height) = Snapshot::restore(self)? else {
+							self.unrecoverably_reorged.store(true, atomic::Ordering::Relaxed);
+							return Err(anyhow!(reorg::Error::Unrecoverable));
+						};
+
+						log::warn!(
+							"recovered from reorg by restoring index snapshot at height {height}"
+						);
+
+						self.snapshot_recovery.lock().unwrap().replace(height);
+					}
 				},
 			}
 		}
 	}
 
+	/// Waits for the index snapshot being taken in the background, if any.
+	pub fn wait_for_snapshot(&self) -> Result {
+		Snapshot::wait(self)
+	}
+
 	pub fn export(&self, filename: &String, include_addresses: bool) -> Result {
 		let mut writer = BufWriter::new(File::create(filename)?);
 		let rtx = self.database.begin_read()?;
@@ -1116,6 +1248,397 @@ impl Index {
 		Ok(converted_result)
 	}
 
+	/// Returns the collection registered by the transaction `txid`, if any.
+	pub fn get_brc721_collection_by_txid(&self, txid: Txid) -> Result<Option<Brc721Collection>> {
+		let Some(collection_id) = self
+			.database
+			.begin_read()?
+			.open_table(BRC721_TXID_TO_COLLECTION_ID)?
+			.get(&txid.store())?
+			.map(|guard| Brc721CollectionId::load(guard.value()))
+		else {
+			return Ok(None);
+		};
+
+		self.get_brc721_collection_by_id(collection_id)
+	}
+
+	/// Returns the outputs among `outputs` that hold the postage of a brc721 collection
+	/// registration, along with the id of the registered collection.
+	pub fn get_brc721_collection_outputs(
+		&self,
+		outputs: &[OutPoint],
+	) -> Result<Vec<(OutPoint, Brc721CollectionId)>> {
+		let rtx = self.database.begin_read()?;
+		let txid_to_collection_id = rtx.open_table(BRC721_TXID_TO_COLLECTION_ID)?;
+
+		let mut collection_outputs = Vec::new();
+
+		// Collection registrations place the postage right after the brc721 script output.
+		for output in outputs.iter().filter(|output| output.vout == 1) {
+			if let Some(guard) = txid_to_collection_id.get(&output.txid.store())? {
+				collection_outputs.push((*output, Brc721CollectionId::load(guard.value())));
+			}
+		}
+
+		Ok(collection_outputs)
+	}
+
+	pub fn get_brc721_registered_slots(
+		&self,
+		collection_id: Brc721CollectionId,
+		initial_owner: H160,
+	) -> Result<Vec<std::ops::RangeInclusive<u128>>> {
+		Ok(self
+			.database
+			.begin_read()?
+			.open_multimap_table(BRC721_INITIAL_OWNER_TO_SLOT_RANGE)?
+			.get((initial_owner.into(), collection_id.block, collection_id.tx))?
+			.map(|result| result.map(|range| range.value().0..=range.value().1))
+			.collect::<Result<Vec<_>, StorageError>>()?)
+	}
+
+	/// Returns the slots registered by `initial_owner` in every collection.
+	pub fn get_brc721_registrations_by_initial_owner(
+		&self,
+		initial_owner: H160,
+	) -> Result<Vec<api::Brc721RegisteredSlots>> {
+		let first: Brc721RegistrationKey = (initial_owner.into(), 0, 0);
+		let last: Brc721RegistrationKey = (initial_owner.into(), u64::MAX, u32::MAX);
+
+		let mut registrations = Vec::new();
+
+		for result in self
+			.database
+			.begin_read()?
+			.open_multimap_table(BRC721_INITIAL_OWNER_TO_SLOT_RANGE)?
+			.range(first..=last)?
+		{
+			let (key, ranges) = result?;
+			let (_, block, tx) = key.value();
+
+			registrations.push(api::Brc721RegisteredSlots {
+				collection_id: Brc721CollectionId { block, tx },
+				initial_owner,
+				slots: ranges
+					.map(|result| result.map(|range| range.value().0..=range.value().1))
+					.collect::<Result<Vec<_>, StorageError>>()?,
+			});
+		}
+
+		Ok(registrations)
+	}
+
+	/// Returns the output holding a brc721 token and its latest token uri, if it was rebased.
+	pub fn get_brc721_token(
+		&self,
+		collection_id: Brc721CollectionId,
+		token_id: Brc721TokenId,
+	) -> Result<Option<(OutPoint, Option<String>)>> {
+		let rtx = self.database.begin_read()?;
+
+		let key =
+			(collection_id.block, collection_id.tx, token_id.initial_owner.into(), token_id.slot);
+
+		let Some((first, location)) = rtx
+			.open_table(BRC721_TOKEN_TO_LOCATION)?
+			.range(..=key)?
+			.next_back()
+			.transpose()?
+		else {
+			return Ok(None);
+		};
+
+		let (block, tx, initial_owner, _) = first.value();
+		let (end, outpoint) = location.value();
+
+		if (block, tx, initial_owner) != (key.0, key.1, key.2) || end < token_id.slot {
+			return Ok(None);
+		}
+
+		let token_uri =
+			rtx.open_table(BRC721_TOKEN_TO_TOKEN_URI)?.get(key)?.map(|guard| guard.value());
+
+		Ok(Some((OutPoint::load(outpoint), token_uri)))
+	}
+
+	/// Returns the height a brc721 token was burned at, if it was burned.
+	pub fn get_brc721_token_burn_height(
+		&self,
+		collection_id: Brc721CollectionId,
+		token_id: Brc721TokenId,
+	) -> Result<Option<u32>> {
+		let key =
+			(collection_id.block, collection_id.tx, token_id.initial_owner.into(), token_id.slot);
+
+		let Some((first, burn)) = self
+			.database
+			.begin_read()?
+			.open_table(BRC721_TOKEN_TO_BURN)?
+			.range(..=key)?
+			.next_back()
+			.transpose()?
+		else {
+			return Ok(None);
+		};
+
+		let (block, tx, initial_owner, _) = first.value();
+		let (end, height) = burn.value();
+
+		Ok(((block, tx, initial_owner) == (key.0, key.1, key.2) && end >= token_id.slot)
+			.then_some(height))
+	}
+
+	/// Returns the token ranges held by an output.
+	pub(crate) fn get_brc721_output_tokens(
+		&self,
+		outpoint: OutPoint,
+	) -> Result<brc721_mempool::OutputTokens> {
+		Ok(self
+			.database
+			.begin_read()?
+			.open_multimap_table(BRC721_OUTPOINT_TO_TOKEN_RANGE)?
+			.get(outpoint.store())?
+			.map(|result| {
+				result.map(|range| {
+					let (block, tx, initial_owner, start, end) = range.value();
+					(Brc721CollectionId { block, tx }, H160::from(initial_owner), start..=end)
+				})
+			})
+			.collect::<Result<Vec<_>, StorageError>>()?)
+	}
+
+	/// Returns the initial owner, slots and output of the ranges of unburned tokens of a
+	/// collection.
+	pub fn get_brc721_collection_tokens(
+		&self,
+		collection_id: Brc721CollectionId,
+	) -> Result<Vec<(H160, std::ops::RangeInclusive<u128>, OutPoint)>> {
+		let first: Brc721TokenKey = (collection_id.block, collection_id.tx, [0; 20], 0);
+		let last: Brc721TokenKey =
+			(collection_id.block, collection_id.tx, [u8::MAX; 20], u128::MAX);
+
+		Ok(self
+			.database
+			.begin_read()?
+			.open_table(BRC721_TOKEN_TO_LOCATION)?
+			.range(first..=last)?
+			.map(|result| {
+				result.map(|(key, location)| {
+					let (_, _, initial_owner, start) = key.value();
+					let (end, outpoint) = location.value();
+					(H160::from(initial_owner), start..=end, OutPoint::load(outpoint))
+				})
+			})
+			.collect::<Result<Vec<_>, StorageError>>()?)
+	}
+
+	/// Returns a page of the token ranges held by the outputs locked by `script_pubkey`, and
+	/// whether there are more.
+	pub fn get_brc721_tokens_by_script_pubkey(
+		&self,
+		script_pubkey: &Script,
+		page_size: usize,
+		page_index: usize,
+	) -> Result<(Vec<api::Brc721TokenRange>, bool)> {
+		let rtx = self.database.begin_read()?;
+
+		let output_tokens = rtx.open_multimap_table(BRC721_OUTPOINT_TO_TOKEN_RANGE)?;
+
+		let end = page_index.saturating_add(1).saturating_mul(page_size);
+
+		let mut entries = Vec::new();
+
+		for outpoint in rtx
+			.open_multimap_table(BRC721_SCRIPT_PUBKEY_TO_OUTPOINT)?
+			.get(script_pubkey.as_bytes())?
+		{
+			let outpoint = outpoint?.value();
+
+			for range in output_tokens.get(outpoint)? {
+				let (block, tx, initial_owner, start, end) = range?.value();
+
+				entries.push(api::Brc721TokenRange {
+					collection_id: Brc721CollectionId { block, tx },
+					initial_owner: H160::from(initial_owner),
+					output: OutPoint::load(outpoint),
+					slots: start..=end,
+				});
+			}
+
+			if entries.len() > end {
+				break;
+			}
+		}
+
+		let more = entries.len() > end;
+
+		let entries = entries
+			.into_iter()
+			.skip(page_index.saturating_mul(page_size))
+			.take(page_size)
+			.collect();
+
+		Ok((entries, more))
+	}
+
+	/// Appends a webhook payload to the outbox, to be delivered after the ones already queued,
+	/// and drops the payloads already delivered.
+	pub(crate) fn queue_webhook_payload(
+		outbox: &mut Table<u64, &str>,
+		statistic_to_count: &Table<u64, u64>,
+		payload: &str,
+	) -> Result {
+		let cursor = statistic_to_count
+			.get(&Statistic::WebhookCursor.key())?
+			.map(|x| x.value())
+			.unwrap_or_default();
+
+		outbox.retain_in(..cursor, |_, _| false)?;
+
+		let sequence_number = outbox.last()?.map(|(key, _)| key.value() + 1).unwrap_or(cursor);
+
+		outbox.insert(sequence_number, payload)?;
+
+		Ok(())
+	}
+
+	/// Returns the oldest undelivered webhook payload and its sequence number.
+	pub(crate) fn next_webhook_payload(&self) -> Result<Option<(u64, String)>> {
+		let rtx = self.database.begin_read()?;
+
+		let cursor = rtx
+			.open_table(STATISTIC_TO_COUNT)?
+			.get(&Statistic::WebhookCursor.key())?
+			.map(|x| x.value())
+			.unwrap_or_default();
+
+		Ok(rtx
+			.open_table(WEBHOOK_SEQUENCE_NUMBER_TO_PAYLOAD)?
+			.range(cursor..)?
+			.next()
+			.transpose()?
+			.map(|(key, value)| (key.value(), value.value().to_string())))
+	}
+
+	/// Advances the delivery cursor past a delivered webhook payload.
+	pub(crate) fn complete_webhook_delivery(&self, sequence_number: u64) -> Result {
+		let wtx = self.begin_write()?;
+
+		Self::set_statistic(
+			&mut wtx.open_table(STATISTIC_TO_COUNT)?,
+			Statistic::WebhookCursor,
+			sequence_number + 1,
+		)?;
+
+		wtx.commit()?;
+
+		Ok(())
+	}
+
+	/// Refreshes the BRC721 operations of the transactions in the mempool.
+	///
+	/// Transactions that left the mempool are dropped, and transactions that entered it since the
+	/// last update are fetched in batches. Transfers depend on the tokens held by the spent
+	/// outputs, so when a block was indexed in between, transactions whose spent outputs no
+	/// longer hold the tokens they were decoded with are fetched again. Transactions that leave
+	/// the mempool before they are fetched are skipped.
+	pub fn update_brc721_mempool(&self) -> Result {
+		// arbitrarily chosen, like the batch size of the updater's fetcher
+		const BATCH_SIZE: usize = 2048;
+
+		if !self.index_brc721 {
+			return Ok(());
+		}
+
+		let tokens_of = |outpoint| self.get_brc721_output_tokens(outpoint);
+
+		let tip = self.block_hash(None)?;
+
+		let (cached_tip, mut cached) = {
+			let mempool = self.brc721_mempool.lock().unwrap();
+
+			(mempool.tip, mempool.transactions.iter().cloned().collect::<HashMap<_, _>>())
+		};
+
+		let txids = self.client.get_raw_mempool()?;
+
+		{
+			let listed = txids.iter().collect::<HashSet<&Txid>>();
+			cached.retain(|txid, _| listed.contains(txid));
+		}
+
+		if cached_tip != tip {
+			let mut stale = Vec::new();
+
+			for (txid, transaction) in &cached {
+				if !transaction.transfers_are_current(tokens_of)? {
+					stale.push(*txid);
+				}
+			}
+
+			for txid in stale {
+				cached.remove(&txid);
+			}
+		}
+
+		let missing = txids
+			.iter()
+			.filter(|txid| !cached.contains_key(*txid))
+			.copied()
+			.collect::<Vec<Txid>>();
+
+		let fetcher = fetcher::Fetcher::new(&self.settings)?;
+
+		let fetched = tokio::runtime::Builder::new_current_thread()
+			.enable_all()
+			.build()?
+			.block_on(async {
+				let mut fetched = Vec::with_capacity(missing.len());
+
+				for chunk in missing.chunks(BATCH_SIZE) {
+					fetched.extend(fetcher.get_mempool_transactions(chunk.to_vec()).await?);
+				}
+
+				Ok::<_, Error>(fetched)
+			})?;
+
+		let mut fetched = missing.into_iter().zip(fetched).collect::<HashMap<_, _>>();
+
+		let mut transactions = Vec::new();
+
+		for txid in txids {
+			let transaction = match cached.remove(&txid) {
+				Some(transaction) => transaction,
+				None => {
+					// the transaction may have left the mempool since it was listed
+					let Some(Some(tx)) = fetched.remove(&txid) else {
+						continue;
+					};
+
+					brc721_mempool::PendingTransaction::new(&tx, tokens_of)?
+				},
+			};
+
+			transactions.push((txid, transaction));
+		}
+
+		*self.brc721_mempool.lock().unwrap() = brc721_mempool::Brc721Mempool { tip, transactions };
+
+		Ok(())
+	}
+
+	/// Returns the BRC721 operations of unconfirmed transactions, as of the last mempool update.
+	pub fn get_brc721_pending_operations(&self) -> Vec<api::Brc721PendingOperation> {
+		self.brc721_mempool
+			.lock()
+			.unwrap()
+			.transactions
+			.iter()
+			.flat_map(|(_, transaction)| transaction.operations.clone())
+			.collect()
+	}
+
 	pub fn block_header(&self, hash: BlockHash) -> Result<Option<Header>> {
 		self.client.get_block_header(&hash).into_option()
 	}
@@ -2325,6 +2848,11 @@ impl Index {
 mod tests {
 	use super::*;
 	use crate::index::testing::Context;
+	use ordinals::brc721::{
+		address_mapping::btc_address_to_h160,
+		burn_token::BurnToken,
+		register_ownership::{RegisterOwnership, SlotsBundle},
+	};
 
 	#[test]
 	fn height_limit() {
@@ -2350,6 +2878,37 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn blocks_fetched_in_parallel_are_indexed_in_order() {
+		for threads in [1, 3] {
+			let context = Context::builder()
+				.args(["--index-sats", "--block-fetch-threads", &threads.to_string()])
+				.build();
+
+			let blocks = context.mine_blocks(10);
+
+			assert_eq!(context.index.block_count().unwrap(), 11);
+
+			for (height, block) in blocks.iter().enumerate() {
+				assert_eq!(
+					context.index.block_hash(Some(u32::try_from(height).unwrap() + 1)).unwrap(),
+					Some(block.block_hash())
+				);
+			}
+
+			assert_eq!(
+				context
+					.index
+					.list(OutPoint { txid: blocks[9].txdata[0].compute_txid(), vout: 0 })
+					.unwrap(),
+				Some(vec![(
+					Height(10).starting_sat().n(),
+					Height(10).starting_sat().n() + 50 * COIN_VALUE
+				)])
+			);
+		}
+	}
+
 	#[test]
 	fn inscriptions_below_first_inscription_height_are_skipped() {
 		let inscription = inscription("text/plain;charset=utf-8", "hello");
@@ -4272,19 +4831,541 @@ mod tests {
 		}
 	}
 
-	#[test]
-	fn inscription_without_parent_tag_has_no_parent_entry() {
-		for context in Context::configurations() {
-			context.mine_blocks(1);
-
-			let txid = context.core.broadcast_tx(TransactionTemplate {
-				inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
-				..default()
-			});
+	fn brc721_reorg_context() -> (Context, tokio::sync::mpsc::Receiver<Event>) {
+		let (event_sender, event_receiver) = tokio::sync::mpsc::channel(1024);
+		let mut context =
+			Context::builder().arg("--index-brc721").event_sender(event_sender).build();
+		context.index.set_durability(redb::Durability::Immediate);
+		context.mine_blocks(1);
+		(context, event_receiver)
+	}
 
-			context.mine_blocks(1);
+	fn register_brc721_collection(context: &Context) -> Brc721CollectionId {
+		context.core.broadcast_tx(TransactionTemplate {
+			inputs: &[],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(ordinals::RegisterCollection::default().as_script()),
+			..default()
+		});
 
-			let inscription_id = InscriptionId { txid, index: 0 };
+		Brc721CollectionId { block: context.core.height() + 1, tx: 1 }
+	}
+
+	fn register_brc721_ownership(context: &Context, collection_id: Brc721CollectionId) -> H160 {
+		context.core.broadcast_tx(TransactionTemplate {
+			inputs: &[(1, 0, 0, Witness::new())],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(
+				RegisterOwnership { collection_id, slots_bundles: vec![SlotsBundle(vec![0..=3])] }
+					.into(),
+			),
+			..default()
+		});
+
+		btc_address_to_h160(
+			context
+				.core
+				.address(OutPoint { txid: context.core.tx(1, 0).compute_txid(), vout: 0 }),
+		)
+		.unwrap()
+	}
+
+	fn reverted_events(event_receiver: &mut tokio::sync::mpsc::Receiver<Event>) -> Vec<Event> {
+		let mut events = Vec::new();
+
+		while let Ok(event) = event_receiver.try_recv() {
+			if matches!(
+				event,
+				Event::Brc721CollectionReverted { .. } | Event::Brc721OwnershipReverted { .. }
+			) {
+				events.push(event);
+			}
+		}
+
+		events
+	}
+
+	#[test]
+	fn brc721_recover_from_reorg_of_ownership_block() {
+		let (context, mut event_receiver) = brc721_reorg_context();
+
+		let collection_id = register_brc721_collection(&context);
+		context.mine_blocks(1);
+
+		let initial_owner = register_brc721_ownership(&context, collection_id);
+		context.mine_blocks(1);
+
+		assert_eq!(
+			context.index.get_brc721_registered_slots(collection_id, initial_owner).unwrap(),
+			[0..=3]
+		);
+
+		assert_eq!(reverted_events(&mut event_receiver), []);
+
+		context.core.invalidate_tip();
+		context.mine_blocks(2);
+
+		assert!(context.index.get_brc721_collection_by_id(collection_id).unwrap().is_some());
+		assert_eq!(
+			context.index.get_brc721_registered_slots(collection_id, initial_owner).unwrap(),
+			[]
+		);
+		assert_eq!(
+			context
+				.index
+				.get_brc721_token(collection_id, Brc721TokenId { slot: 0, initial_owner })
+				.unwrap(),
+			None
+		);
+
+		assert_eq!(
+			reverted_events(&mut event_receiver),
+			[Event::Brc721OwnershipReverted {
+				block_height: 3,
+				collection_id,
+				initial_owner,
+				slots: vec![0..=3],
+			}]
+		);
+	}
+
+	#[test]
+	fn brc721_recover_from_reorg_of_registration_blocks() {
+		let (context, mut event_receiver) = brc721_reorg_context();
+
+		let collection_id = register_brc721_collection(&context);
+		context.mine_blocks_with_update(1, false);
+
+		let initial_owner = register_brc721_ownership(&context, collection_id);
+		context.mine_blocks(1);
+
+		assert!(context.index.get_brc721_collection_by_id(collection_id).unwrap().is_some());
+
+		context.core.invalidate_tip();
+		context.core.invalidate_tip();
+		context.mine_blocks(3);
+
+		assert_eq!(context.index.block_count().unwrap(), 5);
+		assert!(context.index.get_brc721_collection_by_id(collection_id).unwrap().is_none());
+		assert_eq!(
+			context.index.get_brc721_registered_slots(collection_id, initial_owner).unwrap(),
+			[]
+		);
+
+		assert_eq!(
+			reverted_events(&mut event_receiver),
+			[
+				Event::Brc721CollectionReverted { block_height: 2, collection_id },
+				Event::Brc721OwnershipReverted {
+					block_height: 2,
+					collection_id,
+					initial_owner,
+					slots: vec![0..=3],
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn reorg_event_is_sent_before_reverted_events() {
+		let (context, mut event_receiver) = brc721_reorg_context();
+
+		let collection_id = register_brc721_collection(&context);
+		context.mine_blocks_with_update(1, false);
+		context.mine_blocks(1);
+
+		while event_receiver.try_recv().is_ok() {}
+
+		context.core.invalidate_tip();
+		context.core.invalidate_tip();
+		context.mine_blocks(3);
+
+		assert_eq!(event_receiver.try_recv().unwrap(), Event::Reorg { block_height: 2 });
+		assert_eq!(
+			event_receiver.try_recv().unwrap(),
+			Event::Brc721CollectionReverted { block_height: 2, collection_id }
+		);
+	}
+
+	#[test]
+	fn brc721_reorg_beyond_savepoints_keeps_state() {
+		let (context, mut event_receiver) = brc721_reorg_context();
+
+		let collection_id = register_brc721_collection(&context);
+		context.mine_blocks(1);
+
+		let initial_owner = register_brc721_ownership(&context, collection_id);
+		context.mine_blocks(1);
+
+		context.mine_blocks(18);
+
+		for _ in 0..19 {
+			context.core.invalidate_tip();
+		}
+
+		context.mine_blocks_with_update(20, false);
+
+		assert_eq!(
+			context.index.update().unwrap_err().downcast_ref::<reorg::Error>(),
+			Some(&reorg::Error::Unrecoverable)
+		);
+
+		assert!(context.index.get_brc721_collection_by_id(collection_id).unwrap().is_some());
+		assert_eq!(
+			context.index.get_brc721_registered_slots(collection_id, initial_owner).unwrap(),
+			[0..=3]
+		);
+
+		assert_eq!(reverted_events(&mut event_receiver), []);
+	}
+
+	#[test]
+	fn brc721_burned_token_is_retired() {
+		let (context, _event_receiver) = brc721_reorg_context();
+
+		let collection_id = register_brc721_collection(&context);
+		context.mine_blocks(1);
+
+		let initial_owner = register_brc721_ownership(&context, collection_id);
+		context.mine_blocks(1);
+
+		let burned = Brc721TokenId { slot: 2, initial_owner };
+
+		let txid = context.core.broadcast_tx(TransactionTemplate {
+			inputs: &[(3, 1, 1, Witness::new())],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(BurnToken { collection_id, token_id: burned }.into()),
+			..default()
+		});
+		context.mine_blocks(1);
+
+		assert_eq!(context.index.get_brc721_token(collection_id, burned).unwrap(), None);
+		assert_eq!(
+			context.index.get_brc721_token_burn_height(collection_id, burned).unwrap(),
+			Some(4)
+		);
+		assert_eq!(
+			context
+				.index
+				.get_brc721_token(collection_id, Brc721TokenId { slot: 3, initial_owner })
+				.unwrap(),
+			Some((OutPoint { txid, vout: 1 }, None))
+		);
+
+		context.core.invalidate_tip();
+		context.mine_blocks(2);
+
+		assert_eq!(
+			context.index.get_brc721_token_burn_height(collection_id, burned).unwrap(),
+			None
+		);
+		assert!(context.index.get_brc721_token(collection_id, burned).unwrap().is_some());
+	}
+
+	#[test]
+	fn brc721_owner_index_recovers_from_reorg_of_transfer() {
+		let (context, _event_receiver) = brc721_reorg_context();
+
+		let collection_id = register_brc721_collection(&context);
+		context.mine_blocks(1);
+
+		let initial_owner = register_brc721_ownership(&context, collection_id);
+		context.mine_blocks(1);
+
+		let registered = OutPoint { txid: context.core.tx(3, 1).compute_txid(), vout: 1 };
+		let registered_script_pubkey = context.core.address(registered).script_pubkey();
+
+		let tokens = |script_pubkey: &Script| {
+			context.index.get_brc721_tokens_by_script_pubkey(script_pubkey, 100, 0).unwrap()
+		};
+
+		let range =
+			|output| api::Brc721TokenRange { collection_id, initial_owner, output, slots: 0..=3 };
+
+		assert_eq!(tokens(&registered_script_pubkey), (vec![range(registered)], false));
+
+		let txid = context.core.broadcast_tx(TransactionTemplate {
+			inputs: &[(3, 1, 1, Witness::new())],
+			p2tr: true,
+			..default()
+		});
+		context.mine_blocks(1);
+
+		let transferred = OutPoint { txid, vout: 0 };
+		let transferred_script_pubkey = context.core.address(transferred).script_pubkey();
+
+		assert_eq!(tokens(&registered_script_pubkey), (Vec::new(), false));
+		assert_eq!(tokens(&transferred_script_pubkey), (vec![range(transferred)], false));
+
+		context.core.invalidate_tip();
+		context.mine_blocks(2);
+
+		assert_eq!(tokens(&registered_script_pubkey), (vec![range(registered)], false));
+		assert_eq!(tokens(&transferred_script_pubkey), (Vec::new(), false));
+	}
+
+	#[test]
+	fn index_with_different_first_brc721_height_gives_error() {
+		let tempdir = Context::builder()
+			.args(["--index-brc721", "--first-brc721-height", "3"])
+			.build()
+			.tempdir;
+
+		let path = tempdir.path().join("regtest").join("index.redb");
+
+		assert_eq!(
+			Context::builder()
+				.arg("--index-brc721")
+				.tempdir(tempdir)
+				.try_build()
+				.err()
+				.unwrap()
+				.to_string(),
+			format!("index at `{}` was built with first brc721 height 3, not 0", path.display()),
+		);
+	}
+
+	#[test]
+	fn brc721_operations_below_first_brc721_height_are_ignored() {
+		let context = Context::builder()
+			.args(["--index-brc721", "--first-brc721-height", "3"])
+			.build();
+		context.mine_blocks(1);
+
+		let early = register_brc721_collection(&context);
+		context.mine_blocks(1);
+
+		let activated = register_brc721_collection(&context);
+		context.mine_blocks(1);
+
+		assert_eq!(early, Brc721CollectionId { block: 2, tx: 1 });
+		assert_eq!(activated, Brc721CollectionId { block: 3, tx: 1 });
+
+		assert!(context.index.get_brc721_collection_by_id(early).unwrap().is_none());
+		assert!(context.index.get_brc721_collection_by_id(activated).unwrap().is_some());
+	}
+
+	#[test]
+	fn brc721_index_without_inscriptions_keeps_utxo_entries() {
+		let context =
+			Context::builder().args(["--index-brc721", "--no-index-inscriptions"]).build();
+		context.mine_blocks(1);
+
+		assert!(context.index.has_brc721_index());
+		assert!(!context.index.has_inscription_index());
+
+		let collection_id = register_brc721_collection(&context);
+		context.mine_blocks(1);
+
+		let initial_owner = register_brc721_ownership(&context, collection_id);
+		context.mine_blocks(1);
+
+		assert_eq!(
+			context.index.get_brc721_registered_slots(collection_id, initial_owner).unwrap(),
+			[0..=3]
+		);
+
+		assert!(context
+			.index
+			.contains_output(&OutPoint { txid: context.core.tx(3, 0).compute_txid(), vout: 0 })
+			.unwrap());
+	}
+
+	#[test]
+	fn brc721_only_index_skips_utxo_entries() {
+		let context = Context::builder().arg("--index-brc721-only").build();
+		context.mine_blocks(1);
+
+		assert!(context.index.has_brc721_index());
+		assert!(!context.index.has_inscription_index());
+
+		let collection_id = register_brc721_collection(&context);
+		context.mine_blocks(1);
+
+		let initial_owner = register_brc721_ownership(&context, collection_id);
+		context.mine_blocks(1);
+
+		assert_eq!(
+			context.index.get_brc721_registered_slots(collection_id, initial_owner).unwrap(),
+			[0..=3]
+		);
+
+		assert!(!context
+			.index
+			.contains_output(&OutPoint { txid: context.core.tx(3, 0).compute_txid(), vout: 0 })
+			.unwrap());
+
+		assert!(context
+			.index
+			.database
+			.begin_read()
+			.unwrap()
+			.open_table(OUTPOINT_TO_UTXO_ENTRY)
+			.unwrap()
+			.is_empty()
+			.unwrap());
+	}
+
+	#[test]
+	fn recover_from_deep_reorg_with_more_savepoints() {
+		for max_savepoints in [2, 3] {
+			let mut context =
+				Context::builder().arg(format!("--max-savepoints={max_savepoints}")).build();
+			context.index.set_durability(redb::Durability::Immediate);
+
+			context.mine_blocks(1);
+
+			let txid = context.core.broadcast_tx(TransactionTemplate {
+				inputs: &[(1, 0, 0, inscription("text/plain;charset=utf-8", "hello").to_witness())],
+				..default()
+			});
+			let first_id = InscriptionId { txid, index: 0 };
+
+			context.mine_blocks(11);
+
+			let txid = context.core.broadcast_tx(TransactionTemplate {
+				inputs: &[(2, 0, 0, inscription("text/plain;charset=utf-8", "hello").to_witness())],
+				..default()
+			});
+			let second_id = InscriptionId { txid, index: 0 };
+
+			context.mine_blocks(10);
+
+			assert!(context.index.inscription_exists(second_id).unwrap());
+
+			for _ in 0..12 {
+				context.core.invalidate_tip();
+			}
+
+			context.mine_blocks_with_update(13, false);
+
+			if max_savepoints == 2 {
+				assert_eq!(
+					context.index.update().unwrap_err().downcast_ref::<reorg::Error>(),
+					Some(&reorg::Error::Unrecoverable)
+				);
+			} else {
+				context.index.update().unwrap();
+				assert!(context.index.inscription_exists(first_id).unwrap());
+				assert!(!context.index.inscription_exists(second_id).unwrap());
+			}
+		}
+	}
+
+	#[test]
+	fn recover_from_unrecoverable_reorg_with_snapshot() {
+		let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+		let mut context = Context::builder()
+			.args(["--snapshot-interval", "5"])
+			.event_sender(event_sender)
+			.build();
+		context.index.set_durability(redb::Durability::Immediate);
+
+		context.mine_blocks(1);
+
+		let txid = context.core.broadcast_tx(TransactionTemplate {
+			inputs: &[(1, 0, 0, inscription("text/plain;charset=utf-8", "hello").to_witness())],
+			..default()
+		});
+		let first_id = InscriptionId { txid, index: 0 };
+
+		context.mine_blocks(9);
+
+		let txid = context.core.broadcast_tx(TransactionTemplate {
+			inputs: &[(2, 0, 0, inscription("text/plain;charset=utf-8", "hello").to_witness())],
+			..default()
+		});
+		let second_id = InscriptionId { txid, index: 0 };
+
+		context.mine_blocks(12);
+
+		Snapshot::wait(&context.index).unwrap();
+
+		assert_eq!(
+			Snapshot::list(&context.index)
+				.unwrap()
+				.iter()
+				.map(|snapshot| snapshot.height)
+				.collect::<Vec<u32>>(),
+			[10, 22]
+		);
+
+		while event_receiver.try_recv().is_ok() {}
+
+		for _ in 0..12 {
+			context.core.invalidate_tip();
+		}
+
+		context.mine_blocks(13);
+
+		assert_eq!(event_receiver.try_recv().unwrap(), Event::Reorg { block_height: 11 });
+		assert!(matches!(
+			event_receiver.try_recv().unwrap(),
+			Event::BlockIndexed { block_height: 11, .. }
+		));
+
+		assert!(context.index.inscription_exists(first_id).unwrap());
+		assert!(!context.index.inscription_exists(second_id).unwrap());
+		assert_eq!(context.index.block_count().unwrap(), 24);
+		assert_eq!(context.index.status(false).unwrap().snapshot_recovery, Some(10));
+		assert!(!context.index.status(false).unwrap().unrecoverably_reorged);
+
+		Snapshot::wait(&context.index).unwrap();
+
+		assert_eq!(
+			Snapshot::list(&context.index)
+				.unwrap()
+				.iter()
+				.map(|snapshot| snapshot.height)
+				.collect::<Vec<u32>>(),
+			[10, 23]
+		);
+	}
+
+	#[test]
+	fn unrecoverable_reorg_without_usable_snapshot() {
+		let mut context = Context::builder().args(["--snapshot-interval", "5"]).build();
+		context.index.set_durability(redb::Durability::Immediate);
+
+		context.mine_blocks(10);
+		context.mine_blocks(10);
+
+		for _ in 0..19 {
+			context.core.invalidate_tip();
+		}
+
+		context.mine_blocks_with_update(20, false);
+
+		assert_eq!(
+			context.index.update().unwrap_err().downcast_ref::<reorg::Error>(),
+			Some(&reorg::Error::Unrecoverable)
+		);
+
+		assert!(context.index.status(false).unwrap().unrecoverably_reorged);
+		assert_eq!(context.index.status(false).unwrap().snapshot_recovery, None);
+		Snapshot::wait(&context.index).unwrap();
+		assert_eq!(Snapshot::list(&context.index).unwrap(), []);
+	}
+
+	#[test]
+	fn inscription_without_parent_tag_has_no_parent_entry() {
+		for context in Context::configurations() {
+			context.mine_blocks(1);
+
+			let txid = context.core.broadcast_tx(TransactionTemplate {
+				inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+				..default()
+			});
+
+			context.mine_blocks(1);
+
+			let inscription_id = InscriptionId { txid, index: 0 };
 
 			assert!(context
 				.index
@@ -5654,6 +6735,117 @@ mod tests {
 		}
 	}
 
+	fn next_event(event_receiver: &mut tokio::sync::mpsc::Receiver<Event>) -> Event {
+		loop {
+			let event = event_receiver.blocking_recv().unwrap();
+
+			if !matches!(event, Event::BlockIndexed { .. }) {
+				return event;
+			}
+		}
+	}
+
+	#[test]
+	fn block_event_sender_channel() {
+		let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+		let context = Context::builder().event_sender(event_sender).build();
+
+		let blocks = context.mine_blocks(1);
+
+		let mut block_events = Vec::new();
+
+		while let Ok(event) = event_receiver.try_recv() {
+			block_events.push(event);
+		}
+
+		assert_eq!(
+			block_events.last().unwrap(),
+			&Event::BlockIndexed { block_hash: blocks[0].block_hash(), block_height: 1 }
+		);
+	}
+
+	#[test]
+	fn webhook_outbox() {
+		let context = Context::builder().build();
+
+		let queue = |payload| {
+			let wtx = context.index.begin_write().unwrap();
+			Index::queue_webhook_payload(
+				&mut wtx.open_table(WEBHOOK_SEQUENCE_NUMBER_TO_PAYLOAD).unwrap(),
+				&wtx.open_table(STATISTIC_TO_COUNT).unwrap(),
+				payload,
+			)
+			.unwrap();
+			wtx.commit().unwrap();
+		};
+
+		let payloads = || {
+			context
+				.index
+				.database
+				.begin_read()
+				.unwrap()
+				.open_table(WEBHOOK_SEQUENCE_NUMBER_TO_PAYLOAD)
+				.unwrap()
+				.len()
+				.unwrap()
+		};
+
+		assert_eq!(context.index.next_webhook_payload().unwrap(), None);
+
+		queue("foo");
+		queue("bar");
+
+		assert_eq!(context.index.next_webhook_payload().unwrap(), Some((0, "foo".into())));
+
+		context.index.complete_webhook_delivery(0).unwrap();
+
+		assert_eq!(context.index.statistic(Statistic::WebhookCursor), 1);
+		assert_eq!(context.index.next_webhook_payload().unwrap(), Some((1, "bar".into())));
+
+		context.index.complete_webhook_delivery(1).unwrap();
+
+		assert_eq!(context.index.next_webhook_payload().unwrap(), None);
+		assert_eq!(payloads(), 2);
+
+		queue("baz");
+
+		assert_eq!(context.index.next_webhook_payload().unwrap(), Some((2, "baz".into())));
+		assert_eq!(payloads(), 1);
+	}
+
+	#[test]
+	fn brc721_event_sender_channel() {
+		let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+		let context = Context::builder().arg("--index-brc721").event_sender(event_sender).build();
+
+		context.mine_blocks(1);
+
+		let address = H160::repeat_byte(1);
+
+		let txid = context.core.broadcast_tx(TransactionTemplate {
+			inputs: &[(1, 0, 0, Witness::new())],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(ordinals::RegisterCollection { address, rebaseable: true }.as_script()),
+			..default()
+		});
+
+		context.mine_blocks(1);
+
+		assert_eq!(
+			next_event(&mut event_receiver),
+			Event::Brc721CollectionRegistered {
+				block_height: 2,
+				collection_id: Brc721CollectionId { block: 2, tx: 1 },
+				laos_address: address,
+				rebaseable: true,
+				txid,
+			}
+		);
+	}
+
 	#[test]
 	fn inscription_event_sender_channel() {
 		let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
@@ -5672,7 +6864,7 @@ mod tests {
 		context.mine_blocks(1);
 
 		let inscription_id = InscriptionId { txid: create_txid, index: 0 };
-		let create_event = event_receiver.blocking_recv().unwrap();
+		let create_event = next_event(&mut event_receiver);
 		let expected_charms = if context.index.index_sats { 513 } else { 0 };
 		assert_eq!(
 			create_event,
@@ -5699,7 +6891,7 @@ mod tests {
 
 		context.mine_blocks(1);
 
-		let transfer_event = event_receiver.blocking_recv().unwrap();
+		let transfer_event = next_event(&mut event_receiver);
 		assert_eq!(
 			transfer_event,
 			Event::InscriptionTransferred {
@@ -5754,7 +6946,7 @@ mod tests {
 		);
 
 		assert_eq!(
-			event_receiver.blocking_recv().unwrap(),
+			next_event(&mut event_receiver),
 			Event::RuneEtched { block_height: 8, txid: txid0, rune_id: id }
 		);
 
@@ -5784,7 +6976,7 @@ mod tests {
 		);
 
 		assert_eq!(
-			event_receiver.blocking_recv().unwrap(),
+			next_event(&mut event_receiver),
 			Event::RuneMinted { block_height: 9, txid: txid1, rune_id: id, amount: 1000 }
 		);
 
@@ -5818,10 +7010,10 @@ mod tests {
 			[(OutPoint { txid: txid2, vout: 0 }, vec![(id, 1000)])],
 		);
 
-		event_receiver.blocking_recv().unwrap();
+		next_event(&mut event_receiver);
 
 		pretty_assert_eq!(
-			event_receiver.blocking_recv().unwrap(),
+			next_event(&mut event_receiver),
 			Event::RuneTransferred {
 				block_height: 10,
 				txid: txid2,
@@ -5863,10 +7055,10 @@ mod tests {
 			[(OutPoint { txid: txid3, vout: 1 }, vec![(id, 889)])],
 		);
 
-		event_receiver.blocking_recv().unwrap();
+		next_event(&mut event_receiver);
 
 		pretty_assert_eq!(
-			event_receiver.blocking_recv().unwrap(),
+			next_event(&mut event_receiver),
 			Event::RuneBurned { block_height: 11, txid: txid3, amount: 111, rune_id: id }
 		);
 	}
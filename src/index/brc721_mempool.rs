@@ -0,0 +1,324 @@
+// Copyright 2023-2024 Freeverse.io
+// This file is part of LAOS.
+
+// LAOS is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// LAOS is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use api::Brc721PendingOperation;
+use ordinals::{
+	brc721::{
+		burn_token::BurnToken, operations::Brc721Operation, rebase_token::RebaseToken,
+		register_ownership::RegisterOwnership,
+	},
+	RegisterCollection,
+};
+use std::ops::RangeInclusive;
+
+/// Token ranges held by an output: collection, initial owner and slots.
+pub(crate) type OutputTokens = Vec<(Brc721CollectionId, H160, RangeInclusive<u128>)>;
+
+/// BRC721 operations of the transactions in the mempool, in mempool order.
+#[derive(Default)]
+pub(crate) struct Brc721Mempool {
+	/// Tip of the index when the transfers were last checked.
+	pub(crate) tip: Option<BlockHash>,
+	pub(crate) transactions: Vec<(Txid, PendingTransaction)>,
+}
+
+/// BRC721 operations of an unconfirmed transaction and the outputs it spends.
+#[derive(Clone)]
+pub(crate) struct PendingTransaction {
+	pub(crate) inputs: Vec<OutPoint>,
+	pub(crate) operations: Vec<Brc721PendingOperation>,
+}
+
+impl PendingTransaction {
+	pub(crate) fn new(
+		tx: &Transaction,
+		tokens_of: impl Fn(OutPoint) -> Result<OutputTokens>,
+	) -> Result<Self> {
+		Ok(Self {
+			inputs: tx
+				.input
+				.iter()
+				.map(|input| input.previous_output)
+				.filter(|outpoint| !outpoint.is_null())
+				.collect(),
+			operations: pending_operations(tx, tokens_of)?,
+		})
+	}
+
+	/// Returns whether the outputs spent by the transaction still hold the tokens reported as
+	/// transferred. They stop doing so once an output is spent by a block, or once a block
+	/// confirms an unconfirmed output that holds tokens.
+	pub(crate) fn transfers_are_current(
+		&self,
+		tokens_of: impl Fn(OutPoint) -> Result<OutputTokens>,
+	) -> Result<bool> {
+		let mut current = Vec::new();
+
+		for input in &self.inputs {
+			for (collection_id, initial_owner, slots) in tokens_of(*input)? {
+				current.push((*input, collection_id, initial_owner, slots));
+			}
+		}
+
+		let transferred = self
+			.operations
+			.iter()
+			.filter_map(|operation| match operation {
+				Brc721PendingOperation::Transfer {
+					collection_id,
+					initial_owner,
+					slots,
+					output,
+					..
+				} => Some((*output, *collection_id, *initial_owner, slots.clone())),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+
+		Ok(current == transferred)
+	}
+}
+
+/// Decodes the BRC721 operations of an unconfirmed transaction.
+///
+/// Operations are reported as announced, without checking the rules the indexer enforces once
+/// they are confirmed. Transfers are only reported for tokens held by confirmed outputs, which
+/// are resolved by `tokens_of`.
+pub(crate) fn pending_operations(
+	tx: &Transaction,
+	tokens_of: impl Fn(OutPoint) -> Result<OutputTokens>,
+) -> Result<Vec<Brc721PendingOperation>> {
+	let txid = tx.compute_txid();
+
+	let mut operations = Vec::new();
+
+	if let Some(script) = tx.output.first().map(|output| &output.script_pubkey) {
+		match Brc721Operation::from_script(script) {
+			Some(Brc721Operation::RegisterCollection) =>
+				if let Ok(register_collection) = RegisterCollection::from_script(script) {
+					operations.push(Brc721PendingOperation::RegisterCollection {
+						txid,
+						laos_address: register_collection.address,
+						rebaseable: register_collection.rebaseable,
+					});
+				},
+			Some(Brc721Operation::RegisterOwnership) =>
+				if let Ok(register_ownership) = RegisterOwnership::try_from(script.clone()) {
+					operations.push(Brc721PendingOperation::RegisterOwnership {
+						txid,
+						collection_id: register_ownership.collection_id,
+						slots_bundles: register_ownership
+							.slots_bundles
+							.into_iter()
+							.map(|slots_bundle| slots_bundle.0)
+							.collect(),
+					});
+				},
+			Some(Brc721Operation::RebaseToken) =>
+				if let Ok(rebase_token) = RebaseToken::try_from(script.clone()) {
+					operations.push(Brc721PendingOperation::RebaseToken {
+						txid,
+						collection_id: rebase_token.collection_id,
+						token_id: rebase_token.token_id,
+						token_uri: rebase_token.token_uri,
+					});
+				},
+			Some(Brc721Operation::BurnToken) =>
+				if let Ok(burn_token) = BurnToken::try_from(script.clone()) {
+					operations.push(Brc721PendingOperation::BurnToken {
+						txid,
+						collection_id: burn_token.collection_id,
+						token_id: burn_token.token_id,
+					});
+				},
+			None => {},
+		}
+	}
+
+	let destination = tx
+		.output
+		.iter()
+		.position(|output| !output.script_pubkey.is_op_return())
+		.map(|vout| OutPoint { txid, vout: u32::try_from(vout).unwrap() });
+
+	for input in tx.input.iter().filter(|input| !input.previous_output.is_null()) {
+		for (collection_id, initial_owner, slots) in tokens_of(input.previous_output)? {
+			operations.push(Brc721PendingOperation::Transfer {
+				txid,
+				collection_id,
+				initial_owner,
+				slots,
+				output: input.previous_output,
+				destination,
+			});
+		}
+	}
+
+	Ok(operations)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ordinals::brc721::register_ownership::SlotsBundle;
+
+	fn tx(script: ScriptBuf, inputs: &[OutPoint]) -> Transaction {
+		Transaction {
+			version: bitcoin::transaction::Version(2),
+			lock_time: LockTime::ZERO,
+			input: inputs
+				.iter()
+				.map(|previous_output| TxIn { previous_output: *previous_output, ..default() })
+				.collect(),
+			output: vec![
+				TxOut { value: Amount::ZERO, script_pubkey: script },
+				TxOut { value: Amount::from_sat(1000), script_pubkey: ScriptBuf::new() },
+			],
+		}
+	}
+
+	fn no_tokens(_: OutPoint) -> Result<OutputTokens> {
+		Ok(Vec::new())
+	}
+
+	#[test]
+	fn register_collection() {
+		let tx = tx(
+			RegisterCollection { address: H160::repeat_byte(1), rebaseable: true }.as_script(),
+			&[],
+		);
+
+		assert_eq!(
+			pending_operations(&tx, no_tokens).unwrap(),
+			[Brc721PendingOperation::RegisterCollection {
+				txid: tx.compute_txid(),
+				laos_address: H160::repeat_byte(1),
+				rebaseable: true,
+			}]
+		);
+	}
+
+	#[test]
+	fn register_ownership() {
+		let collection_id = Brc721CollectionId { block: 2, tx: 1 };
+
+		let tx = tx(
+			RegisterOwnership { collection_id, slots_bundles: vec![SlotsBundle(vec![0..=3])] }
+				.into(),
+			&[],
+		);
+
+		assert_eq!(
+			pending_operations(&tx, no_tokens).unwrap(),
+			[Brc721PendingOperation::RegisterOwnership {
+				txid: tx.compute_txid(),
+				collection_id,
+				slots_bundles: vec![vec![0..=3]],
+			}]
+		);
+	}
+
+	#[test]
+	fn rebase_token_and_transfer() {
+		let collection_id = Brc721CollectionId { block: 2, tx: 1 };
+		let token_id = Brc721TokenId { slot: 2, initial_owner: H160::repeat_byte(2) };
+		let output = OutPoint { txid: Txid::all_zeros(), vout: 1 };
+
+		let tx = tx(
+			RebaseToken { collection_id, token_id, token_uri: "ipfs://foo".into() }.into(),
+			&[output],
+		);
+
+		let txid = tx.compute_txid();
+
+		assert_eq!(
+			pending_operations(&tx, |outpoint| {
+				Ok(if outpoint == output {
+					vec![(collection_id, token_id.initial_owner, 0..=3)]
+				} else {
+					Vec::new()
+				})
+			})
+			.unwrap(),
+			[
+				Brc721PendingOperation::RebaseToken {
+					txid,
+					collection_id,
+					token_id,
+					token_uri: "ipfs://foo".into(),
+				},
+				Brc721PendingOperation::Transfer {
+					txid,
+					collection_id,
+					initial_owner: token_id.initial_owner,
+					slots: 0..=3,
+					output,
+					destination: Some(OutPoint { txid, vout: 1 }),
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn burn_token() {
+		let collection_id = Brc721CollectionId { block: 2, tx: 1 };
+		let token_id = Brc721TokenId { slot: 2, initial_owner: H160::repeat_byte(2) };
+
+		let tx = tx(BurnToken { collection_id, token_id }.into(), &[]);
+
+		assert_eq!(
+			pending_operations(&tx, no_tokens).unwrap(),
+			[Brc721PendingOperation::BurnToken {
+				txid: tx.compute_txid(),
+				collection_id,
+				token_id
+			}]
+		);
+	}
+
+	#[test]
+	fn unrelated_transaction() {
+		let tx = tx(ScriptBuf::new(), &[OutPoint { txid: Txid::all_zeros(), vout: 0 }]);
+		assert_eq!(pending_operations(&tx, no_tokens).unwrap(), []);
+	}
+
+	#[test]
+	fn transfers_are_current_while_spent_outputs_hold_the_same_tokens() {
+		let collection_id = Brc721CollectionId { block: 2, tx: 1 };
+		let output = OutPoint { txid: Txid::all_zeros(), vout: 1 };
+		let tokens = vec![(collection_id, H160::repeat_byte(2), 0..=3)];
+
+		let holding =
+			|outpoint: OutPoint| Ok(if outpoint == output { tokens.clone() } else { Vec::new() });
+
+		let transaction =
+			PendingTransaction::new(&tx(ScriptBuf::new(), &[output]), holding).unwrap();
+
+		assert_eq!(transaction.inputs, [output]);
+		assert!(transaction.transfers_are_current(holding).unwrap());
+
+		// the spent output was spent by a block, so it no longer holds the tokens
+		assert!(!transaction.transfers_are_current(no_tokens).unwrap());
+
+		// the spent output was confirmed by a block, so it now holds tokens
+		let transaction =
+			PendingTransaction::new(&tx(ScriptBuf::new(), &[output]), no_tokens).unwrap();
+
+		assert!(transaction.transfers_are_current(no_tokens).unwrap());
+		assert!(!transaction.transfers_are_current(holding).unwrap());
+	}
+}
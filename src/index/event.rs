@@ -15,9 +15,66 @@
 // along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::*;
+use std::ops::RangeInclusive;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Event {
+	BlockIndexed {
+		block_hash: BlockHash,
+		block_height: u32,
+	},
+	Brc721CollectionRegistered {
+		block_height: u32,
+		collection_id: Brc721CollectionId,
+		#[serde(rename = "LAOS_address")]
+		laos_address: H160,
+		rebaseable: bool,
+		txid: Txid,
+	},
+	/// A collection registered in a block that was rolled back by a reorg. `block_height` is the
+	/// height the index was rolled back to.
+	Brc721CollectionReverted {
+		block_height: u32,
+		collection_id: Brc721CollectionId,
+	},
+	/// Slots registered in blocks that were rolled back by a reorg. `block_height` is the height
+	/// the index was rolled back to.
+	Brc721OwnershipReverted {
+		block_height: u32,
+		collection_id: Brc721CollectionId,
+		initial_owner: H160,
+		slots: Vec<RangeInclusive<u128>>,
+	},
+	Brc721OwnershipRegistered {
+		block_height: u32,
+		collection_id: Brc721CollectionId,
+		initial_owner: H160,
+		slots_bundles: Vec<Vec<RangeInclusive<u128>>>,
+		txid: Txid,
+	},
+	Brc721TokenBurned {
+		block_height: u32,
+		collection_id: Brc721CollectionId,
+		token_id: Brc721TokenId,
+		txid: Txid,
+	},
+	Brc721TokenRebased {
+		block_height: u32,
+		collection_id: Brc721CollectionId,
+		token_id: Brc721TokenId,
+		token_uri: String,
+		txid: Txid,
+	},
+	Brc721TokensTransferred {
+		block_height: u32,
+		collection_id: Brc721CollectionId,
+		initial_owner: H160,
+		new_location: Option<OutPoint>,
+		old_location: OutPoint,
+		slots: RangeInclusive<u128>,
+		txid: Txid,
+	},
 	InscriptionCreated {
 		block_height: u32,
 		charms: u16,
@@ -33,6 +90,11 @@ pub enum Event {
 		old_location: SatPoint,
 		sequence_number: u32,
 	},
+	/// The index was rolled back by a reorg to `block_height`. Events of blocks from
+	/// `block_height` on were reverted.
+	Reorg {
+		block_height: u32,
+	},
 	RuneBurned {
 		amount: u128,
 		block_height: u32,
@@ -58,3 +120,25 @@ pub enum Event {
 		txid: Txid,
 	},
 }
+
+impl Event {
+	pub fn block_height(&self) -> u32 {
+		match self {
+			Self::BlockIndexed { block_height, .. } |
+			Self::Brc721CollectionRegistered { block_height, .. } |
+			Self::Brc721CollectionReverted { block_height, .. } |
+			Self::Brc721OwnershipReverted { block_height, .. } |
+			Self::Brc721OwnershipRegistered { block_height, .. } |
+			Self::Brc721TokenBurned { block_height, .. } |
+			Self::Brc721TokenRebased { block_height, .. } |
+			Self::Brc721TokensTransferred { block_height, .. } |
+			Self::InscriptionCreated { block_height, .. } |
+			Self::InscriptionTransferred { block_height, .. } |
+			Self::Reorg { block_height } |
+			Self::RuneBurned { block_height, .. } |
+			Self::RuneEtched { block_height, .. } |
+			Self::RuneMinted { block_height, .. } |
+			Self::RuneTransferred { block_height, .. } => *block_height,
+		}
+	}
+}
@@ -57,6 +57,45 @@ impl Fetcher {
 	}
 
 	pub(crate) async fn get_transactions(&self, txids: Vec<Txid>) -> Result<Vec<Transaction>> {
+		let results = self.get_raw_transactions(&txids).await?;
+
+		// Return early on any error, because we need all results to proceed
+		if let Some(err) = results.iter().find_map(|res| res.error.as_ref()) {
+			return Err(anyhow!(
+				"failed to fetch raw transaction: code {} message {}",
+				err.code,
+				err.message
+			));
+		}
+
+		results.into_iter().map(Self::decode_transaction).collect()
+	}
+
+	/// Fetches the transactions with `txids`, returning `None` for transactions that are neither
+	/// in the mempool nor in a block, like mempool transactions that were evicted after being
+	/// listed.
+	pub(crate) async fn get_mempool_transactions(
+		&self,
+		txids: Vec<Txid>,
+	) -> Result<Vec<Option<Transaction>>> {
+		self.get_raw_transactions(&txids)
+			.await?
+			.into_iter()
+			.map(|res| match &res.error {
+				Some(JsonError { code: -5 | -8, .. }) => Ok(None),
+				Some(err) => Err(anyhow!(
+					"failed to fetch raw transaction: code {} message {}",
+					err.code,
+					err.message
+				)),
+				None => Self::decode_transaction(res).map(Some),
+			})
+			.collect()
+	}
+
+	/// Requests the raw transactions with `txids` in a single batch, returning the responses in
+	/// the order of `txids`.
+	async fn get_raw_transactions(&self, txids: &[Txid]) -> Result<Vec<JsonResponse<String>>> {
 		if txids.is_empty() {
 			return Ok(Vec::new());
 		}
@@ -98,38 +137,25 @@ impl Fetcher {
 			break;
 		}
 
-		// Return early on any error, because we need all results to proceed
-		if let Some(err) = results.iter().find_map(|res| res.error.as_ref()) {
-			return Err(anyhow!(
-				"failed to fetch raw transaction: code {} message {}",
-				err.code,
-				err.message
-			));
-		}
-
 		// Results from batched JSON-RPC requests can come back in any order, so we must sort them
 		// by id
 		results.sort_by(|a, b| a.id.cmp(&b.id));
 
-		let txs =
-			results
-				.into_iter()
-				.map(|res| {
-					res.result
-						.ok_or_else(|| anyhow!("Missing result for batched JSON-RPC response"))
-						.and_then(|str| {
-							hex::decode(str).map_err(|e| {
-								anyhow!("Result for batched JSON-RPC response not valid hex: {e}")
-							})
-						})
-						.and_then(|hex| {
-							consensus::deserialize(&hex).map_err(|e| {
-								anyhow!("Result for batched JSON-RPC response not valid bitcoin tx: {e}")
-							})
-						})
+		Ok(results)
+	}
+
+	fn decode_transaction(res: JsonResponse<String>) -> Result<Transaction> {
+		res.result
+			.ok_or_else(|| anyhow!("Missing result for batched JSON-RPC response"))
+			.and_then(|str| {
+				hex::decode(str)
+					.map_err(|e| anyhow!("Result for batched JSON-RPC response not valid hex: {e}"))
+			})
+			.and_then(|hex| {
+				consensus::deserialize(&hex).map_err(|e| {
+					anyhow!("Result for batched JSON-RPC response not valid bitcoin tx: {e}")
 				})
-				.collect::<Result<Vec<Transaction>>>()?;
-		Ok(txs)
+			})
 	}
 
 	async fn try_get_transactions(&self, body: String) -> Result<Vec<JsonResponse<String>>> {
@@ -36,10 +36,6 @@ impl Display for Error {
 
 impl std::error::Error for Error {}
 
-const MAX_SAVEPOINTS: u32 = 2;
-const SAVEPOINT_INTERVAL: u32 = 10;
-const CHAIN_TIP_DISTANCE: u32 = 21;
-
 pub(crate) struct Reorg {}
 
 impl Reorg {
@@ -49,8 +45,11 @@ impl Reorg {
 		match index.block_hash(height.checked_sub(1))? {
 			Some(index_prev_blockhash) if index_prev_blockhash == bitcoind_prev_blockhash => Ok(()),
 			Some(index_prev_blockhash) if index_prev_blockhash != bitcoind_prev_blockhash => {
-				let max_recoverable_reorg_depth =
-					(MAX_SAVEPOINTS - 1) * SAVEPOINT_INTERVAL + height % SAVEPOINT_INTERVAL;
+				let savepoint_interval = index.settings.savepoint_interval();
+
+				let max_recoverable_reorg_depth = (index.settings.max_savepoints() - 1) *
+					savepoint_interval +
+					height % savepoint_interval;
 
 				for depth in 1..max_recoverable_reorg_depth {
 					let index_block_hash = index.block_hash(height.checked_sub(depth))?;
@@ -70,31 +69,100 @@ impl Reorg {
 		}
 	}
 
-	pub(crate) fn handle_reorg(index: &Index, height: u32, depth: u32) -> Result {
+	/// Rolls the index back to the oldest savepoint. Returns false if there is no savepoint.
+	pub(crate) fn handle_reorg(index: &Index, height: u32, depth: u32) -> Result<bool> {
 		log::info!("rolling back database after reorg of depth {depth} at height {height}");
 
 		if let redb::Durability::None = index.durability {
 			panic!("set index durability to `Durability::Immediate` to test reorg handling");
 		}
 
+		// snapshot of the database before rolling back, to find out what was reverted
+		let rtx = index.database.begin_read()?;
+
 		let mut wtx = index.begin_write()?;
 
-		let oldest_savepoint =
-			wtx.get_persistent_savepoint(wtx.list_persistent_savepoints()?.min().unwrap())?;
+		// savepoints are discarded when restoring a snapshot
+		let Some(oldest_savepoint) = wtx.list_persistent_savepoints()?.min() else {
+			return Ok(false);
+		};
+
+		let oldest_savepoint = wtx.get_persistent_savepoint(oldest_savepoint)?;
 
 		wtx.restore_savepoint(&oldest_savepoint)?;
 
 		Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
 		wtx.commit()?;
 
-		log::info!(
-			"successfully rolled back database to height {}",
-			index.begin_read()?.block_count()?
-		);
+		let block_count = index.begin_read()?.block_count()?;
+
+		log::info!("successfully rolled back database to height {block_count}");
+
+		Self::send_reverted_events(index, &rtx, block_count)?;
+
+		Ok(true)
+	}
+
+	/// Sends the events announcing that the index was rolled back to `block_count` blocks, given
+	/// `before`, the database before being rolled back.
+	pub(crate) fn send_reverted_events(
+		index: &Index,
+		before: &redb::ReadTransaction,
+		block_count: u32,
+	) -> Result {
+		if let Some(sender) = index.event_sender.as_ref() {
+			sender.blocking_send(Event::Reorg { block_height: block_count })?;
+
+			if index.index_brc721 {
+				for event in Self::brc721_reverted_events(before, block_count)? {
+					sender.blocking_send(event)?;
+				}
+			}
+		}
 
 		Ok(())
 	}
 
+	/// Returns the events of the BRC721 collections and ownerships registered in `before`, the
+	/// database before being rolled back, from `height` on.
+	fn brc721_reverted_events(before: &redb::ReadTransaction, height: u32) -> Result<Vec<Event>> {
+		let mut events = Vec::new();
+
+		for result in before
+			.open_table(BRC721_COLLECTION_ID_TO_BRC721_COLLECTION_VALUE)?
+			.range((u64::from(height), 0)..)?
+		{
+			let (block, tx) = result?.0.value();
+
+			events.push(Event::Brc721CollectionReverted {
+				block_height: height,
+				collection_id: Brc721CollectionId { block, tx },
+			});
+		}
+
+		let mut registrations = BTreeMap::<Brc721RegistrationKey, Vec<SlotRangeValue>>::new();
+
+		for result in before.open_multimap_table(BRC721_HEIGHT_TO_REGISTRATION)?.range(height..)? {
+			for registration in result?.1 {
+				let (key, range) = registration?.value();
+				registrations.entry(key).or_default().push(range);
+			}
+		}
+
+		for ((initial_owner, block, tx), mut ranges) in registrations {
+			ranges.sort();
+
+			events.push(Event::Brc721OwnershipReverted {
+				block_height: height,
+				collection_id: Brc721CollectionId { block, tx },
+				initial_owner: H160::from(initial_owner),
+				slots: ranges.into_iter().map(|(start, end)| start..=end).collect(),
+			});
+		}
+
+		Ok(events)
+	}
+
 	pub(crate) fn update_savepoints(index: &Index, height: u32) -> Result {
 		if let redb::Durability::None = index.durability {
 			return Ok(());
@@ -112,15 +180,17 @@ impl Reorg {
 
 		let blocks = index.client.get_blockchain_info()?.headers;
 
-		if (height < SAVEPOINT_INTERVAL.into() ||
-			height.saturating_sub(last_savepoint_height) >= SAVEPOINT_INTERVAL.into()) &&
-			blocks.saturating_sub(height) <= CHAIN_TIP_DISTANCE.into()
+		let savepoint_interval = u64::from(index.settings.savepoint_interval());
+
+		if (height < savepoint_interval ||
+			height.saturating_sub(last_savepoint_height) >= savepoint_interval) &&
+			blocks.saturating_sub(height) <= index.settings.chain_tip_distance().into()
 		{
 			let wtx = index.begin_write()?;
 
 			let savepoints = wtx.list_persistent_savepoints()?.collect::<Vec<u64>>();
 
-			if savepoints.len() >= usize::try_from(MAX_SAVEPOINTS).unwrap() {
+			if savepoints.len() >= usize::try_from(index.settings.max_savepoints()).unwrap() {
 				wtx.delete_persistent_savepoint(savepoints.into_iter().min().unwrap())?;
 			}
 
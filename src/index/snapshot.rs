@@ -0,0 +1,491 @@
+// Copyright 2023-2024 Freeverse.io
+// This file is part of LAOS.
+
+// LAOS is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// LAOS is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use bitcoin::hashes::{sha256, HashEngine};
+use brotli::{enc::writer::CompressorWriter, Decompressor};
+use redb::{Key, MultimapTableDefinition, ReadTransaction, TableDefinition, TableError, Value};
+use std::io::BufRead;
+
+const BUFFER_SIZE: usize = 1 << 16;
+
+/// Metadata of an index snapshot archive.
+///
+/// An archive is this metadata as a line of JSON, followed by a brotli-compressed copy of the
+/// index, whose SHA-256 is `sha256`.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct SnapshotArchive {
+	pub chain: Chain,
+	pub first_brc721_height: u32,
+	pub hash: BlockHash,
+	pub height: u32,
+	pub index_addresses: bool,
+	pub index_brc721: bool,
+	pub index_brc721_only: bool,
+	pub index_inscriptions: bool,
+	pub index_runes: bool,
+	pub index_sats: bool,
+	pub index_transactions: bool,
+	pub schema_version: u64,
+	pub sha256: sha256::Hash,
+}
+
+impl SnapshotArchive {
+	/// Writes an archive of a consistent copy of the index to `path`.
+	pub(crate) fn create(index: &Index, path: &Path) -> Result<Self> {
+		let rtx = index.begin_read()?;
+
+		let Some(height) = rtx.block_height()?.map(|height| height.n()) else {
+			bail!("cannot snapshot an empty index");
+		};
+
+		let hash = rtx.block_hash(Some(height))?.unwrap();
+
+		let tempdir = tempfile::tempdir_in(path.parent().unwrap_or(Path::new(".")))?;
+
+		let database = tempdir.path().join("index.redb");
+
+		log::info!("copying index at height {height}");
+
+		write_database(&rtx.0, &database)?;
+
+		let archive = Self {
+			chain: index.settings.chain(),
+			first_brc721_height: index.settings.first_brc721_height(),
+			hash,
+			height,
+			index_addresses: index.index_addresses,
+			index_brc721: index.index_brc721,
+			index_brc721_only: index.index_brc721_only,
+			index_inscriptions: index.index_inscriptions,
+			index_runes: index.index_runes,
+			index_sats: index.index_sats,
+			index_transactions: index.index_transactions,
+			schema_version: SCHEMA_VERSION,
+			sha256: checksum(File::open(&database)?, io::sink())?,
+		};
+
+		log::info!("compressing index snapshot to {}", path.display());
+
+		let mut writer = BufWriter::new(File::create(path)?);
+
+		serde_json::to_writer(&mut writer, &archive)?;
+		writeln!(writer)?;
+
+		let mut compressor = CompressorWriter::new(writer, BUFFER_SIZE, 6, 22);
+		io::copy(&mut File::open(&database)?, &mut compressor)?;
+		compressor.into_inner().flush()?;
+
+		Ok(archive)
+	}
+
+	/// Validates the archive at `path` against `settings` and the connected node, and installs it
+	/// as the index.
+	pub(crate) fn restore(settings: &Settings, path: &Path) -> Result<Self> {
+		let index = settings.index();
+
+		ensure!(
+			!index.exists(),
+			"index already exists at `{}`, remove it before restoring a snapshot",
+			index.display()
+		);
+
+		let mut reader = BufReader::new(File::open(path)?);
+
+		let mut line = String::new();
+		reader.read_line(&mut line)?;
+
+		let archive = serde_json::from_str::<Self>(&line)
+			.with_context(|| format!("`{}` is not an index snapshot", path.display()))?;
+
+		ensure!(
+			archive.schema_version == SCHEMA_VERSION,
+			"snapshot has index schema {}, laos-btc index schema is {SCHEMA_VERSION}",
+			archive.schema_version,
+		);
+
+		ensure!(
+			archive.chain == settings.chain(),
+			"snapshot is for {}, not {}",
+			archive.chain,
+			settings.chain(),
+		);
+
+		for (flag, snapshot, setting) in [
+			("--index-addresses", archive.index_addresses, settings.index_addresses_raw()),
+			("--index-brc721", archive.index_brc721, settings.index_brc721_raw()),
+			("--index-brc721-only", archive.index_brc721_only, settings.index_brc721_only()),
+			("--index-inscriptions", archive.index_inscriptions, settings.index_inscriptions_raw()),
+			("--index-runes", archive.index_runes, settings.index_runes_raw()),
+			("--index-sats", archive.index_sats, settings.index_sats_raw()),
+			("--index-transactions", archive.index_transactions, settings.index_transactions_raw()),
+		] {
+			ensure!(
+				snapshot == setting,
+				"snapshot was built {} `{flag}`",
+				if snapshot { "with" } else { "without" }
+			);
+		}
+
+		ensure!(
+			!archive.index_brc721 || archive.first_brc721_height == settings.first_brc721_height(),
+			"snapshot was built with first brc721 height {}, not {}",
+			archive.first_brc721_height,
+			settings.first_brc721_height(),
+		);
+
+		let client = settings.bitcoin_rpc_client(None)?;
+
+		ensure!(
+			client.get_block_hash(archive.height.into()).into_option()? == Some(archive.hash),
+			"snapshot block {} at height {} is not in the best chain of the connected node",
+			archive.hash,
+			archive.height,
+		);
+
+		if let Some(parent) = index.parent() {
+			fs::create_dir_all(parent)?;
+		}
+
+		let mut partial = index.as_os_str().to_owned();
+		partial.push(".partial");
+		let partial = PathBuf::from(partial);
+
+		log::info!("decompressing index snapshot at height {}", archive.height);
+
+		let sha256 = {
+			let mut writer = BufWriter::new(File::create(&partial)?);
+			let sha256 = checksum(Decompressor::new(reader, BUFFER_SIZE), &mut writer)?;
+			writer.flush()?;
+			sha256
+		};
+
+		if sha256 != archive.sha256 {
+			fs::remove_file(&partial)?;
+			bail!("snapshot checksum mismatch: expected {}, got {sha256}", archive.sha256);
+		}
+
+		fs::rename(&partial, index)?;
+
+		Ok(archive)
+	}
+}
+
+/// An index snapshot, a copy of the index taken when `hash` was the block at `height`.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Snapshot {
+	pub(crate) hash: BlockHash,
+	pub(crate) height: u32,
+	pub(crate) path: PathBuf,
+}
+
+impl Snapshot {
+	/// Returns the snapshots of the index, oldest first.
+	pub(crate) fn list(index: &Index) -> Result<Vec<Self>> {
+		Self::list_dir(&Self::dir(index))
+	}
+
+	fn list_dir(dir: &Path) -> Result<Vec<Self>> {
+		if !dir.exists() {
+			return Ok(Vec::new());
+		}
+
+		let mut snapshots = Vec::new();
+
+		for entry in fs::read_dir(dir)? {
+			let path = entry?.path();
+
+			let Some((height, hash)) = path
+				.file_name()
+				.and_then(|name| name.to_str())
+				.and_then(|name| name.strip_suffix(".redb"))
+				.and_then(|name| name.split_once('-'))
+			else {
+				continue;
+			};
+
+			let (Ok(height), Ok(hash)) = (height.parse(), hash.parse()) else {
+				continue;
+			};
+
+			snapshots.push(Self { hash, height, path });
+		}
+
+		snapshots.sort_by_key(|snapshot| snapshot.height);
+
+		Ok(snapshots)
+	}
+
+	fn dir(index: &Index) -> PathBuf {
+		index.path.with_file_name("snapshots")
+	}
+
+	/// Takes a snapshot of the index every `--snapshot-interval` blocks close to the chain tip,
+	/// keeping the newest `--max-snapshots`.
+	///
+	/// The snapshot is copied from a read transaction on a background thread, so indexing is not
+	/// blocked while it is written.
+	pub(crate) fn update(index: &Index) -> Result {
+		let Some(interval) = index.settings.snapshot_interval() else {
+			return Ok(());
+		};
+
+		// snapshots are taken one at a time, so while one is still being written, this one is
+		// skipped
+		{
+			let mut snapshot_thread = index.snapshot_thread.lock().unwrap();
+
+			if snapshot_thread.as_ref().is_some_and(|thread| !thread.is_finished()) {
+				return Ok(());
+			}
+
+			if let Some(thread) = snapshot_thread.take() {
+				match thread.join() {
+					Ok(Ok(())) => {},
+					Ok(Err(error)) => log::warn!("taking index snapshot failed: {error}"),
+					Err(_) => log::warn!("index snapshot thread panicked"),
+				}
+			}
+		}
+
+		let rtx = index.begin_read()?;
+
+		let Some(height) = rtx.block_height()?.map(|height| height.n()) else {
+			return Ok(());
+		};
+
+		let dir = Self::dir(index);
+
+		if let Some(last) = Self::list_dir(&dir)?.last() {
+			if height < last.height.saturating_add(interval) {
+				return Ok(());
+			}
+		}
+
+		let blocks = index.client.get_blockchain_info()?.headers;
+
+		if blocks.saturating_sub(height.into()) > index.settings.chain_tip_distance().into() {
+			return Ok(());
+		}
+
+		let hash = rtx.block_hash(Some(height))?.unwrap();
+
+		let max_snapshots = usize::try_from(index.settings.max_snapshots()).unwrap();
+
+		let thread = thread::spawn(move || {
+			fs::create_dir_all(&dir)?;
+
+			let path = dir.join(format!("{height}-{hash}.redb"));
+			let partial = path.with_extension("redb.partial");
+
+			log::info!("taking index snapshot at height {height}");
+
+			if partial.exists() {
+				fs::remove_file(&partial)?;
+			}
+
+			write_database(&rtx.0, &partial)?;
+
+			fs::rename(&partial, &path)?;
+
+			let mut snapshots = Self::list_dir(&dir)?;
+
+			if snapshots.len() > max_snapshots {
+				for snapshot in snapshots.drain(..snapshots.len() - max_snapshots) {
+					fs::remove_file(snapshot.path)?;
+				}
+			}
+
+			Ok(())
+		});
+
+		index.snapshot_thread.lock().unwrap().replace(thread);
+
+		Ok(())
+	}
+
+	/// Waits for the snapshot being taken in the background, if any, returning its result.
+	pub(crate) fn wait(index: &Index) -> Result {
+		let Some(thread) = index.snapshot_thread.lock().unwrap().take() else {
+			return Ok(());
+		};
+
+		thread.join().map_err(|_| anyhow!("index snapshot thread panicked"))?
+	}
+
+	/// Rolls the index back to the newest snapshot whose block is still in the best chain.
+	///
+	/// Savepoints and the snapshots of reorged blocks are discarded. Returns the height of the
+	/// snapshot, or `None` if there is no usable snapshot.
+	pub(crate) fn restore(index: &Index) -> Result<Option<u32>> {
+		if let Err(error) = Self::wait(index) {
+			log::warn!("taking index snapshot failed: {error}");
+		}
+
+		let mut snapshots = Self::list(index)?;
+
+		while let Some(snapshot) = snapshots.pop() {
+			if index.client.get_block_hash(snapshot.height.into()).into_option()? !=
+				Some(snapshot.hash)
+			{
+				log::info!("discarding index snapshot of reorged block {}", snapshot.hash);
+				fs::remove_file(&snapshot.path)?;
+				continue;
+			}
+
+			log::info!("restoring index snapshot at height {}", snapshot.height);
+
+			let database = Database::open(&snapshot.path)?;
+
+			// the index before restoring, to find out what was reverted
+			let before = index.database.begin_read()?;
+
+			let wtx = index.begin_write()?;
+
+			copy_tables(&database.begin_read()?, &wtx)?;
+
+			let savepoints = wtx.list_persistent_savepoints()?.collect::<Vec<u64>>();
+
+			for savepoint in savepoints {
+				wtx.delete_persistent_savepoint(savepoint)?;
+			}
+
+			Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+
+			wtx.commit()?;
+
+			log::info!("successfully restored index snapshot at height {}", snapshot.height);
+
+			Reorg::send_reverted_events(index, &before, index.begin_read()?.block_count()?)?;
+
+			return Ok(Some(snapshot.height));
+		}
+
+		Ok(None)
+	}
+}
+
+/// Copies reader into writer, returning the SHA-256 of the copied bytes.
+fn checksum(mut reader: impl Read, mut writer: impl Write) -> Result<sha256::Hash> {
+	let mut engine = sha256::Hash::engine();
+	let mut buffer = vec![0; BUFFER_SIZE];
+
+	loop {
+		let n = reader.read(&mut buffer)?;
+
+		if n == 0 {
+			return Ok(sha256::Hash::from_engine(engine));
+		}
+
+		engine.input(&buffer[..n]);
+		writer.write_all(&buffer[..n])?;
+	}
+}
+
+/// Creates a database at `path` with the contents of every index table in `rtx`.
+fn write_database(rtx: &ReadTransaction, path: &Path) -> Result {
+	let database = Database::create(path)?;
+	let wtx = database.begin_write()?;
+	copy_tables(rtx, &wtx)?;
+	wtx.commit()?;
+	Ok(())
+}
+
+/// Replaces the contents of every index table in `wtx` with their contents in `rtx`.
+fn copy_tables(rtx: &ReadTransaction, wtx: &WriteTransaction) -> Result {
+	let mut copier = TableCopier { copied: 0, rtx, wtx };
+
+	visit_tables(&mut copier)?;
+
+	let tables = rtx.list_tables()?.count() + rtx.list_multimap_tables()?.count();
+
+	ensure!(copier.copied == tables, "copied {} of {tables} index tables", copier.copied);
+
+	Ok(())
+}
+
+struct TableCopier<'a> {
+	copied: usize,
+	rtx: &'a ReadTransaction,
+	wtx: &'a WriteTransaction,
+}
+
+impl TableVisitor for TableCopier<'_> {
+	fn table<K: Key + 'static, V: Value + 'static>(
+		&mut self,
+		definition: TableDefinition<K, V>,
+	) -> Result {
+		self.copied += copy_table(self.rtx, self.wtx, definition)?;
+		Ok(())
+	}
+
+	fn multimap_table<K: Key + 'static, V: Key + 'static>(
+		&mut self,
+		definition: MultimapTableDefinition<K, V>,
+	) -> Result {
+		self.copied += copy_multimap_table(self.rtx, self.wtx, definition)?;
+		Ok(())
+	}
+}
+
+fn copy_table<K: Key + 'static, V: Value + 'static>(
+	rtx: &ReadTransaction,
+	wtx: &WriteTransaction,
+	definition: TableDefinition<K, V>,
+) -> Result<usize> {
+	wtx.delete_table(definition)?;
+
+	let source = match rtx.open_table(definition) {
+		Ok(source) => source,
+		Err(TableError::TableDoesNotExist(_)) => return Ok(0),
+		Err(error) => return Err(error.into()),
+	};
+
+	let mut destination = wtx.open_table(definition)?;
+
+	for result in source.iter()? {
+		let (key, value) = result?;
+		destination.insert(key.value(), value.value())?;
+	}
+
+	Ok(1)
+}
+
+fn copy_multimap_table<K: Key + 'static, V: Key + 'static>(
+	rtx: &ReadTransaction,
+	wtx: &WriteTransaction,
+	definition: MultimapTableDefinition<K, V>,
+) -> Result<usize> {
+	wtx.delete_multimap_table(definition)?;
+
+	let source = match rtx.open_multimap_table(definition) {
+		Ok(source) => source,
+		Err(TableError::TableDoesNotExist(_)) => return Ok(0),
+		Err(error) => return Err(error.into()),
+	};
+
+	let mut destination = wtx.open_multimap_table(definition)?;
+
+	for result in source.iter()? {
+		let (key, values) = result?;
+
+		for value in values {
+			destination.insert(key.value(), value?.value())?;
+		}
+	}
+
+	Ok(1)
+}
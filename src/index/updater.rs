@@ -15,17 +15,22 @@
 // along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
 
 use self::{
-	brc721_updater::Brc721Updater, inscription_updater::InscriptionUpdater,
+	brc721_updater::{Brc721Updater, Brc721WriteTables},
+	inscription_updater::InscriptionUpdater,
 	rune_updater::RuneUpdater,
 };
 use super::{fetcher::Fetcher, *};
 use futures::future::try_join_all;
+use ordinals::brc721::{address_mapping::btc_address_to_h160, is_brc721_script};
 use tokio::sync::{
 	broadcast::{self, error::TryRecvError},
 	mpsc::{self},
 };
 
-pub(crate) use brc721_updater::RegisterCollectionValue;
+pub(crate) use brc721_updater::{
+	Brc721BurnValue, Brc721RegistrationKey, Brc721RegistrationValue, Brc721TokenKey,
+	Brc721TokenLocationValue, Brc721TokenRangeValue, RegisterCollectionValue, SlotRangeValue,
+};
 
 mod brc721_updater;
 mod inscription_updater;
@@ -34,25 +39,89 @@ mod rune_updater;
 pub(crate) struct BlockData {
 	pub(crate) header: Header,
 	pub(crate) txdata: Vec<(Transaction, Txid)>,
+	/// Whether the first output of each transaction is a BRC721 script, if BRC721 operations are
+	/// indexed at this height, otherwise empty.
+	pub(crate) brc721_scripts: Vec<bool>,
+	/// Envelopes of each transaction, if inscriptions are indexed at this height, otherwise empty.
+	pub(crate) envelopes: Vec<Vec<ParsedEnvelope>>,
+	/// Runestone of each transaction, if runes are indexed at this height, otherwise empty.
+	pub(crate) runestones: Vec<Option<Artifact>>,
 }
 
-impl From<Block> for BlockData {
-	fn from(block: Block) -> Self {
-		BlockData {
-			header: block.header,
-			txdata: block
-				.txdata
-				.into_iter()
-				.map(|transaction| {
-					let txid = transaction.compute_txid();
-					(transaction, txid)
-				})
-				.collect(),
+/// Decodes the artifacts of the protocols indexed at each height.
+///
+/// Blocks are decoded by the fetcher threads, in parallel, so the updater thread only has to
+/// index them.
+#[derive(Clone, Copy)]
+struct Decoder {
+	first_brc721_height: Option<u32>,
+	first_inscription_height: Option<u32>,
+	first_rune_height: Option<u32>,
+}
+
+impl Decoder {
+	fn new(index: &Index) -> Self {
+		Self {
+			first_brc721_height: index.index_brc721.then(|| index.settings.first_brc721_height()),
+			first_inscription_height: index
+				.index_inscriptions
+				.then(|| index.settings.first_inscription_height()),
+			first_rune_height: index.index_runes.then(|| index.settings.first_rune_height()),
 		}
 	}
+
+	fn brc721(self, height: u32) -> bool {
+		self.first_brc721_height.is_some_and(|first| height >= first)
+	}
+
+	fn inscriptions(self, height: u32) -> bool {
+		self.first_inscription_height.is_some_and(|first| height >= first)
+	}
+
+	fn runes(self, height: u32) -> bool {
+		self.first_rune_height.is_some_and(|first| height >= first)
+	}
+
+	fn decode(self, height: u32, block: Block) -> BlockData {
+		let txdata = block
+			.txdata
+			.into_iter()
+			.map(|transaction| {
+				let txid = transaction.compute_txid();
+				(transaction, txid)
+			})
+			.collect::<Vec<(Transaction, Txid)>>();
+
+		let brc721_scripts = if self.brc721(height) {
+			txdata
+				.iter()
+				.map(|(tx, _)| {
+					tx.output.first().is_some_and(|output| is_brc721_script(&output.script_pubkey))
+				})
+				.collect()
+		} else {
+			Vec::new()
+		};
+
+		let envelopes = if self.inscriptions(height) {
+			txdata.iter().map(|(tx, _)| ParsedEnvelope::from_transaction(tx)).collect()
+		} else {
+			Vec::new()
+		};
+
+		let runestones = if self.runes(height) {
+			txdata.iter().map(|(tx, _)| Runestone::decipher(tx)).collect()
+		} else {
+			Vec::new()
+		};
+
+		BlockData { header: block.header, txdata, brc721_scripts, envelopes, runestones }
+	}
 }
 
 pub(crate) struct Updater<'index> {
+	/// Events of the blocks indexed since the last commit, sent once they are committed.
+	pub(super) events: Vec<Event>,
 	pub(super) height: u32,
 	pub(super) index: &'index Index,
 	pub(super) outputs_cached: u64,
@@ -164,38 +233,70 @@ impl Updater<'_> {
 		Ok(())
 	}
 
+	/// Fetches and decodes blocks from `height` on `--block-fetch-threads` threads, returning
+	/// them in order.
 	fn fetch_blocks_from(
 		index: &Index,
-		mut height: u32,
+		height: u32,
 	) -> Result<std::sync::mpsc::Receiver<BlockData>> {
 		let (tx, rx) = std::sync::mpsc::sync_channel(32);
 
+		let decoder = Decoder::new(index);
+
 		let first_index_height = index.first_index_height;
 
 		let height_limit = index.height_limit;
 
-		let client = index.settings.bitcoin_rpc_client(None)?;
+		let threads = index.settings.block_fetch_threads();
 
-		thread::spawn(move || loop {
-			if let Some(height_limit) = height_limit {
-				if height >= height_limit {
-					break;
-				}
-			}
+		let step = u32::try_from(threads)?;
+
+		let mut receivers = Vec::new();
+
+		for offset in 0..step {
+			let client = index.settings.bitcoin_rpc_client(None)?;
+
+			let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+
+			receivers.push(receiver);
 
-			match Self::get_block_with_retries(&client, height, first_index_height) {
-				Ok(Some(block)) => {
-					if let Err(err) = tx.send(block.into()) {
-						log::info!("Block receiver disconnected: {err}");
+			let mut height = height + offset;
+
+			thread::spawn(move || loop {
+				if let Some(height_limit) = height_limit {
+					if height >= height_limit {
 						break;
 					}
-					height += 1;
-				},
-				Ok(None) => break,
-				Err(err) => {
-					log::error!("failed to fetch block {height}: {err}");
+				}
+
+				match Self::get_block_with_retries(&client, height, first_index_height) {
+					Ok(Some(block)) => {
+						if sender.send(decoder.decode(height, block)).is_err() {
+							break;
+						}
+						height += step;
+					},
+					Ok(None) => break,
+					Err(err) => {
+						log::error!("failed to fetch block {height}: {err}");
+						break;
+					},
+				}
+			});
+		}
+
+		// each thread fetches every `step`th block, so receiving from them in turn yields blocks in
+		// order, stopping at the first block that could not be fetched
+		thread::spawn(move || {
+			for receiver in receivers.iter().cycle() {
+				let Ok(block) = receiver.recv() else {
 					break;
-				},
+				};
+
+				if let Err(err) = tx.send(block) {
+					log::info!("Block receiver disconnected: {err}");
+					break;
+				}
 			}
 		});
 
@@ -323,11 +424,18 @@ impl Updater<'_> {
 		output_sender: &mut mpsc::Sender<OutPoint>,
 		txout_receiver: &mut broadcast::Receiver<TxOut>,
 		wtx: &mut WriteTransaction,
-		block: BlockData,
+		mut block: BlockData,
 		utxo_cache: &mut HashMap<OutPoint, UtxoEntryBuf>,
 	) -> Result<()> {
 		Reorg::detect_reorg(&block, self.height, self.index)?;
 
+		let decoder = Decoder::new(self.index);
+
+		// events are recorded for the event feed and the webhook outbox
+		let mut events = (self.index.event_sender.is_some() ||
+			self.index.settings.webhook().is_some())
+		.then(Vec::new);
+
 		let start = Instant::now();
 		let mut sat_ranges_written = 0;
 		let mut outputs_in_block = 0;
@@ -347,10 +455,10 @@ impl Updater<'_> {
 		if self.index.index_inscriptions ||
 			self.index.index_addresses ||
 			self.index.index_sats ||
-			self.index.index_brc721
+			(self.index.index_brc721 && !self.index.index_brc721_only)
 		{
 			self.index_utxo_entries(
-				&block,
+				&mut block,
 				txout_receiver,
 				output_sender,
 				utxo_cache,
@@ -359,10 +467,11 @@ impl Updater<'_> {
 				&mut statistic_to_count,
 				&mut sat_ranges_written,
 				&mut outputs_in_block,
+				events.as_mut(),
 			)?;
 		}
 
-		if self.index.index_runes && self.height >= self.index.settings.first_rune_height() {
+		if decoder.runes(self.height) {
 			let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
 			let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
 			let mut rune_to_rune_id = wtx.open_table(RUNE_TO_RUNE_ID)?;
@@ -375,7 +484,7 @@ impl Updater<'_> {
 				.unwrap_or(0);
 
 			let mut rune_updater = RuneUpdater {
-				event_sender: self.index.event_sender.as_ref(),
+				events: events.as_mut(),
 				block_time: block.header.time,
 				burned: HashMap::new(),
 				client: &self.index.client,
@@ -394,29 +503,80 @@ impl Updater<'_> {
 				transaction_id_to_rune: &mut transaction_id_to_rune,
 			};
 
-			for (i, (tx, txid)) in block.txdata.iter().enumerate() {
-				rune_updater.index_runes(u32::try_from(i).unwrap(), tx, *txid)?;
+			for (i, ((tx, txid), runestone)) in
+				block.txdata.iter().zip(mem::take(&mut block.runestones)).enumerate()
+			{
+				rune_updater.index_runes(u32::try_from(i).unwrap(), tx, *txid, runestone)?;
 			}
 
 			rune_updater.update()?;
 		}
 
-		if self.index.index_brc721 {
-			let mut brc721_collection_id_to_brc721_collection_value =
-				wtx.open_table(BRC721_COLLECTION_ID_TO_BRC721_COLLECTION_VALUE)?;
+		if decoder.brc721(self.height) {
+			let mut brc721_tables = Brc721WriteTables {
+				collections: wtx.open_table(BRC721_COLLECTION_ID_TO_BRC721_COLLECTION_VALUE)?,
+				output_tokens: wtx.open_multimap_table(BRC721_OUTPOINT_TO_TOKEN_RANGE)?,
+				owners: (
+					wtx.open_multimap_table(BRC721_SCRIPT_PUBKEY_TO_OUTPOINT)?,
+					wtx.open_table(BRC721_OUTPOINT_TO_SCRIPT_PUBKEY)?,
+				),
+				registration_heights: wtx.open_multimap_table(BRC721_HEIGHT_TO_REGISTRATION)?,
+				registrations: wtx.open_multimap_table(BRC721_INITIAL_OWNER_TO_SLOT_RANGE)?,
+				token_burns: wtx.open_table(BRC721_TOKEN_TO_BURN)?,
+				token_locations: wtx.open_table(BRC721_TOKEN_TO_LOCATION)?,
+				token_uris: wtx.open_table(BRC721_TOKEN_TO_TOKEN_URI)?,
+				txids: wtx.open_table(BRC721_TXID_TO_COLLECTION_ID)?,
+			};
 
-			let mut brc721_collection_updater = Brc721Updater {
+			let mut brc721_updater = Brc721Updater {
+				events: events.as_mut(),
 				height: self.height,
-				collection_table: &mut brc721_collection_id_to_brc721_collection_value,
+				tables: &mut brc721_tables,
 			};
 
-			for (i, (tx, _)) in block.txdata.iter().enumerate() {
-				brc721_collection_updater.index_collections(u32::try_from(i).unwrap(), tx)?;
+			for (i, ((tx, txid), brc721_script)) in
+				block.txdata.iter().zip(&block.brc721_scripts).enumerate()
+			{
+				if *brc721_script {
+					brc721_updater.index_collections(u32::try_from(i).unwrap(), tx)?;
+					brc721_updater.index_rebases(tx)?;
+					brc721_updater.index_burns(tx)?;
+				}
+
+				brc721_updater.index_transfers(tx, *txid)?;
+
+				if *brc721_script {
+					brc721_updater
+						.index_ownerships(tx, |outpoint| self.brc721_owner_of(outpoint))?;
+				}
 			}
 		}
 
 		height_to_block_header.insert(&self.height, &block.header.store())?;
 
+		if let Some(events) = events {
+			let block_hash = block.header.block_hash();
+
+			if self.index.settings.webhook().is_some() {
+				let payload = api::BlockEvents {
+					block_hash,
+					block_height: self.height,
+					events: events.clone(),
+				};
+
+				Index::queue_webhook_payload(
+					&mut wtx.open_table(WEBHOOK_SEQUENCE_NUMBER_TO_PAYLOAD)?,
+					&statistic_to_count,
+					&serde_json::to_string(&payload)?,
+				)?;
+			}
+
+			if self.index.event_sender.is_some() {
+				self.events.extend(events);
+				self.events.push(Event::BlockIndexed { block_hash, block_height: self.height });
+			}
+		}
+
 		self.height += 1;
 		self.outputs_traversed += outputs_in_block;
 
@@ -428,9 +588,28 @@ impl Updater<'_> {
 		Ok(())
 	}
 
+	/// Returns the H160 of the address owning `outpoint`, as used by the BRC721 protocol.
+	fn brc721_owner_of(&self, outpoint: OutPoint) -> Result<Option<H160>> {
+		let Some(tx) = self.index.get_transaction(outpoint.txid)? else {
+			return Ok(None);
+		};
+
+		let Some(output) = tx.output.get(usize::try_from(outpoint.vout).unwrap()) else {
+			return Ok(None);
+		};
+
+		Ok(self
+			.index
+			.settings
+			.chain()
+			.address_from_script(&output.script_pubkey)
+			.ok()
+			.and_then(|address| btc_address_to_h160(address).ok()))
+	}
+
 	fn index_utxo_entries<'wtx>(
 		&mut self,
-		block: &BlockData,
+		block: &mut BlockData,
 		txout_receiver: &mut broadcast::Receiver<TxOut>,
 		output_sender: &mut mpsc::Sender<OutPoint>,
 		utxo_cache: &mut HashMap<OutPoint, UtxoEntryBuf>,
@@ -439,6 +618,7 @@ impl Updater<'_> {
 		statistic_to_count: &mut Table<'wtx, u64, u64>,
 		sat_ranges_written: &mut u64,
 		outputs_in_block: &mut u64,
+		events: Option<&mut Vec<Event>>,
 	) -> Result<(), Error> {
 		let mut height_to_last_sequence_number = wtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
 		let mut home_inscriptions = wtx.open_table(HOME_INSCRIPTIONS)?;
@@ -454,8 +634,7 @@ impl Updater<'_> {
 			wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 		let mut transaction_id_to_transaction = wtx.open_table(TRANSACTION_ID_TO_TRANSACTION)?;
 
-		let index_inscriptions = self.height >= self.index.settings.first_inscription_height() &&
-			self.index.index_inscriptions;
+		let index_inscriptions = Decoder::new(self.index).inscriptions(self.height);
 
 		// If the receiver still has inputs something went wrong in the last
 		// block and we shouldn't recover from this and commit the last block
@@ -528,6 +707,7 @@ impl Updater<'_> {
 		let mut inscription_updater = InscriptionUpdater {
 			blessed_inscription_count,
 			cursed_inscription_count,
+			events,
 			flotsam: Vec::new(),
 			height: self.height,
 			home_inscription_count,
@@ -660,6 +840,7 @@ impl Updater<'_> {
 				inscription_updater.index_inscriptions(
 					tx,
 					*txid,
+					mem::take(&mut block.envelopes[tx_offset]),
 					&input_utxo_entries,
 					&mut output_utxo_entries,
 					utxo_cache,
@@ -867,12 +1048,20 @@ impl Updater<'_> {
 		Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
 		wtx.commit()?;
 
+		if let Some(sender) = &self.index.event_sender {
+			for event in self.events.drain(..) {
+				sender.blocking_send(event)?;
+			}
+		}
+
 		// Commit twice since due to a bug redb will only reuse pages freed in the
 		// transaction before last.
 		self.index.begin_write()?.commit()?;
 
 		Reorg::update_savepoints(self.index, self.height)?;
 
+		Snapshot::update(self.index)?;
+
 		Ok(())
 	}
 }
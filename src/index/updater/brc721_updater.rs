@@ -14,7 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
 
-use ordinals::{brc721::is_brc721_script, RegisterCollection};
+use ordinals::{
+	brc721::{
+		burn_token::BurnToken, is_brc721_script, operations::Brc721Operation,
+		rebase_token::RebaseToken, register_ownership::RegisterOwnership,
+	},
+	RegisterCollection,
+};
+use sp_core::H160;
 
 use super::*;
 
@@ -34,17 +41,276 @@ impl Insertable<Brc721CollectionIdValue, RegisterCollectionValue>
 	}
 }
 
+impl Insertable<Brc721TokenKey, Brc721TokenLocationValue>
+	for Table<'_, Brc721TokenKey, Brc721TokenLocationValue>
+{
+	fn insert(&mut self, key: Brc721TokenKey, value: Brc721TokenLocationValue) -> redb::Result {
+		self.insert(key, value).map(|_| ())
+	}
+}
+
+impl Insertable<Brc721TokenKey, String> for Table<'_, Brc721TokenKey, String> {
+	fn insert(&mut self, key: Brc721TokenKey, value: String) -> redb::Result {
+		self.insert(key, value).map(|_| ())
+	}
+}
+
+impl Insertable<Brc721TokenKey, Brc721BurnValue> for Table<'_, Brc721TokenKey, Brc721BurnValue> {
+	fn insert(&mut self, key: Brc721TokenKey, value: Brc721BurnValue) -> redb::Result {
+		self.insert(key, value).map(|_| ())
+	}
+}
+
+impl Insertable<TxidValue, Brc721CollectionIdValue>
+	for Table<'_, &'static TxidValue, Brc721CollectionIdValue>
+{
+	fn insert(&mut self, key: TxidValue, value: Brc721CollectionIdValue) -> redb::Result {
+		self.insert(&key, value).map(|_| ())
+	}
+}
+
+pub(super) trait Removable<K> {
+	fn remove_value(&mut self, key: K) -> redb::Result;
+}
+
+impl Removable<Brc721TokenKey> for Table<'_, Brc721TokenKey, Brc721TokenLocationValue> {
+	fn remove_value(&mut self, key: Brc721TokenKey) -> redb::Result {
+		self.remove(key).map(|_| ())
+	}
+}
+
+pub(super) trait Gettable<K, V> {
+	fn get_value(&self, key: K) -> redb::Result<Option<V>>;
+}
+
+impl Gettable<Brc721CollectionIdValue, RegisterCollectionValue>
+	for Table<'_, Brc721CollectionIdValue, RegisterCollectionValue>
+{
+	fn get_value(
+		&self,
+		key: Brc721CollectionIdValue,
+	) -> redb::Result<Option<RegisterCollectionValue>> {
+		Ok(self.get(key)?.map(|guard| guard.value()))
+	}
+}
+
+pub(super) trait FloorGettable<K, V> {
+	/// Returns the entry with the greatest key less than or equal to `key`.
+	fn get_floor(&self, key: K) -> redb::Result<Option<(K, V)>>;
+}
+
+impl FloorGettable<Brc721TokenKey, Brc721TokenLocationValue>
+	for Table<'_, Brc721TokenKey, Brc721TokenLocationValue>
+{
+	fn get_floor(
+		&self,
+		key: Brc721TokenKey,
+	) -> redb::Result<Option<(Brc721TokenKey, Brc721TokenLocationValue)>> {
+		self.range(..=key)?
+			.next_back()
+			.transpose()
+			.map(|entry| entry.map(|(key, value)| (key.value(), value.value())))
+	}
+}
+
+pub(super) trait MultimapInsertable<K, V> {
+	fn insert_value(&mut self, key: K, value: V) -> redb::Result;
+	fn get_values(&self, key: K) -> redb::Result<Vec<V>>;
+}
+
+impl MultimapInsertable<Brc721RegistrationKey, SlotRangeValue>
+	for MultimapTable<'_, Brc721RegistrationKey, SlotRangeValue>
+{
+	fn insert_value(&mut self, key: Brc721RegistrationKey, value: SlotRangeValue) -> redb::Result {
+		self.insert(key, value).map(|_| ())
+	}
+
+	fn get_values(&self, key: Brc721RegistrationKey) -> redb::Result<Vec<SlotRangeValue>> {
+		self.get(key)?.map(|value| value.map(|value| value.value())).collect()
+	}
+}
+
+impl MultimapInsertable<u32, Brc721RegistrationValue>
+	for MultimapTable<'_, u32, Brc721RegistrationValue>
+{
+	fn insert_value(&mut self, key: u32, value: Brc721RegistrationValue) -> redb::Result {
+		self.insert(key, value).map(|_| ())
+	}
+
+	fn get_values(&self, key: u32) -> redb::Result<Vec<Brc721RegistrationValue>> {
+		self.get(key)?.map(|value| value.map(|value| value.value())).collect()
+	}
+}
+
+impl MultimapInsertable<OutPointValue, Brc721TokenRangeValue>
+	for MultimapTable<'_, OutPointValue, Brc721TokenRangeValue>
+{
+	fn insert_value(&mut self, key: OutPointValue, value: Brc721TokenRangeValue) -> redb::Result {
+		self.insert(key, value).map(|_| ())
+	}
+
+	fn get_values(&self, key: OutPointValue) -> redb::Result<Vec<Brc721TokenRangeValue>> {
+		self.get(key)?.map(|value| value.map(|value| value.value())).collect()
+	}
+}
+
+pub(super) trait MultimapRemovable<K, V> {
+	fn remove_values(&mut self, key: K) -> redb::Result<Vec<V>>;
+}
+
+impl MultimapRemovable<OutPointValue, Brc721TokenRangeValue>
+	for MultimapTable<'_, OutPointValue, Brc721TokenRangeValue>
+{
+	fn remove_values(&mut self, key: OutPointValue) -> redb::Result<Vec<Brc721TokenRangeValue>> {
+		self.remove_all(key)?.map(|value| value.map(|value| value.value())).collect()
+	}
+}
+
+pub(super) trait OwnerIndexable {
+	/// Records that the output `outpoint`, locked by `script_pubkey`, holds tokens.
+	fn insert_output(&mut self, script_pubkey: &[u8], outpoint: OutPointValue) -> redb::Result;
+	/// Forgets the output `outpoint`, if it held tokens.
+	fn remove_output(&mut self, outpoint: OutPointValue) -> redb::Result;
+}
+
+/// Outputs holding tokens by script pubkey, and script pubkeys of outputs holding tokens.
+pub(super) type Brc721OwnerTables<'txn> =
+	(MultimapTable<'txn, &'static [u8], OutPointValue>, Table<'txn, OutPointValue, &'static [u8]>);
+
+impl OwnerIndexable for Brc721OwnerTables<'_> {
+	fn insert_output(&mut self, script_pubkey: &[u8], outpoint: OutPointValue) -> redb::Result {
+		self.0.insert(script_pubkey, outpoint)?;
+		self.1.insert(outpoint, script_pubkey)?;
+		Ok(())
+	}
+
+	fn remove_output(&mut self, outpoint: OutPointValue) -> redb::Result {
+		if let Some(script_pubkey) = self.1.remove(outpoint)? {
+			self.0.remove(script_pubkey.value(), outpoint)?;
+		}
+		Ok(())
+	}
+}
+
 pub(crate) type RegisterCollectionValue = ([u8; COLLECTION_ADDRESS_LENGTH], bool);
 
+/// Initial owner followed by the collection id the slots were registered in.
+pub(crate) type Brc721RegistrationKey = ([u8; 20], u64, u32);
+
+/// Inclusive range of slots.
+pub(crate) type SlotRangeValue = (u128, u128);
+
+/// Registration key followed by a range of slots registered to it.
+pub(crate) type Brc721RegistrationValue = (Brc721RegistrationKey, SlotRangeValue);
+
+/// Collection id, initial owner and slot of a token. Also used to identify a range of tokens by
+/// its first token.
+pub(crate) type Brc721TokenKey = (u64, u32, [u8; 20], u128);
+
+/// Last slot of a range of tokens followed by the output holding them.
+pub(crate) type Brc721TokenLocationValue = (u128, OutPointValue);
+
+/// Last slot of a range of burned tokens followed by the height they were burned at.
+pub(crate) type Brc721BurnValue = (u128, u32);
+
+/// Collection id, initial owner, first and last slot of a range of tokens.
+pub(crate) type Brc721TokenRangeValue = (u64, u32, [u8; 20], u128, u128);
+
+/// The tables indexed by [`Brc721Updater`].
+pub(super) trait Brc721Tables {
+	type Collections: Insertable<Brc721CollectionIdValue, RegisterCollectionValue>
+		+ Gettable<Brc721CollectionIdValue, RegisterCollectionValue>;
+	type OutputTokens: MultimapInsertable<OutPointValue, Brc721TokenRangeValue>
+		+ MultimapRemovable<OutPointValue, Brc721TokenRangeValue>;
+	type Owners: OwnerIndexable;
+	type RegistrationHeights: MultimapInsertable<u32, Brc721RegistrationValue>;
+	type Registrations: MultimapInsertable<Brc721RegistrationKey, SlotRangeValue>;
+	type TokenBurns: Insertable<Brc721TokenKey, Brc721BurnValue>;
+	type TokenLocations: Insertable<Brc721TokenKey, Brc721TokenLocationValue>
+		+ Removable<Brc721TokenKey>
+		+ FloorGettable<Brc721TokenKey, Brc721TokenLocationValue>;
+	type TokenUris: Insertable<Brc721TokenKey, String>;
+	type Txids: Insertable<TxidValue, Brc721CollectionIdValue>;
+
+	fn collections(&mut self) -> &mut Self::Collections;
+	fn output_tokens(&mut self) -> &mut Self::OutputTokens;
+	fn owners(&mut self) -> &mut Self::Owners;
+	fn registration_heights(&mut self) -> &mut Self::RegistrationHeights;
+	fn registrations(&mut self) -> &mut Self::Registrations;
+	fn token_burns(&mut self) -> &mut Self::TokenBurns;
+	fn token_locations(&mut self) -> &mut Self::TokenLocations;
+	fn token_uris(&mut self) -> &mut Self::TokenUris;
+	fn txids(&mut self) -> &mut Self::Txids;
+}
+
+/// The brc721 tables opened by a write transaction.
+pub(super) struct Brc721WriteTables<'txn> {
+	pub(super) collections: Table<'txn, Brc721CollectionIdValue, RegisterCollectionValue>,
+	pub(super) output_tokens: MultimapTable<'txn, OutPointValue, Brc721TokenRangeValue>,
+	pub(super) owners: Brc721OwnerTables<'txn>,
+	pub(super) registration_heights: MultimapTable<'txn, u32, Brc721RegistrationValue>,
+	pub(super) registrations: MultimapTable<'txn, Brc721RegistrationKey, SlotRangeValue>,
+	pub(super) token_burns: Table<'txn, Brc721TokenKey, Brc721BurnValue>,
+	pub(super) token_locations: Table<'txn, Brc721TokenKey, Brc721TokenLocationValue>,
+	pub(super) token_uris: Table<'txn, Brc721TokenKey, String>,
+	pub(super) txids: Table<'txn, &'static TxidValue, Brc721CollectionIdValue>,
+}
+
+impl<'txn> Brc721Tables for Brc721WriteTables<'txn> {
+	type Collections = Table<'txn, Brc721CollectionIdValue, RegisterCollectionValue>;
+	type OutputTokens = MultimapTable<'txn, OutPointValue, Brc721TokenRangeValue>;
+	type Owners = Brc721OwnerTables<'txn>;
+	type RegistrationHeights = MultimapTable<'txn, u32, Brc721RegistrationValue>;
+	type Registrations = MultimapTable<'txn, Brc721RegistrationKey, SlotRangeValue>;
+	type TokenBurns = Table<'txn, Brc721TokenKey, Brc721BurnValue>;
+	type TokenLocations = Table<'txn, Brc721TokenKey, Brc721TokenLocationValue>;
+	type TokenUris = Table<'txn, Brc721TokenKey, String>;
+	type Txids = Table<'txn, &'static TxidValue, Brc721CollectionIdValue>;
+
+	fn collections(&mut self) -> &mut Self::Collections {
+		&mut self.collections
+	}
+
+	fn output_tokens(&mut self) -> &mut Self::OutputTokens {
+		&mut self.output_tokens
+	}
+
+	fn owners(&mut self) -> &mut Self::Owners {
+		&mut self.owners
+	}
+
+	fn registration_heights(&mut self) -> &mut Self::RegistrationHeights {
+		&mut self.registration_heights
+	}
+
+	fn registrations(&mut self) -> &mut Self::Registrations {
+		&mut self.registrations
+	}
+
+	fn token_burns(&mut self) -> &mut Self::TokenBurns {
+		&mut self.token_burns
+	}
+
+	fn token_locations(&mut self) -> &mut Self::TokenLocations {
+		&mut self.token_locations
+	}
+
+	fn token_uris(&mut self) -> &mut Self::TokenUris {
+		&mut self.token_uris
+	}
+
+	fn txids(&mut self) -> &mut Self::Txids {
+		&mut self.txids
+	}
+}
+
 pub(super) struct Brc721Updater<'a, T> {
+	pub(super) events: Option<&'a mut Vec<Event>>,
 	pub(super) height: u32,
-	pub(super) collection_table: &'a mut T,
+	pub(super) tables: &'a mut T,
 }
 
-impl<T> Brc721Updater<'_, T>
-where
-	T: Insertable<Brc721CollectionIdValue, RegisterCollectionValue>,
-{
+impl<T: Brc721Tables> Brc721Updater<'_, T> {
 	/// Indexes collections from a transaction.
 	///
 	/// # Arguments
@@ -66,39 +332,641 @@ where
 			return Ok(());
 		}
 
+		// other operations are indexed by their own methods
+		if matches!(
+			Brc721Operation::from_script(&script),
+			Some(operation) if operation != Brc721Operation::RegisterCollection
+		) {
+			return Ok(());
+		}
+
 		// Decode the register collection from the first output's script public key.
 		match RegisterCollection::from_script(&script) {
 			Ok(register_collection) => {
-				self.collection_table.insert(
+				let txid = tx.compute_txid();
+
+				self.tables.collections().insert(
 					(self.height.into(), tx_index),
 					(register_collection.address.into(), register_collection.rebaseable),
 				)?;
+
+				self.tables.txids().insert(txid.store(), (self.height.into(), tx_index))?;
+
+				if let Some(events) = &mut self.events {
+					events.push(Event::Brc721CollectionRegistered {
+						block_height: self.height,
+						collection_id: Brc721CollectionId {
+							block: self.height.into(),
+							tx: tx_index,
+						},
+						laos_address: register_collection.address,
+						rebaseable: register_collection.rebaseable,
+						txid,
+					});
+				}
 			},
 			Err(e) => {
 				log::warn!("Failed to decode register collection: {:?}", e);
 			},
 		}
-		Ok(())
+		Ok(())
+	}
+
+	/// Indexes the slots registered by a register ownership operation.
+	///
+	/// The initial owner is the owner of the output spent by the first input, which is resolved
+	/// by `owner_of`. The registration is ignored as a whole if the collection does not exist,
+	/// the initial owner can't be resolved, there are not enough outputs for its slots bundles,
+	/// or any of its slots overlap each other or slots already registered by the same initial
+	/// owner in the same collection.
+	///
+	/// # Arguments
+	/// * `tx` - The transaction to process.
+	/// * `owner_of` - Resolves the owner of a previous output.
+	pub(super) fn index_ownerships(
+		&mut self,
+		tx: &Transaction,
+		owner_of: impl FnOnce(OutPoint) -> Result<Option<H160>>,
+	) -> Result<()> {
+		let Some(first_output) = tx.output.first() else {
+			return Ok(());
+		};
+
+		if Brc721Operation::from_script(&first_output.script_pubkey) !=
+			Some(Brc721Operation::RegisterOwnership)
+		{
+			return Ok(());
+		}
+
+		let register_ownership =
+			match RegisterOwnership::try_from(first_output.script_pubkey.clone()) {
+				Ok(register_ownership) => register_ownership,
+				Err(e) => {
+					log::warn!("Failed to decode register ownership: {:?}", e);
+					return Ok(());
+				},
+			};
+
+		let collection_id = register_ownership.collection_id;
+
+		if self.tables.collections().get_value(collection_id.store())?.is_none() {
+			log::warn!("Ignoring register ownership: collection {collection_id} not found");
+			return Ok(());
+		}
+
+		// the first output carries the operation, slots bundles go to the following ones
+		if register_ownership.slots_bundles.len() >= tx.output.len() {
+			log::warn!("Ignoring register ownership: not enough outputs for slots bundles");
+			return Ok(());
+		}
+
+		let Some(input) = tx.input.first().filter(|input| !input.previous_output.is_null()) else {
+			log::warn!("Ignoring register ownership: initial owner input not found");
+			return Ok(());
+		};
+
+		let Some(initial_owner) = owner_of(input.previous_output)? else {
+			log::warn!("Ignoring register ownership: unsupported initial owner");
+			return Ok(());
+		};
+
+		let key = (initial_owner.into(), collection_id.block, collection_id.tx);
+
+		let registered_slots = self.tables.registrations().get_values(key)?;
+
+		let new_slots = register_ownership
+			.slots_bundles
+			.iter()
+			.flat_map(|slots_bundle| slots_bundle.0.iter())
+			.map(|range| (*range.start(), *range.end()))
+			.collect::<Vec<SlotRangeValue>>();
+
+		let mut slots = registered_slots.iter().chain(&new_slots).collect::<Vec<_>>();
+		slots.sort();
+
+		if slots.windows(2).any(|pair| pair[0].1 >= pair[1].0) {
+			log::warn!(
+				"Ignoring register ownership: overlapping slots for initial owner {initial_owner:?} in collection {collection_id}"
+			);
+			return Ok(());
+		}
+
+		for range in new_slots {
+			self.tables.registrations().insert_value(key, range)?;
+			self.tables.registration_heights().insert_value(self.height, (key, range))?;
+		}
+
+		let txid = tx.compute_txid();
+
+		for (index, slots_bundle) in register_ownership.slots_bundles.iter().enumerate() {
+			let outpoint = OutPoint { txid, vout: u32::try_from(index + 1).unwrap() }.store();
+
+			self.tables
+				.owners()
+				.insert_output(tx.output[index + 1].script_pubkey.as_bytes(), outpoint)?;
+
+			for range in &slots_bundle.0 {
+				self.tables.output_tokens().insert_value(
+					outpoint,
+					(collection_id.block, collection_id.tx, key.0, *range.start(), *range.end()),
+				)?;
+				self.tables.token_locations().insert(
+					(collection_id.block, collection_id.tx, key.0, *range.start()),
+					(*range.end(), outpoint),
+				)?;
+			}
+		}
+
+		if let Some(events) = &mut self.events {
+			events.push(Event::Brc721OwnershipRegistered {
+				block_height: self.height,
+				collection_id,
+				initial_owner,
+				slots_bundles: register_ownership
+					.slots_bundles
+					.into_iter()
+					.map(|slots_bundle| slots_bundle.0)
+					.collect(),
+				txid,
+			});
+		}
+
+		Ok(())
+	}
+
+	/// Moves the tokens held by the outputs spent by a transaction to its first output that is
+	/// not an OP_RETURN. Tokens are burned, recording the height they were burned at, if there is
+	/// no such output. Spent outputs are removed from the owner index, and the output receiving
+	/// the tokens is added to it.
+	///
+	/// See the token transfers section of `docs/brc721.md` for the rule this implements.
+	///
+	/// # Arguments
+	/// * `tx` - The transaction to process.
+	/// * `txid` - The id of the transaction.
+	pub(super) fn index_transfers(&mut self, tx: &Transaction, txid: Txid) -> Result<()> {
+		let destination_vout =
+			tx.output.iter().position(|output| !output.script_pubkey.is_op_return());
+
+		let destination = destination_vout
+			.map(|vout| OutPoint { txid, vout: u32::try_from(vout).unwrap() }.store());
+
+		for input in tx.input.iter().filter(|input| !input.previous_output.is_null()) {
+			let ranges =
+				self.tables.output_tokens().remove_values(input.previous_output.store())?;
+
+			// outputs emptied by burns of the same transaction are forgotten as well
+			self.tables.owners().remove_output(input.previous_output.store())?;
+
+			if let Some((vout, destination)) =
+				destination_vout.zip(destination).filter(|_| !ranges.is_empty())
+			{
+				self.tables
+					.owners()
+					.insert_output(tx.output[vout].script_pubkey.as_bytes(), destination)?;
+			}
+
+			for range in ranges {
+				let (block, tx, initial_owner, start, end) = range;
+
+				match destination {
+					Some(destination) => {
+						self.tables.output_tokens().insert_value(destination, range)?;
+						self.tables
+							.token_locations()
+							.insert((block, tx, initial_owner, start), (end, destination))?;
+					},
+					None => {
+						self.tables.token_locations().remove_value((
+							block,
+							tx,
+							initial_owner,
+							start,
+						))?;
+						self.tables
+							.token_burns()
+							.insert((block, tx, initial_owner, start), (end, self.height))?;
+					},
+				}
+
+				if let Some(events) = &mut self.events {
+					events.push(Event::Brc721TokensTransferred {
+						block_height: self.height,
+						collection_id: Brc721CollectionId { block, tx },
+						initial_owner: H160::from(initial_owner),
+						new_location: destination.map(OutPoint::load),
+						old_location: input.previous_output,
+						slots: start..=end,
+						txid,
+					});
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Indexes the token uri set by a rebase token operation.
+	///
+	/// The rebase is ignored if the collection does not exist or is not rebaseable, the token is
+	/// not registered, or the transaction does not spend the output holding the token, which
+	/// proves it was sent by the current owner. It must be indexed before the transfers of the
+	/// same transaction, which move the token out of the spent output.
+	///
+	/// # Arguments
+	/// * `tx` - The transaction to process.
+	pub(super) fn index_rebases(&mut self, tx: &Transaction) -> Result<()> {
+		let Some(first_output) = tx.output.first() else {
+			return Ok(());
+		};
+
+		if Brc721Operation::from_script(&first_output.script_pubkey) !=
+			Some(Brc721Operation::RebaseToken)
+		{
+			return Ok(());
+		}
+
+		let rebase_token = match RebaseToken::try_from(first_output.script_pubkey.clone()) {
+			Ok(rebase_token) => rebase_token,
+			Err(e) => {
+				log::warn!("Failed to decode rebase token: {:?}", e);
+				return Ok(());
+			},
+		};
+
+		let collection_id = rebase_token.collection_id;
+		let token_id = rebase_token.token_id;
+
+		let Some((_, rebaseable)) = self.tables.collections().get_value(collection_id.store())?
+		else {
+			log::warn!("Ignoring rebase token: collection {collection_id} not found");
+			return Ok(());
+		};
+
+		if !rebaseable {
+			log::warn!("Ignoring rebase token: collection {collection_id} is not rebaseable");
+			return Ok(());
+		}
+
+		let key =
+			(collection_id.block, collection_id.tx, token_id.initial_owner.into(), token_id.slot);
+
+		let Some(outpoint) = self.tables.token_locations().get_floor(key)?.and_then(
+			|((block, tx, initial_owner, _), (end, outpoint))| {
+				((block, tx, initial_owner) == (key.0, key.1, key.2) && end >= key.3)
+					.then(|| OutPoint::load(outpoint))
+			},
+		) else {
+			log::warn!(
+				"Ignoring rebase token: token {token_id} not found in collection {collection_id}"
+			);
+			return Ok(());
+		};
+
+		if !tx.input.iter().any(|input| input.previous_output == outpoint) {
+			log::warn!(
+				"Ignoring rebase token: token {token_id} of collection {collection_id} not spent by its owner"
+			);
+			return Ok(());
+		}
+
+		self.tables.token_uris().insert(key, rebase_token.token_uri.clone())?;
+
+		if let Some(events) = &mut self.events {
+			events.push(Event::Brc721TokenRebased {
+				block_height: self.height,
+				collection_id,
+				token_id,
+				token_uri: rebase_token.token_uri,
+				txid: tx.compute_txid(),
+			});
+		}
+
+		Ok(())
+	}
+
+	/// Retires the token burned by a burn token operation, recording the height it was burned
+	/// at.
+	///
+	/// The burn is ignored if the token is not registered or the transaction does not spend the
+	/// output holding the token. The token is split off its range, whose remaining tokens stay in
+	/// the spent output, so it must be indexed before the transfers of the same transaction,
+	/// which move them to their new output.
+	///
+	/// # Arguments
+	/// * `tx` - The transaction to process.
+	pub(super) fn index_burns(&mut self, tx: &Transaction) -> Result<()> {
+		let Some(first_output) = tx.output.first() else {
+			return Ok(());
+		};
+
+		if Brc721Operation::from_script(&first_output.script_pubkey) !=
+			Some(Brc721Operation::BurnToken)
+		{
+			return Ok(());
+		}
+
+		let burn_token = match BurnToken::try_from(first_output.script_pubkey.clone()) {
+			Ok(burn_token) => burn_token,
+			Err(e) => {
+				log::warn!("Failed to decode burn token: {:?}", e);
+				return Ok(());
+			},
+		};
+
+		let collection_id = burn_token.collection_id;
+		let token_id = burn_token.token_id;
+
+		let key =
+			(collection_id.block, collection_id.tx, token_id.initial_owner.into(), token_id.slot);
+
+		let Some((start, end, outpoint)) = self.tables.token_locations().get_floor(key)?.and_then(
+			|((block, tx, initial_owner, start), (end, outpoint))| {
+				((block, tx, initial_owner) == (key.0, key.1, key.2) && end >= key.3)
+					.then_some((start, end, outpoint))
+			},
+		) else {
+			log::warn!(
+				"Ignoring burn token: token {token_id} not found in collection {collection_id}"
+			);
+			return Ok(());
+		};
+
+		if !tx.input.iter().any(|input| input.previous_output.store() == outpoint) {
+			log::warn!(
+				"Ignoring burn token: token {token_id} of collection {collection_id} not spent by its owner"
+			);
+			return Ok(());
+		}
+
+		let burned = (key.0, key.1, key.2, start, end);
+
+		for range in self.tables.output_tokens().remove_values(outpoint)? {
+			if range != burned {
+				self.tables.output_tokens().insert_value(outpoint, range)?;
+			}
+		}
+
+		self.tables.token_locations().remove_value((key.0, key.1, key.2, start))?;
+
+		let remaining =
+			[(start < key.3).then(|| (start, key.3 - 1)), (key.3 < end).then(|| (key.3 + 1, end))];
+
+		for (first, last) in remaining.into_iter().flatten() {
+			self.tables
+				.output_tokens()
+				.insert_value(outpoint, (key.0, key.1, key.2, first, last))?;
+			self.tables
+				.token_locations()
+				.insert((key.0, key.1, key.2, first), (last, outpoint))?;
+		}
+
+		self.tables.token_burns().insert(key, (key.3, self.height))?;
+
+		if let Some(events) = &mut self.events {
+			events.push(Event::Brc721TokenBurned {
+				block_height: self.height,
+				collection_id,
+				token_id,
+				txid: tx.compute_txid(),
+			});
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bitcoin::Transaction;
+	use ordinals::{brc721::register_ownership::SlotsBundle, Brc721TokenId};
+	use std::collections::{BTreeMap, HashMap};
+
+	impl Insertable<Brc721CollectionIdValue, RegisterCollectionValue>
+		for HashMap<Brc721CollectionIdValue, RegisterCollectionValue>
+	{
+		fn insert(
+			&mut self,
+			key: Brc721CollectionIdValue,
+			value: RegisterCollectionValue,
+		) -> redb::Result<()> {
+			HashMap::insert(self, key, value);
+			Ok(())
+		}
+	}
+
+	impl Gettable<Brc721CollectionIdValue, RegisterCollectionValue>
+		for HashMap<Brc721CollectionIdValue, RegisterCollectionValue>
+	{
+		fn get_value(
+			&self,
+			key: Brc721CollectionIdValue,
+		) -> redb::Result<Option<RegisterCollectionValue>> {
+			Ok(self.get(&key).copied())
+		}
+	}
+
+	impl MultimapInsertable<Brc721RegistrationKey, SlotRangeValue>
+		for HashMap<Brc721RegistrationKey, Vec<SlotRangeValue>>
+	{
+		fn insert_value(
+			&mut self,
+			key: Brc721RegistrationKey,
+			value: SlotRangeValue,
+		) -> redb::Result {
+			self.entry(key).or_default().push(value);
+			Ok(())
+		}
+
+		fn get_values(&self, key: Brc721RegistrationKey) -> redb::Result<Vec<SlotRangeValue>> {
+			Ok(self.get(&key).cloned().unwrap_or_default())
+		}
+	}
+
+	impl MultimapInsertable<u32, Brc721RegistrationValue>
+		for HashMap<u32, Vec<Brc721RegistrationValue>>
+	{
+		fn insert_value(&mut self, key: u32, value: Brc721RegistrationValue) -> redb::Result {
+			self.entry(key).or_default().push(value);
+			Ok(())
+		}
+
+		fn get_values(&self, key: u32) -> redb::Result<Vec<Brc721RegistrationValue>> {
+			Ok(self.get(&key).cloned().unwrap_or_default())
+		}
+	}
+
+	impl MultimapInsertable<OutPointValue, Brc721TokenRangeValue>
+		for HashMap<OutPointValue, Vec<Brc721TokenRangeValue>>
+	{
+		fn insert_value(
+			&mut self,
+			key: OutPointValue,
+			value: Brc721TokenRangeValue,
+		) -> redb::Result {
+			self.entry(key).or_default().push(value);
+			Ok(())
+		}
+
+		fn get_values(&self, key: OutPointValue) -> redb::Result<Vec<Brc721TokenRangeValue>> {
+			Ok(self.get(&key).cloned().unwrap_or_default())
+		}
+	}
+
+	impl MultimapRemovable<OutPointValue, Brc721TokenRangeValue>
+		for HashMap<OutPointValue, Vec<Brc721TokenRangeValue>>
+	{
+		fn remove_values(
+			&mut self,
+			key: OutPointValue,
+		) -> redb::Result<Vec<Brc721TokenRangeValue>> {
+			Ok(self.remove(&key).unwrap_or_default())
+		}
+	}
+
+	impl Insertable<Brc721TokenKey, Brc721TokenLocationValue>
+		for BTreeMap<Brc721TokenKey, Brc721TokenLocationValue>
+	{
+		fn insert(&mut self, key: Brc721TokenKey, value: Brc721TokenLocationValue) -> redb::Result {
+			BTreeMap::insert(self, key, value);
+			Ok(())
+		}
+	}
+
+	impl Removable<Brc721TokenKey> for BTreeMap<Brc721TokenKey, Brc721TokenLocationValue> {
+		fn remove_value(&mut self, key: Brc721TokenKey) -> redb::Result {
+			self.remove(&key);
+			Ok(())
+		}
+	}
+
+	impl FloorGettable<Brc721TokenKey, Brc721TokenLocationValue>
+		for BTreeMap<Brc721TokenKey, Brc721TokenLocationValue>
+	{
+		fn get_floor(
+			&self,
+			key: Brc721TokenKey,
+		) -> redb::Result<Option<(Brc721TokenKey, Brc721TokenLocationValue)>> {
+			Ok(self.range(..=key).next_back().map(|(key, value)| (*key, *value)))
+		}
+	}
+
+	impl Insertable<Brc721TokenKey, String> for HashMap<Brc721TokenKey, String> {
+		fn insert(&mut self, key: Brc721TokenKey, value: String) -> redb::Result {
+			HashMap::insert(self, key, value);
+			Ok(())
+		}
+	}
+
+	impl Insertable<Brc721TokenKey, Brc721BurnValue> for HashMap<Brc721TokenKey, Brc721BurnValue> {
+		fn insert(&mut self, key: Brc721TokenKey, value: Brc721BurnValue) -> redb::Result {
+			HashMap::insert(self, key, value);
+			Ok(())
+		}
+	}
+
+	impl Insertable<TxidValue, Brc721CollectionIdValue>
+		for HashMap<TxidValue, Brc721CollectionIdValue>
+	{
+		fn insert(&mut self, key: TxidValue, value: Brc721CollectionIdValue) -> redb::Result {
+			HashMap::insert(self, key, value);
+			Ok(())
+		}
+	}
+
+	impl OwnerIndexable for HashMap<OutPointValue, Vec<u8>> {
+		fn insert_output(&mut self, script_pubkey: &[u8], outpoint: OutPointValue) -> redb::Result {
+			HashMap::insert(self, outpoint, script_pubkey.to_vec());
+			Ok(())
+		}
+
+		fn remove_output(&mut self, outpoint: OutPointValue) -> redb::Result {
+			self.remove(&outpoint);
+			Ok(())
+		}
+	}
+
+	#[derive(Default)]
+	struct Maps {
+		collections: HashMap<Brc721CollectionIdValue, RegisterCollectionValue>,
+		output_tokens: HashMap<OutPointValue, Vec<Brc721TokenRangeValue>>,
+		owners: HashMap<OutPointValue, Vec<u8>>,
+		registration_heights: HashMap<u32, Vec<Brc721RegistrationValue>>,
+		registrations: HashMap<Brc721RegistrationKey, Vec<SlotRangeValue>>,
+		token_burns: HashMap<Brc721TokenKey, Brc721BurnValue>,
+		token_locations: BTreeMap<Brc721TokenKey, Brc721TokenLocationValue>,
+		token_uris: HashMap<Brc721TokenKey, String>,
+		txids: HashMap<TxidValue, Brc721CollectionIdValue>,
+	}
+
+	impl Brc721Tables for Maps {
+		type Collections = HashMap<Brc721CollectionIdValue, RegisterCollectionValue>;
+		type OutputTokens = HashMap<OutPointValue, Vec<Brc721TokenRangeValue>>;
+		type Owners = HashMap<OutPointValue, Vec<u8>>;
+		type RegistrationHeights = HashMap<u32, Vec<Brc721RegistrationValue>>;
+		type Registrations = HashMap<Brc721RegistrationKey, Vec<SlotRangeValue>>;
+		type TokenBurns = HashMap<Brc721TokenKey, Brc721BurnValue>;
+		type TokenLocations = BTreeMap<Brc721TokenKey, Brc721TokenLocationValue>;
+		type TokenUris = HashMap<Brc721TokenKey, String>;
+		type Txids = HashMap<TxidValue, Brc721CollectionIdValue>;
+
+		fn collections(&mut self) -> &mut Self::Collections {
+			&mut self.collections
+		}
+
+		fn output_tokens(&mut self) -> &mut Self::OutputTokens {
+			&mut self.output_tokens
+		}
+
+		fn owners(&mut self) -> &mut Self::Owners {
+			&mut self.owners
+		}
+
+		fn registration_heights(&mut self) -> &mut Self::RegistrationHeights {
+			&mut self.registration_heights
+		}
+
+		fn registrations(&mut self) -> &mut Self::Registrations {
+			&mut self.registrations
+		}
+
+		fn token_burns(&mut self) -> &mut Self::TokenBurns {
+			&mut self.token_burns
+		}
+
+		fn token_locations(&mut self) -> &mut Self::TokenLocations {
+			&mut self.token_locations
+		}
+
+		fn token_uris(&mut self) -> &mut Self::TokenUris {
+			&mut self.token_uris
+		}
+
+		fn txids(&mut self) -> &mut Self::Txids {
+			&mut self.txids
+		}
 	}
-}
 
-#[cfg(test)]
-mod tests {
-	use super::*;
-	use bitcoin::Transaction;
-	use sp_core::H160;
-	use std::collections::HashMap;
+	#[derive(Default)]
+	struct Tables {
+		events: Vec<Event>,
+		maps: Maps,
+	}
 
-	impl Insertable<Brc721CollectionIdValue, RegisterCollectionValue>
-		for HashMap<Brc721CollectionIdValue, RegisterCollectionValue>
-	{
-		fn insert(
-			&mut self,
-			key: Brc721CollectionIdValue,
-			value: RegisterCollectionValue,
-		) -> redb::Result<()> {
-			HashMap::insert(self, key, value);
-			Ok(())
+	impl Tables {
+		fn with_collection(rebaseable: bool) -> Self {
+			Self {
+				maps: Maps {
+					collections: HashMap::from([((1, 1), (COLLECTION_ADDRESS, rebaseable))]),
+					..default()
+				},
+				..default()
+			}
+		}
+
+		fn updater(&mut self, height: u32) -> Brc721Updater<'_, Maps> {
+			Brc721Updater { events: Some(&mut self.events), height, tables: &mut self.maps }
 		}
 	}
 
@@ -134,48 +1002,47 @@ mod tests {
 		let expected_rebaseable = true;
 		let expected_tx_index = 5;
 
-		let mut id_to_collection = HashMap::new();
+		let mut tables = Tables::default();
 
-		let mut updater =
-			Brc721Updater { height: expected_height, collection_table: &mut id_to_collection };
+		let mut updater = tables.updater(expected_height);
 
 		let tx = brc721_collection_tx(expected_rebaseable);
 		assert_eq!(tx.output.len(), 1);
 
 		updater.index_collections(expected_tx_index, &tx).unwrap();
 
-		assert_eq!(id_to_collection.len(), 1);
+		assert_eq!(tables.maps.collections.len(), 1);
 		let key = (expected_height.into(), expected_tx_index);
-		assert!(id_to_collection.contains_key(&key));
+		assert!(tables.maps.collections.contains_key(&key));
 
-		let (address, rebaseable) = id_to_collection.get(&key).unwrap();
+		let (address, rebaseable) = tables.maps.collections.get(&key).unwrap();
 		assert_eq!(*address, COLLECTION_ADDRESS);
 		assert_eq!(*rebaseable, expected_rebaseable);
+
+		assert_eq!(tables.maps.txids, HashMap::from([(tx.compute_txid().store(), key)]));
 	}
 
 	#[test]
 	fn test_no_collections() {
 		let expected_height = 100u32;
-		let mut id_to_collection = HashMap::new();
+		let mut tables = Tables::default();
 
-		let mut updater =
-			Brc721Updater { height: expected_height, collection_table: &mut id_to_collection };
+		let mut updater = tables.updater(expected_height);
 
 		let tx_index = 5;
 		let tx = empty_tx();
 
 		updater.index_collections(tx_index, &tx).unwrap();
 
-		assert_eq!(id_to_collection.len(), 0);
+		assert_eq!(tables.maps.collections.len(), 0);
 	}
 
 	#[test]
 	fn test_multiple_transactions() {
 		let expected_height = 100u32;
-		let mut id_to_collection = HashMap::new();
+		let mut tables = Tables::default();
 
-		let mut updater =
-			Brc721Updater { height: expected_height, collection_table: &mut id_to_collection };
+		let mut updater = tables.updater(expected_height);
 
 		let transactions =
 			[(0, brc721_collection_tx(true)), (1, brc721_collection_tx(false)), (2, empty_tx())];
@@ -184,20 +1051,588 @@ mod tests {
 			updater.index_collections(*tx_index, tx).unwrap();
 		}
 
-		assert_eq!(id_to_collection.len(), 2);
-		assert!(id_to_collection.contains_key(&(expected_height.into(), 0)));
-		assert!(id_to_collection.contains_key(&(expected_height.into(), 1)));
-		assert!(!id_to_collection.contains_key(&(expected_height.into(), 2)));
+		assert_eq!(tables.maps.collections.len(), 2);
+		assert!(tables.maps.collections.contains_key(&(expected_height.into(), 0)));
+		assert!(tables.maps.collections.contains_key(&(expected_height.into(), 1)));
+		assert!(!tables.maps.collections.contains_key(&(expected_height.into(), 2)));
 
-		assert!(id_to_collection.get(&(expected_height.into(), 0)).unwrap().1);
+		assert!(tables.maps.collections.get(&(expected_height.into(), 0)).unwrap().1);
 		assert_eq!(
-			id_to_collection.get(&(expected_height.into(), 0)).unwrap().0,
+			tables.maps.collections.get(&(expected_height.into(), 0)).unwrap().0,
 			COLLECTION_ADDRESS
 		);
-		assert!(!id_to_collection.get(&(expected_height.into(), 1)).unwrap().1);
+		assert!(!tables.maps.collections.get(&(expected_height.into(), 1)).unwrap().1);
 		assert_eq!(
-			id_to_collection.get(&(expected_height.into(), 0)).unwrap().0,
+			tables.maps.collections.get(&(expected_height.into(), 0)).unwrap().0,
 			COLLECTION_ADDRESS
 		);
 	}
+
+	const INITIAL_OWNER: [u8; 20] = [0x11; 20];
+
+	fn brc721_ownership_tx(
+		collection_id: Brc721CollectionId,
+		slots: &[&[(u128, u128)]],
+	) -> Transaction {
+		let register_ownership = RegisterOwnership {
+			collection_id,
+			slots_bundles: slots
+				.iter()
+				.map(|ranges| {
+					SlotsBundle(ranges.iter().map(|(start, end)| *start..=*end).collect())
+				})
+				.collect(),
+		};
+
+		let mut output =
+			vec![TxOut { value: Amount::ZERO, script_pubkey: register_ownership.into() }];
+		output.extend(
+			slots
+				.iter()
+				.map(|_| TxOut { value: Amount::ONE_SAT, script_pubkey: ScriptBuf::new() }),
+		);
+
+		Transaction {
+			version: Version(1),
+			lock_time: LockTime::from_height(1000).unwrap(),
+			input: vec![TxIn {
+				previous_output: OutPoint { txid: Txid::all_zeros(), vout: 0 },
+				script_sig: ScriptBuf::new(),
+				sequence: Sequence::MAX,
+				witness: Witness::new(),
+			}],
+			output,
+		}
+	}
+
+	fn initial_owner(_: OutPoint) -> Result<Option<H160>> {
+		Ok(Some(H160::from_slice(&INITIAL_OWNER)))
+	}
+
+	#[test]
+	fn register_ownership_of_existing_collection() {
+		let mut tables = Tables::with_collection(false);
+
+		let mut updater = tables.updater(2);
+
+		let tx = brc721_ownership_tx(
+			Brc721CollectionId { block: 1, tx: 1 },
+			&[&[(0, 3), (10, 10)], &[(4, 9)]],
+		);
+
+		updater.index_collections(1, &tx).unwrap();
+		updater.index_ownerships(&tx, initial_owner).unwrap();
+
+		assert_eq!(tables.maps.collections.len(), 1);
+		assert_eq!(
+			tables.maps.registrations.get(&(INITIAL_OWNER, 1, 1)).unwrap(),
+			&vec![(0, 3), (10, 10), (4, 9)]
+		);
+		assert_eq!(
+			tables.maps.registration_heights.get(&2).unwrap(),
+			&vec![
+				((INITIAL_OWNER, 1, 1), (0, 3)),
+				((INITIAL_OWNER, 1, 1), (10, 10)),
+				((INITIAL_OWNER, 1, 1), (4, 9)),
+			]
+		);
+	}
+
+	#[test]
+	fn register_ownership_of_unexistent_collection_is_ignored() {
+		let mut tables = Tables::default();
+
+		let mut updater = tables.updater(2);
+
+		let tx = brc721_ownership_tx(Brc721CollectionId { block: 1, tx: 1 }, &[&[(0, 3)]]);
+
+		updater.index_ownerships(&tx, initial_owner).unwrap();
+
+		assert!(tables.maps.registrations.is_empty());
+	}
+
+	#[test]
+	fn register_ownership_with_overlapping_bundles_is_ignored() {
+		let mut tables = Tables::with_collection(false);
+
+		let mut updater = tables.updater(2);
+
+		let tx =
+			brc721_ownership_tx(Brc721CollectionId { block: 1, tx: 1 }, &[&[(0, 3)], &[(3, 5)]]);
+
+		updater.index_ownerships(&tx, initial_owner).unwrap();
+
+		assert!(tables.maps.registrations.is_empty());
+	}
+
+	#[test]
+	fn register_ownership_of_already_registered_slots_is_ignored() {
+		let mut tables = Tables::with_collection(false);
+
+		let mut updater = tables.updater(2);
+
+		let collection_id = Brc721CollectionId { block: 1, tx: 1 };
+
+		updater
+			.index_ownerships(&brc721_ownership_tx(collection_id, &[&[(0, 3)]]), initial_owner)
+			.unwrap();
+		updater
+			.index_ownerships(&brc721_ownership_tx(collection_id, &[&[(3, 3)]]), initial_owner)
+			.unwrap();
+		updater
+			.index_ownerships(&brc721_ownership_tx(collection_id, &[&[(4, 4)]]), initial_owner)
+			.unwrap();
+
+		assert_eq!(
+			tables.maps.registrations.get(&(INITIAL_OWNER, 1, 1)).unwrap(),
+			&vec![(0, 3), (4, 4)]
+		);
+	}
+
+	#[test]
+	fn register_ownership_of_same_slots_by_different_initial_owners() {
+		let mut tables = Tables::with_collection(false);
+
+		let mut updater = tables.updater(2);
+
+		let tx = brc721_ownership_tx(Brc721CollectionId { block: 1, tx: 1 }, &[&[(0, 3)]]);
+
+		updater.index_ownerships(&tx, initial_owner).unwrap();
+		updater
+			.index_ownerships(&tx, |_| Ok(Some(H160::from_slice(&[0x22; 20]))))
+			.unwrap();
+
+		assert_eq!(tables.maps.registrations.len(), 2);
+		assert_eq!(tables.maps.registrations.get(&([0x22; 20], 1, 1)).unwrap(), &vec![(0, 3)]);
+	}
+
+	#[test]
+	fn register_ownership_without_initial_owner_is_ignored() {
+		let mut tables = Tables::with_collection(false);
+
+		let mut updater = tables.updater(2);
+
+		let tx = brc721_ownership_tx(Brc721CollectionId { block: 1, tx: 1 }, &[&[(0, 3)]]);
+
+		updater.index_ownerships(&tx, |_| Ok(None)).unwrap();
+
+		assert!(tables.maps.registrations.is_empty());
+	}
+
+	#[test]
+	fn register_ownership_without_enough_outputs_is_ignored() {
+		let mut tables = Tables::with_collection(false);
+
+		let mut updater = tables.updater(2);
+
+		let mut tx =
+			brc721_ownership_tx(Brc721CollectionId { block: 1, tx: 1 }, &[&[(0, 3)], &[(4, 5)]]);
+		tx.output.pop();
+
+		updater.index_ownerships(&tx, initial_owner).unwrap();
+
+		assert!(tables.maps.registrations.is_empty());
+	}
+
+	fn spending_tx(inputs: &[OutPoint], outputs: Vec<ScriptBuf>) -> Transaction {
+		Transaction {
+			version: Version(1),
+			lock_time: LockTime::from_height(1000).unwrap(),
+			input: inputs
+				.iter()
+				.map(|previous_output| TxIn {
+					previous_output: *previous_output,
+					script_sig: ScriptBuf::new(),
+					sequence: Sequence::MAX,
+					witness: Witness::new(),
+				})
+				.collect(),
+			output: outputs
+				.into_iter()
+				.map(|script_pubkey| TxOut { value: Amount::ONE_SAT, script_pubkey })
+				.collect(),
+		}
+	}
+
+	fn rebase_tx(inputs: &[OutPoint], slot: u128, token_uri: &str) -> Transaction {
+		spending_tx(
+			inputs,
+			vec![
+				RebaseToken {
+					collection_id: Brc721CollectionId { block: 1, tx: 1 },
+					token_id: Brc721TokenId {
+						slot,
+						initial_owner: H160::from_slice(&INITIAL_OWNER),
+					},
+					token_uri: token_uri.into(),
+				}
+				.into(),
+				ScriptBuf::new(),
+			],
+		)
+	}
+
+	fn register_tokens(tables: &mut Tables) -> OutPoint {
+		let tx = brc721_ownership_tx(Brc721CollectionId { block: 1, tx: 1 }, &[&[(0, 3)]]);
+
+		tables.updater(2).index_ownerships(&tx, initial_owner).unwrap();
+
+		OutPoint { txid: tx.compute_txid(), vout: 1 }
+	}
+
+	#[test]
+	fn register_ownership_assigns_slots_bundles_to_outputs() {
+		let mut tables = Tables::with_collection(false);
+
+		let tx = brc721_ownership_tx(
+			Brc721CollectionId { block: 1, tx: 1 },
+			&[&[(0, 3), (10, 10)], &[(4, 9)]],
+		);
+
+		tables.updater(2).index_ownerships(&tx, initial_owner).unwrap();
+
+		let txid = tx.compute_txid();
+		let first = OutPoint { txid, vout: 1 }.store();
+		let second = OutPoint { txid, vout: 2 }.store();
+
+		assert_eq!(
+			tables.maps.output_tokens.get(&first).unwrap(),
+			&vec![(1, 1, INITIAL_OWNER, 0, 3), (1, 1, INITIAL_OWNER, 10, 10)]
+		);
+		assert_eq!(
+			tables.maps.output_tokens.get(&second).unwrap(),
+			&vec![(1, 1, INITIAL_OWNER, 4, 9)]
+		);
+		assert_eq!(
+			tables.maps.token_locations,
+			BTreeMap::from([
+				((1, 1, INITIAL_OWNER, 0), (3, first)),
+				((1, 1, INITIAL_OWNER, 4), (9, second)),
+				((1, 1, INITIAL_OWNER, 10), (10, first)),
+			])
+		);
+	}
+
+	#[test]
+	fn transfer_moves_tokens_to_first_non_op_return_output() {
+		let mut tables = Tables::with_collection(false);
+
+		let outpoint = register_tokens(&mut tables);
+
+		let tx = spending_tx(
+			&[outpoint],
+			vec![ScriptBuf::new_op_return([]), ScriptBuf::new(), ScriptBuf::new()],
+		);
+		let txid = tx.compute_txid();
+
+		tables.updater(3).index_transfers(&tx, txid).unwrap();
+
+		let destination = OutPoint { txid, vout: 1 }.store();
+
+		assert!(!tables.maps.output_tokens.contains_key(&outpoint.store()));
+		assert_eq!(
+			tables.maps.output_tokens.get(&destination).unwrap(),
+			&vec![(1, 1, INITIAL_OWNER, 0, 3)]
+		);
+		assert_eq!(
+			tables.maps.token_locations,
+			BTreeMap::from([((1, 1, INITIAL_OWNER, 0), (3, destination))])
+		);
+	}
+
+	#[test]
+	fn transfer_without_non_op_return_output_burns_tokens() {
+		let mut tables = Tables::with_collection(false);
+
+		let outpoint = register_tokens(&mut tables);
+
+		let tx = spending_tx(&[outpoint], vec![ScriptBuf::new_op_return([])]);
+
+		tables.updater(3).index_transfers(&tx, tx.compute_txid()).unwrap();
+
+		assert!(tables.maps.output_tokens.is_empty());
+		assert!(tables.maps.token_locations.is_empty());
+		assert_eq!(tables.maps.token_burns, HashMap::from([((1, 1, INITIAL_OWNER, 0), (3, 3))]));
+	}
+
+	#[test]
+	fn owner_index_follows_tokens() {
+		let mut tables = Tables::with_collection(false);
+
+		let outpoint = register_tokens(&mut tables);
+
+		assert_eq!(tables.maps.owners, HashMap::from([(outpoint.store(), Vec::new())]));
+
+		let script_pubkey = ScriptBuf::from_bytes(vec![0x51]);
+
+		let tx =
+			spending_tx(&[outpoint], vec![ScriptBuf::new_op_return([]), script_pubkey.clone()]);
+		let txid = tx.compute_txid();
+
+		tables.updater(3).index_transfers(&tx, txid).unwrap();
+
+		assert_eq!(
+			tables.maps.owners,
+			HashMap::from([(OutPoint { txid, vout: 1 }.store(), script_pubkey.to_bytes())])
+		);
+
+		let tx = spending_tx(&[OutPoint { txid, vout: 1 }], vec![ScriptBuf::new_op_return([])]);
+
+		tables.updater(4).index_transfers(&tx, tx.compute_txid()).unwrap();
+
+		assert!(tables.maps.owners.is_empty());
+	}
+
+	#[test]
+	fn owner_index_forgets_outputs_emptied_by_burns() {
+		let mut tables = Tables::with_collection(false);
+
+		let tx = brc721_ownership_tx(Brc721CollectionId { block: 1, tx: 1 }, &[&[(0, 0)]]);
+
+		tables.updater(2).index_ownerships(&tx, initial_owner).unwrap();
+
+		let tx = burn_tx(&[OutPoint { txid: tx.compute_txid(), vout: 1 }], 0);
+
+		let mut updater = tables.updater(3);
+		updater.index_burns(&tx).unwrap();
+		updater.index_transfers(&tx, tx.compute_txid()).unwrap();
+
+		assert!(tables.maps.owners.is_empty());
+	}
+
+	#[test]
+	fn transfer_sends_event() {
+		let mut tables = Tables::with_collection(false);
+
+		let outpoint = register_tokens(&mut tables);
+
+		let tx = spending_tx(&[outpoint], vec![ScriptBuf::new_op_return([])]);
+		let txid = tx.compute_txid();
+
+		tables.events.clear();
+
+		tables.updater(3).index_transfers(&tx, txid).unwrap();
+
+		assert_eq!(
+			tables.events,
+			[Event::Brc721TokensTransferred {
+				block_height: 3,
+				collection_id: Brc721CollectionId { block: 1, tx: 1 },
+				initial_owner: H160::from_slice(&INITIAL_OWNER),
+				new_location: None,
+				old_location: outpoint,
+				slots: 0..=3,
+				txid,
+			}]
+		);
+	}
+
+	#[test]
+	fn rebase_token_of_rebaseable_collection() {
+		let mut tables = Tables::with_collection(true);
+
+		let outpoint = register_tokens(&mut tables);
+
+		let tx = rebase_tx(&[outpoint], 2, "ipfs://foo");
+
+		tables.updater(3).index_rebases(&tx).unwrap();
+
+		assert_eq!(
+			tables.maps.token_uris,
+			HashMap::from([((1, 1, INITIAL_OWNER, 2), "ipfs://foo".to_string())])
+		);
+	}
+
+	#[test]
+	fn rebase_token_overwrites_previous_token_uri() {
+		let mut tables = Tables::with_collection(true);
+
+		let outpoint = register_tokens(&mut tables);
+
+		let tx = rebase_tx(&[outpoint], 2, "ipfs://foo");
+		let mut updater = tables.updater(3);
+		updater.index_rebases(&tx).unwrap();
+		updater.index_transfers(&tx, tx.compute_txid()).unwrap();
+
+		let tx = rebase_tx(&[OutPoint { txid: tx.compute_txid(), vout: 1 }], 2, "ipfs://bar");
+		tables.updater(4).index_rebases(&tx).unwrap();
+
+		assert_eq!(
+			tables.maps.token_uris,
+			HashMap::from([((1, 1, INITIAL_OWNER, 2), "ipfs://bar".to_string())])
+		);
+	}
+
+	#[test]
+	fn rebase_token_of_non_rebaseable_collection_is_ignored() {
+		let mut tables = Tables::with_collection(false);
+
+		let outpoint = register_tokens(&mut tables);
+
+		tables
+			.updater(3)
+			.index_rebases(&rebase_tx(&[outpoint], 2, "ipfs://foo"))
+			.unwrap();
+
+		assert!(tables.maps.token_uris.is_empty());
+	}
+
+	#[test]
+	fn rebase_unregistered_token_is_ignored() {
+		let mut tables = Tables::with_collection(true);
+
+		let outpoint = register_tokens(&mut tables);
+
+		tables
+			.updater(3)
+			.index_rebases(&rebase_tx(&[outpoint], 4, "ipfs://foo"))
+			.unwrap();
+
+		assert!(tables.maps.token_uris.is_empty());
+	}
+
+	#[test]
+	fn rebase_token_not_spending_its_output_is_ignored() {
+		let mut tables = Tables::with_collection(true);
+
+		register_tokens(&mut tables);
+
+		let tx = rebase_tx(&[OutPoint { txid: Txid::all_zeros(), vout: 0 }], 2, "ipfs://foo");
+
+		tables.updater(3).index_rebases(&tx).unwrap();
+
+		assert!(tables.maps.token_uris.is_empty());
+	}
+
+	fn burn_tx(inputs: &[OutPoint], slot: u128) -> Transaction {
+		spending_tx(
+			inputs,
+			vec![
+				BurnToken {
+					collection_id: Brc721CollectionId { block: 1, tx: 1 },
+					token_id: Brc721TokenId {
+						slot,
+						initial_owner: H160::from_slice(&INITIAL_OWNER),
+					},
+				}
+				.into(),
+				ScriptBuf::new(),
+			],
+		)
+	}
+
+	#[test]
+	fn burn_token_splits_it_off_its_range() {
+		let mut tables = Tables::with_collection(false);
+
+		let outpoint = register_tokens(&mut tables);
+
+		let tx = burn_tx(&[outpoint], 2);
+		let txid = tx.compute_txid();
+
+		let mut updater = tables.updater(3);
+		updater.index_burns(&tx).unwrap();
+		updater.index_transfers(&tx, txid).unwrap();
+
+		let destination = OutPoint { txid, vout: 1 }.store();
+
+		assert_eq!(
+			tables.maps.output_tokens,
+			HashMap::from([(
+				destination,
+				vec![(1, 1, INITIAL_OWNER, 0, 1), (1, 1, INITIAL_OWNER, 3, 3)]
+			)])
+		);
+		assert_eq!(
+			tables.maps.token_locations,
+			BTreeMap::from([
+				((1, 1, INITIAL_OWNER, 0), (1, destination)),
+				((1, 1, INITIAL_OWNER, 3), (3, destination)),
+			])
+		);
+		assert_eq!(tables.maps.token_burns, HashMap::from([((1, 1, INITIAL_OWNER, 2), (2, 3))]));
+	}
+
+	#[test]
+	fn burn_first_and_last_token_of_range() {
+		let mut tables = Tables::with_collection(false);
+
+		let outpoint = register_tokens(&mut tables);
+
+		let tx = burn_tx(&[outpoint], 0);
+		let mut updater = tables.updater(3);
+		updater.index_burns(&tx).unwrap();
+		updater.index_transfers(&tx, tx.compute_txid()).unwrap();
+
+		let outpoint = OutPoint { txid: tx.compute_txid(), vout: 1 };
+
+		let tx = burn_tx(&[outpoint], 3);
+		let mut updater = tables.updater(4);
+		updater.index_burns(&tx).unwrap();
+		updater.index_transfers(&tx, tx.compute_txid()).unwrap();
+
+		let destination = OutPoint { txid: tx.compute_txid(), vout: 1 }.store();
+
+		assert_eq!(
+			tables.maps.token_locations,
+			BTreeMap::from([((1, 1, INITIAL_OWNER, 1), (2, destination))])
+		);
+		assert_eq!(
+			tables.maps.token_burns,
+			HashMap::from([((1, 1, INITIAL_OWNER, 0), (0, 3)), ((1, 1, INITIAL_OWNER, 3), (3, 4))])
+		);
+	}
+
+	#[test]
+	fn burn_unregistered_token_is_ignored() {
+		let mut tables = Tables::with_collection(false);
+
+		let outpoint = register_tokens(&mut tables);
+
+		tables.updater(3).index_burns(&burn_tx(&[outpoint], 4)).unwrap();
+
+		assert!(tables.maps.token_burns.is_empty());
+		assert_eq!(tables.maps.token_locations.len(), 1);
+	}
+
+	#[test]
+	fn burn_token_not_spending_its_output_is_ignored() {
+		let mut tables = Tables::with_collection(false);
+
+		register_tokens(&mut tables);
+
+		tables
+			.updater(3)
+			.index_burns(&burn_tx(&[OutPoint { txid: Txid::all_zeros(), vout: 0 }], 2))
+			.unwrap();
+
+		assert!(tables.maps.token_burns.is_empty());
+		assert_eq!(tables.maps.token_locations.len(), 1);
+	}
+
+	#[test]
+	fn burn_token_sends_event() {
+		let mut tables = Tables::with_collection(false);
+
+		let outpoint = register_tokens(&mut tables);
+
+		let tx = burn_tx(&[outpoint], 2);
+
+		tables.events.clear();
+
+		tables.updater(3).index_burns(&tx).unwrap();
+
+		assert_eq!(
+			tables.events,
+			[Event::Brc721TokenBurned {
+				block_height: 3,
+				collection_id: Brc721CollectionId { block: 1, tx: 1 },
+				token_id: Brc721TokenId {
+					slot: 2,
+					initial_owner: H160::from_slice(&INITIAL_OWNER)
+				},
+				txid: tx.compute_txid(),
+			}]
+		);
+	}
 }
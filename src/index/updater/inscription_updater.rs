@@ -56,6 +56,7 @@ enum Origin {
 pub(super) struct InscriptionUpdater<'a, 'tx> {
 	pub(super) blessed_inscription_count: u64,
 	pub(super) cursed_inscription_count: u64,
+	pub(super) events: Option<&'a mut Vec<Event>>,
 	pub(super) flotsam: Vec<Flotsam>,
 	pub(super) height: u32,
 	pub(super) home_inscription_count: u64,
@@ -79,6 +80,7 @@ impl InscriptionUpdater<'_, '_> {
 		&mut self,
 		tx: &Transaction,
 		txid: Txid,
+		envelopes: Vec<ParsedEnvelope>,
 		input_utxo_entries: &[ParsedUtxoEntry],
 		output_utxo_entries: &mut [UtxoEntryBuf],
 		utxo_cache: &mut HashMap<OutPoint, UtxoEntryBuf>,
@@ -92,7 +94,6 @@ impl InscriptionUpdater<'_, '_> {
 		let mut total_input_value = 0;
 		let total_output_value = tx.output.iter().map(|txout| txout.value.to_sat()).sum::<u64>();
 
-		let envelopes = ParsedEnvelope::from_transaction(tx);
 		let has_new_inscriptions = !envelopes.is_empty();
 		let mut envelopes = envelopes.into_iter().peekable();
 
@@ -365,14 +366,14 @@ impl InscriptionUpdater<'_, '_> {
 						.insert(sequence_number, &InscriptionEntry { charms, ..entry }.store())?;
 				}
 
-				if let Some(ref sender) = index.event_sender {
-					sender.blocking_send(Event::InscriptionTransferred {
+				if let Some(events) = &mut self.events {
+					events.push(Event::InscriptionTransferred {
 						block_height: self.height,
 						inscription_id,
 						new_location: new_satpoint,
 						old_location: old_satpoint,
 						sequence_number,
-					})?;
+					});
 				}
 
 				(false, sequence_number)
@@ -447,15 +448,15 @@ impl InscriptionUpdater<'_, '_> {
 					})
 					.collect::<Result<Vec<u32>>>()?;
 
-				if let Some(ref sender) = index.event_sender {
-					sender.blocking_send(Event::InscriptionCreated {
+				if let Some(events) = &mut self.events {
+					events.push(Event::InscriptionCreated {
 						block_height: self.height,
 						charms,
 						inscription_id,
 						location: (!unbound).then_some(new_satpoint),
 						parent_inscription_ids: parents,
 						sequence_number,
-					})?;
+					});
 				}
 
 				self.sequence_number_to_entry.insert(
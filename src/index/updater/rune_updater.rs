@@ -20,7 +20,7 @@ pub(super) struct RuneUpdater<'a, 'tx, 'client> {
 	pub(super) block_time: u32,
 	pub(super) burned: HashMap<RuneId, Lot>,
 	pub(super) client: &'client Client,
-	pub(super) event_sender: Option<&'a mpsc::Sender<Event>>,
+	pub(super) events: Option<&'a mut Vec<Event>>,
 	pub(super) height: u32,
 	pub(super) id_to_entry: &'a mut Table<'tx, RuneIdValue, RuneEntryValue>,
 	pub(super) inscription_id_to_sequence_number: &'a Table<'tx, InscriptionIdValue, u32>,
@@ -39,9 +39,8 @@ impl RuneUpdater<'_, '_, '_> {
 		tx_index: u32,
 		tx: &Transaction,
 		txid: Txid,
+		artifact: Option<Artifact>,
 	) -> Result<()> {
-		let artifact = Runestone::decipher(tx);
-
 		let mut unallocated = self.unallocated(tx)?;
 
 		let mut allocated: Vec<HashMap<RuneId, Lot>> = vec![HashMap::new(); tx.output.len()];
@@ -51,13 +50,13 @@ impl RuneUpdater<'_, '_, '_> {
 				if let Some(amount) = self.mint(id)? {
 					*unallocated.entry(id).or_default() += amount;
 
-					if let Some(sender) = self.event_sender {
-						sender.blocking_send(Event::RuneMinted {
+					if let Some(events) = &mut self.events {
+						events.push(Event::RuneMinted {
 							block_height: self.height,
 							txid,
 							rune_id: id,
 							amount: amount.n(),
-						})?;
+						});
 					}
 				}
 			}
@@ -212,14 +211,14 @@ impl RuneUpdater<'_, '_, '_> {
 			for (id, balance) in balances {
 				Index::encode_rune_balance(id, balance.n(), &mut buffer);
 
-				if let Some(sender) = self.event_sender {
-					sender.blocking_send(Event::RuneTransferred {
+				if let Some(events) = &mut self.events {
+					events.push(Event::RuneTransferred {
 						outpoint,
 						block_height: self.height,
 						txid,
 						rune_id: id,
 						amount: balance.0,
-					})?;
+					});
 				}
 			}
 
@@ -230,13 +229,13 @@ impl RuneUpdater<'_, '_, '_> {
 		for (id, amount) in burned {
 			*self.burned.entry(id).or_default() += amount;
 
-			if let Some(sender) = self.event_sender {
-				sender.blocking_send(Event::RuneBurned {
+			if let Some(events) = &mut self.events {
+				events.push(Event::RuneBurned {
 					block_height: self.height,
 					txid,
 					rune_id: id,
 					amount: amount.n(),
-				})?;
+				});
 			}
 		}
 
@@ -307,12 +306,8 @@ impl RuneUpdater<'_, '_, '_> {
 
 		self.id_to_entry.insert(id.store(), entry.store())?;
 
-		if let Some(sender) = self.event_sender {
-			sender.blocking_send(Event::RuneEtched {
-				block_height: self.height,
-				txid,
-				rune_id: id,
-			})?;
+		if let Some(events) = &mut self.events {
+			events.push(Event::RuneEtched { block_height: self.height, txid, rune_id: id });
 		}
 
 		let inscription_id = InscriptionId { txid, index: 0 };
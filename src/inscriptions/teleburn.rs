@@ -31,6 +31,18 @@ impl From<InscriptionId> for Ethereum {
 	}
 }
 
+impl From<(Brc721CollectionId, Brc721TokenId)> for Ethereum {
+	fn from((collection_id, token_id): (Brc721CollectionId, Brc721TokenId)) -> Self {
+		let mut array = [0; 44];
+		let (collection, token) = array.split_at_mut(12);
+		collection[..8].copy_from_slice(&collection_id.block.to_be_bytes());
+		collection[8..].copy_from_slice(&collection_id.tx.to_be_bytes());
+		token.copy_from_slice(&token_id.to_u256().to_big_endian());
+		let digest = bitcoin::hashes::sha256::Hash::hash(&array);
+		Self(create_address_with_checksum(&hex::encode(&digest[0..20])))
+	}
+}
+
 impl Display for Ethereum {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		write!(f, "{}", self.0)
@@ -60,6 +72,7 @@ fn create_address_with_checksum(address: &str) -> String {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use sp_core::H160;
 
 	#[test]
 	fn test_eth_checksum_generation() {
@@ -104,4 +117,22 @@ mod tests {
 			assert_eq!(*addr, Ethereum::from(*inscription_id).0);
 		}
 	}
+
+	#[test]
+	fn brc721_token_to_teleburn_address() {
+		for (collection_id, token_id, addr) in [
+			(
+				Brc721CollectionId { block: 0, tx: 0 },
+				Brc721TokenId::default(),
+				"0x85759B3811Ff7dc47B03792Ac85317be51431A3F",
+			),
+			(
+				Brc721CollectionId { block: 874123, tx: 5 },
+				Brc721TokenId { slot: 42, initial_owner: H160::repeat_byte(0x2a) },
+				"0xa8B3F2aA7F090dB5e3899747F3831534728874f5",
+			),
+		] {
+			assert_eq!(addr, Ethereum::from((collection_id, token_id)).0);
+		}
+	}
 }
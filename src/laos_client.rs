@@ -0,0 +1,250 @@
+// Copyright 2023-2024 Freeverse.io
+// This file is part of LAOS.
+
+// LAOS is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// LAOS is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use serde::de::DeserializeOwned;
+use sha3::{Digest, Keccak256};
+use sp_core::{H160, H256, U256};
+
+/// Error code returned by Ethereum JSON-RPC nodes when a call reverts.
+const EXECUTION_REVERTED: i64 = 3;
+
+/// LAOS collections are precompiles created by the collection factory at addresses made of this
+/// prefix followed by the big-endian 64-bit collection id.
+const COLLECTION_ADDRESS_PREFIX: [u8; 12] =
+	[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe];
+
+const MINTED_WITH_EXTERNAL_URI: &str = "MintedWithExternalURI(address,uint96,uint256,string)";
+
+const EVOLVED_WITH_EXTERNAL_URI: &str = "EvolvedWithExternalURI(uint256,string)";
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RpcError {
+	pub(crate) code: i64,
+	pub(crate) message: String,
+}
+
+impl Display for RpcError {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "LAOS RPC error {}: {}", self.code, self.message)
+	}
+}
+
+impl std::error::Error for RpcError {}
+
+#[derive(Deserialize)]
+struct Response<T> {
+	result: Option<T>,
+	error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+struct Log {
+	topics: Vec<H256>,
+	data: String,
+}
+
+/// Client for the Ethereum JSON-RPC API of a LAOS node, used to look up the sibling collections
+/// that BRC721 collections point to.
+pub(crate) struct LaosClient {
+	client: reqwest::blocking::Client,
+	url: Url,
+}
+
+impl LaosClient {
+	pub(crate) fn new(url: &str) -> Result<Self> {
+		Ok(Self {
+			client: reqwest::blocking::Client::new(),
+			url: url.parse().with_context(|| format!("invalid LAOS RPC URL `{url}`"))?,
+		})
+	}
+
+	/// Returns the outer error if the node could not be reached, and the inner error if it
+	/// answered with a JSON-RPC error.
+	fn request<T: DeserializeOwned>(
+		&self,
+		method: &str,
+		params: serde_json::Value,
+	) -> Result<Result<T, RpcError>> {
+		let response = self
+			.client
+			.post(self.url.clone())
+			.json(&serde_json::json!({
+				"jsonrpc": "2.0",
+				"id": 1,
+				"method": method,
+				"params": params,
+			}))
+			.send()
+			.with_context(|| format!("failed to connect to LAOS RPC at {}", self.url))?
+			.error_for_status()?
+			.json::<Response<T>>()?;
+
+		match (response.result, response.error) {
+			(_, Some(err)) => Ok(Err(err)),
+			(Some(result), None) => Ok(Ok(result)),
+			(None, None) => bail!("LAOS RPC response to `{method}` has neither result nor error"),
+		}
+	}
+
+	fn decode_hex(hex: &str) -> Result<Vec<u8>> {
+		Ok(hex::decode(hex.strip_prefix("0x").unwrap_or(hex))?)
+	}
+
+	pub(crate) fn block_number(&self) -> Result<u64> {
+		let block_number = self.request::<U256>("eth_blockNumber", serde_json::json!([]))??;
+
+		u64::try_from(block_number).map_err(|err| anyhow!("invalid LAOS block number: {err}"))
+	}
+
+	pub(crate) fn get_code(&self, address: H160) -> Result<Vec<u8>> {
+		let code =
+			self.request::<String>("eth_getCode", serde_json::json!([address, "latest"]))??;
+
+		Self::decode_hex(&code)
+	}
+
+	/// Calls `to` with `data`, returning `None` if the call reverts.
+	pub(crate) fn call(&self, to: H160, data: &[u8]) -> Result<Option<Vec<u8>>> {
+		let result = self.request::<String>(
+			"eth_call",
+			serde_json::json!([{ "to": to, "data": format!("0x{}", hex::encode(data)) }, "latest"]),
+		)?;
+
+		match result {
+			Ok(output) => Ok(Some(Self::decode_hex(&output)?)),
+			Err(err) if err.code == EXECUTION_REVERTED || err.message.contains("revert") =>
+				Ok(None),
+			Err(err) => Err(err.into()),
+		}
+	}
+
+	/// Returns the owner of the collection at `address`, or `None` if it does not answer
+	/// `owner()` like a collection.
+	pub(crate) fn collection_owner(&self, address: H160) -> Result<Option<H160>> {
+		let Some(output) = self.call(address, &selector("owner()"))? else {
+			return Ok(None);
+		};
+
+		if output.len() != 32 || output[..12].iter().any(|byte| *byte != 0) {
+			return Ok(None);
+		}
+
+		Ok(Some(H160::from_slice(&output[12..])))
+	}
+
+	/// Checks that `address` has deployed bytecode, is a collection precompile address, and
+	/// implements the collection interface. Any `Ownable` contract answers `owner()`, so the
+	/// address prefix is what tells collections apart from other contracts.
+	pub(crate) fn check_collection(&self, address: H160) -> Result {
+		ensure!(
+			!self.get_code(address)?.is_empty(),
+			"LAOS address {address:#x} has no deployed bytecode"
+		);
+
+		ensure!(
+			address.as_bytes().starts_with(&COLLECTION_ADDRESS_PREFIX),
+			"LAOS address {address:#x} is not a collection address"
+		);
+
+		ensure!(
+			self.collection_owner(address)?.is_some(),
+			"LAOS address {address:#x} does not implement the collection interface"
+		);
+
+		Ok(())
+	}
+
+	/// Returns the latest token uri of every token minted in the collection at `address`,
+	/// according to its mint and evolve events from `from_block` on, requested `block_range`
+	/// blocks at a time.
+	pub(crate) fn token_uris(
+		&self,
+		address: H160,
+		from_block: u64,
+		block_range: u64,
+	) -> Result<BTreeMap<U256, String>> {
+		let minted = event_topic(MINTED_WITH_EXTERNAL_URI);
+		let evolved = event_topic(EVOLVED_WITH_EXTERNAL_URI);
+
+		let latest = self.block_number()?;
+
+		let mut logs = Vec::new();
+
+		// logs are requested in consecutive block ranges, so later evolutions overwrite earlier
+		// token uris below
+		for from_block in (from_block..=latest).step_by(block_range.try_into()?) {
+			let to_block = latest.min(from_block.saturating_add(block_range - 1));
+
+			logs.extend(self.request::<Vec<Log>>(
+				"eth_getLogs",
+				serde_json::json!([{
+					"address": address,
+					"fromBlock": format!("{from_block:#x}"),
+					"toBlock": format!("{to_block:#x}"),
+					"topics": [[minted, evolved]],
+				}]),
+			)??);
+		}
+
+		let mut token_uris = BTreeMap::new();
+
+		for log in logs {
+			let data = Self::decode_hex(&log.data)?;
+
+			// mint events carry the slot, token id and token uri, evolve events index the token id
+			let (token_id, token_uri) = match log.topics.first() {
+				Some(topic) if *topic == minted => (word(&data, 32), string(&data, 64)),
+				Some(topic) if *topic == evolved => (
+					log.topics.get(1).map(|topic| U256::from_big_endian(topic.as_bytes())),
+					string(&data, 0),
+				),
+				_ => continue,
+			};
+
+			let (Some(token_id), Some(token_uri)) = (token_id, token_uri) else {
+				bail!("malformed event of LAOS collection {address:#x}");
+			};
+
+			token_uris.insert(token_id, token_uri);
+		}
+
+		Ok(token_uris)
+	}
+}
+
+/// Returns the topic identifying an event with `signature`.
+fn event_topic(signature: &str) -> H256 {
+	H256::from_slice(&Keccak256::digest(signature.as_bytes()))
+}
+
+/// Decodes the ABI word at `offset`.
+fn word(data: &[u8], offset: usize) -> Option<U256> {
+	data.get(offset..offset.checked_add(32)?).map(U256::from_big_endian)
+}
+
+/// Decodes the ABI `string` whose offset is stored at `offset`.
+fn string(data: &[u8], offset: usize) -> Option<String> {
+	let offset = usize::try_from(word(data, offset)?).ok()?;
+	let len = usize::try_from(word(data, offset)?).ok()?;
+	let start = offset.checked_add(32)?;
+	String::from_utf8(data.get(start..start.checked_add(len)?)?.to_vec()).ok()
+}
+
+/// Returns the selector of a function with `signature`.
+fn selector(signature: &str) -> [u8; 4] {
+	Keccak256::digest(signature.as_bytes())[..4].try_into().unwrap()
+}
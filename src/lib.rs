@@ -30,6 +30,7 @@
 use self::{
 	arguments::Arguments,
 	blocktime::Blocktime,
+	brc721_token_ref::Brc721TokenRef,
 	decimal::Decimal,
 	deserialize_from_str::DeserializeFromStr,
 	index::BitcoinCoreRpcResultExt,
@@ -39,6 +40,7 @@ use self::{
 		teleburn, ParsedEnvelope,
 	},
 	into_usize::IntoUsize,
+	laos_client::LaosClient,
 	outgoing::Outgoing,
 	representation::Representation,
 	settings::Settings,
@@ -72,8 +74,8 @@ use html_escaper::{Escape, Trusted};
 use http::{HeaderMap, StatusCode};
 use lazy_static::lazy_static;
 use ordinals::{
-	varint, Artifact, Brc721Collection, Brc721CollectionId, Charm, Edict, Epoch, Etching, Height,
-	Pile, Rarity, Rune, RuneId, Runestone, Sat, SatPoint, SpacedRune, Terms,
+	varint, Artifact, Brc721Collection, Brc721CollectionId, Brc721TokenId, Charm, Edict, Epoch,
+	Etching, Height, Pile, Rarity, Rune, RuneId, Runestone, Sat, SatPoint, SpacedRune, Terms,
 	COLLECTION_ADDRESS_LENGTH,
 };
 use regex::Regex;
@@ -108,7 +110,7 @@ use tokio::{runtime::Runtime, task};
 pub use self::{
 	chain::Chain,
 	fee_rate::FeeRate,
-	index::{Index, RuneEntry},
+	index::{event::Event, Index, RuneEntry, SnapshotArchive},
 	inscriptions::{Envelope, Inscription, InscriptionId},
 	object::Object,
 	options::Options,
@@ -125,6 +127,7 @@ use self::test::*;
 pub mod api;
 pub mod arguments;
 mod blocktime;
+mod brc721_token_ref;
 pub mod chain;
 pub mod decimal;
 mod deserialize_from_str;
@@ -133,6 +136,7 @@ mod fee_rate;
 pub mod index;
 mod inscriptions;
 mod into_usize;
+mod laos_client;
 mod macros;
 mod object;
 pub mod options;
@@ -33,8 +33,18 @@ pub struct Options {
 	pub(crate) bitcoin_rpc_username: Option<String>,
 	#[arg(long, help = "Max <N> requests in flight. [default: 12]")]
 	pub(crate) bitcoin_rpc_limit: Option<u32>,
+	#[arg(
+		long,
+		help = "Download and decode blocks with <BLOCK_FETCH_THREADS> threads. [default: available parallelism, at most 8] [maximum: 64]"
+	)]
+	pub(crate) block_fetch_threads: Option<usize>,
 	#[arg(long = "chain", value_enum, help = "Use <CHAIN>. [default: mainnet]")]
 	pub(crate) chain_argument: Option<Chain>,
+	#[arg(
+		long,
+		help = "Only create savepoints within <CHAIN_TIP_DISTANCE> blocks of the chain tip. Must be at least <MAX_SAVEPOINTS> * <SAVEPOINT_INTERVAL>. [default: <MAX_SAVEPOINTS> * <SAVEPOINT_INTERVAL> + 1]"
+	)]
+	pub(crate) chain_tip_distance: Option<u32>,
 	#[arg(long, help = "Commit to index every <COMMIT_INTERVAL> blocks. [default: 5000]")]
 	pub(crate) commit_interval: Option<usize>,
 	#[arg(long, help = "Load configuration from <CONFIG>.")]
@@ -45,6 +55,11 @@ pub struct Options {
 	pub(crate) cookie_file: Option<PathBuf>,
 	#[arg(long, alias = "datadir", help = "Store index in <DATA_DIR>.")]
 	pub(crate) data_dir: Option<PathBuf>,
+	#[arg(
+		long,
+		help = "Index brc721 operations from <FIRST_BRC721_HEIGHT>. [default: brc721 activation height of chain]"
+	)]
+	pub(crate) first_brc721_height: Option<u32>,
 	#[arg(long, help = "Limit index to <HEIGHT_LIMIT> blocks.")]
 	pub(crate) height_limit: Option<u32>,
 	#[arg(long, help = "Use index at <INDEX>.")]
@@ -53,6 +68,11 @@ pub struct Options {
 	pub(crate) index_addresses: bool,
 	#[arg(long, help = "Track location of brc721.")]
 	pub(crate) index_brc721: bool,
+	#[arg(
+		long,
+		help = "Only index brc721 operations and the outputs carrying brc721 tokens. Implies --index-brc721 and --no-index-inscriptions."
+	)]
+	pub(crate) index_brc721_only: bool,
 	#[arg(
 		long,
 		help = "Set index cache size to <INDEX_CACHE_SIZE> bytes. [default: 1/4 available RAM]"
@@ -66,10 +86,18 @@ pub struct Options {
 	pub(crate) index_transactions: bool,
 	#[arg(long, help = "Run in integration test mode.")]
 	pub(crate) integration_test: bool,
+	#[arg(long, help = "Query LAOS collections at <LAOS_RPC_URL>.")]
+	pub(crate) laos_rpc_url: Option<String>,
+	#[arg(long, help = "Keep <MAX_SAVEPOINTS> savepoints to recover from reorgs. [default: 2]")]
+	pub(crate) max_savepoints: Option<u32>,
+	#[arg(long, help = "Keep <MAX_SNAPSHOTS> index snapshots. [default: 2]")]
+	pub(crate) max_snapshots: Option<u32>,
 	#[clap(long, short, long, help = "Specify output format. [default: json]")]
 	pub(crate) format: Option<OutputFormat>,
 	#[arg(long, short, alias = "noindex_inscriptions", help = "Do not index inscriptions.")]
 	pub(crate) no_index_inscriptions: bool,
+	#[arg(long, help = "Create a savepoint every <SAVEPOINT_INTERVAL> blocks. [default: 10]")]
+	pub(crate) savepoint_interval: Option<u32>,
 	#[arg(
 		long,
 		help = "Require basic HTTP authentication with <SERVER_PASSWORD>. Credentials are sent in cleartext. Consider using authentication in conjunction with HTTPS."
@@ -80,6 +108,11 @@ pub struct Options {
 		help = "Require basic HTTP authentication with <SERVER_USERNAME>. Credentials are sent in cleartext. Consider using authentication in conjunction with HTTPS."
 	)]
 	pub(crate) server_username: Option<String>,
+	#[arg(
+		long,
+		help = "Snapshot the index every <SNAPSHOT_INTERVAL> blocks, to recover from reorgs deeper than the savepoints."
+	)]
+	pub(crate) snapshot_interval: Option<u32>,
 	#[arg(long, short, help = "Use regtest. Equivalent to `--chain regtest`.")]
 	pub(crate) regtest: bool,
 	#[arg(long, short, help = "Use signet. Equivalent to `--chain signet`.")]
@@ -88,4 +121,8 @@ pub struct Options {
 	pub(crate) testnet: bool,
 	#[arg(long, help = "Use testnet4. Equivalent to `--chain testnet4`.")]
 	pub(crate) testnet4: bool,
+	#[arg(long, help = "Sign webhook requests with HMAC-SHA256 keyed by <WEBHOOK_SECRET>.")]
+	pub(crate) webhook_secret: Option<String>,
+	#[arg(long, help = "POST the events of each indexed block to <WEBHOOK_URL>.")]
+	pub(crate) webhook_url: Option<String>,
 }
@@ -25,30 +25,43 @@ pub struct Settings {
 	bitcoin_rpc_password: Option<String>,
 	bitcoin_rpc_url: Option<String>,
 	bitcoin_rpc_username: Option<String>,
+	block_fetch_threads: Option<usize>,
 	chain: Option<Chain>,
+	chain_tip_distance: Option<u32>,
 	commit_interval: Option<usize>,
 	config: Option<PathBuf>,
 	config_dir: Option<PathBuf>,
 	cookie_file: Option<PathBuf>,
 	data_dir: Option<PathBuf>,
+	first_brc721_height: Option<u32>,
 	height_limit: Option<u32>,
 	hidden: Option<HashSet<InscriptionId>>,
 	http_port: Option<u16>,
 	index: Option<PathBuf>,
 	index_addresses: bool,
 	index_brc721: bool,
+	index_brc721_only: bool,
 	index_cache_size: Option<usize>,
 	index_runes: bool,
 	index_sats: bool,
 	index_transactions: bool,
 	integration_test: bool,
+	laos_rpc_url: Option<String>,
+	max_savepoints: Option<u32>,
+	max_snapshots: Option<u32>,
 	no_index_inscriptions: bool,
+	savepoint_interval: Option<u32>,
 	server_password: Option<String>,
 	server_url: Option<String>,
 	server_username: Option<String>,
+	snapshot_interval: Option<u32>,
+	webhook_secret: Option<String>,
+	webhook_url: Option<String>,
 }
 
 impl Settings {
+	const MAX_BLOCK_FETCH_THREADS: usize = 64;
+
 	pub fn load(options: Options) -> Result<Settings> {
 		let mut env = BTreeMap::<String, String>::new();
 
@@ -116,6 +129,12 @@ impl Settings {
 			_ => {},
 		};
 
+		match (&settings.webhook_url, &settings.webhook_secret) {
+			(None, Some(_webhook_secret)) => bail!("no webhook url specified"),
+			(Some(_webhook_url), None) => bail!("no webhook secret specified"),
+			_ => {},
+		};
+
 		Ok(settings)
 	}
 
@@ -126,12 +145,15 @@ impl Settings {
 			bitcoin_rpc_password: self.bitcoin_rpc_password.or(source.bitcoin_rpc_password),
 			bitcoin_rpc_url: self.bitcoin_rpc_url.or(source.bitcoin_rpc_url),
 			bitcoin_rpc_username: self.bitcoin_rpc_username.or(source.bitcoin_rpc_username),
+			block_fetch_threads: self.block_fetch_threads.or(source.block_fetch_threads),
 			chain: self.chain.or(source.chain),
+			chain_tip_distance: self.chain_tip_distance.or(source.chain_tip_distance),
 			commit_interval: self.commit_interval.or(source.commit_interval),
 			config: self.config.or(source.config),
 			config_dir: self.config_dir.or(source.config_dir),
 			cookie_file: self.cookie_file.or(source.cookie_file),
 			data_dir: self.data_dir.or(source.data_dir),
+			first_brc721_height: self.first_brc721_height.or(source.first_brc721_height),
 			height_limit: self.height_limit.or(source.height_limit),
 			hidden: Some(
 				self.hidden
@@ -145,15 +167,23 @@ impl Settings {
 			index: self.index.or(source.index),
 			index_addresses: self.index_addresses || source.index_addresses,
 			index_brc721: self.index_brc721 || source.index_brc721,
+			index_brc721_only: self.index_brc721_only || source.index_brc721_only,
 			index_cache_size: self.index_cache_size.or(source.index_cache_size),
 			index_runes: self.index_runes || source.index_runes,
 			index_sats: self.index_sats || source.index_sats,
 			index_transactions: self.index_transactions || source.index_transactions,
 			integration_test: self.integration_test || source.integration_test,
+			laos_rpc_url: self.laos_rpc_url.or(source.laos_rpc_url),
+			max_savepoints: self.max_savepoints.or(source.max_savepoints),
+			max_snapshots: self.max_snapshots.or(source.max_snapshots),
 			no_index_inscriptions: self.no_index_inscriptions || source.no_index_inscriptions,
+			savepoint_interval: self.savepoint_interval.or(source.savepoint_interval),
 			server_password: self.server_password.or(source.server_password),
 			server_url: self.server_url.or(source.server_url),
 			server_username: self.server_username.or(source.server_username),
+			snapshot_interval: self.snapshot_interval.or(source.snapshot_interval),
+			webhook_secret: self.webhook_secret.or(source.webhook_secret),
+			webhook_url: self.webhook_url.or(source.webhook_url),
 		}
 	}
 
@@ -164,6 +194,7 @@ impl Settings {
 			bitcoin_rpc_password: options.bitcoin_rpc_password,
 			bitcoin_rpc_url: options.bitcoin_rpc_url,
 			bitcoin_rpc_username: options.bitcoin_rpc_username,
+			block_fetch_threads: options.block_fetch_threads,
 			chain: options
 				.signet
 				.then_some(Chain::Signet)
@@ -171,26 +202,36 @@ impl Settings {
 				.or(options.testnet.then_some(Chain::Testnet))
 				.or(options.testnet4.then_some(Chain::Testnet4))
 				.or(options.chain_argument),
+			chain_tip_distance: options.chain_tip_distance,
 			commit_interval: options.commit_interval,
 			config: options.config,
 			config_dir: options.config_dir,
 			cookie_file: options.cookie_file,
 			data_dir: options.data_dir,
+			first_brc721_height: options.first_brc721_height,
 			height_limit: options.height_limit,
 			hidden: None,
 			http_port: None,
 			index: options.index,
 			index_addresses: options.index_addresses,
 			index_brc721: options.index_brc721,
+			index_brc721_only: options.index_brc721_only,
 			index_cache_size: options.index_cache_size,
 			index_runes: options.index_runes,
 			index_sats: options.index_sats,
 			index_transactions: options.index_transactions,
 			integration_test: options.integration_test,
+			laos_rpc_url: options.laos_rpc_url,
+			max_savepoints: options.max_savepoints,
+			max_snapshots: options.max_snapshots,
 			no_index_inscriptions: options.no_index_inscriptions,
+			savepoint_interval: options.savepoint_interval,
 			server_password: options.server_password,
 			server_url: None,
 			server_username: options.server_username,
+			snapshot_interval: options.snapshot_interval,
+			webhook_secret: options.webhook_secret,
+			webhook_url: options.webhook_url,
 		}
 	}
 
@@ -249,27 +290,38 @@ impl Settings {
 			bitcoin_rpc_password: get_string("BITCOIN_RPC_PASSWORD"),
 			bitcoin_rpc_url: get_string("BITCOIN_RPC_URL"),
 			bitcoin_rpc_username: get_string("BITCOIN_RPC_USERNAME"),
+			block_fetch_threads: get_usize("BLOCK_FETCH_THREADS")?,
 			chain: get_chain("CHAIN")?,
+			chain_tip_distance: get_u32("CHAIN_TIP_DISTANCE")?,
 			commit_interval: get_usize("COMMIT_INTERVAL")?,
 			config: get_path("CONFIG"),
 			config_dir: get_path("CONFIG_DIR"),
 			cookie_file: get_path("COOKIE_FILE"),
 			data_dir: get_path("DATA_DIR"),
+			first_brc721_height: get_u32("FIRST_BRC721_HEIGHT")?,
 			height_limit: get_u32("HEIGHT_LIMIT")?,
 			hidden: inscriptions("HIDDEN")?,
 			http_port: get_u16("HTTP_PORT")?,
 			index: get_path("INDEX"),
 			index_addresses: get_bool("INDEX_ADDRESSES"),
 			index_brc721: get_bool("INDEX_BRC721"),
+			index_brc721_only: get_bool("INDEX_BRC721_ONLY"),
 			index_cache_size: get_usize("INDEX_CACHE_SIZE")?,
 			index_runes: get_bool("INDEX_RUNES"),
 			index_sats: get_bool("INDEX_SATS"),
 			index_transactions: get_bool("INDEX_TRANSACTIONS"),
 			integration_test: get_bool("INTEGRATION_TEST"),
+			laos_rpc_url: get_string("LAOS_RPC_URL"),
+			max_savepoints: get_u32("MAX_SAVEPOINTS")?,
+			max_snapshots: get_u32("MAX_SNAPSHOTS")?,
 			no_index_inscriptions: get_bool("NO_INDEX_INSCRIPTIONS"),
+			savepoint_interval: get_u32("SAVEPOINT_INTERVAL")?,
 			server_password: get_string("SERVER_PASSWORD"),
 			server_url: get_string("SERVER_URL"),
 			server_username: get_string("SERVER_USERNAME"),
+			snapshot_interval: get_u32("SNAPSHOT_INTERVAL")?,
+			webhook_secret: get_string("WEBHOOK_SECRET"),
+			webhook_url: get_string("WEBHOOK_URL"),
 		})
 	}
 
@@ -280,27 +332,38 @@ impl Settings {
 			bitcoin_rpc_url: Some(rpc_url.into()),
 			bitcoin_rpc_username: None,
 			bitcoin_rpc_limit: None,
+			block_fetch_threads: None,
 			chain: Some(Chain::Regtest),
+			chain_tip_distance: None,
 			commit_interval: None,
 			config: None,
 			config_dir: None,
 			cookie_file: None,
 			data_dir: Some(dir.into()),
+			first_brc721_height: None,
 			height_limit: None,
 			hidden: None,
 			http_port: None,
 			index: None,
 			index_addresses: true,
 			index_brc721: true,
+			index_brc721_only: false,
 			index_cache_size: None,
 			index_runes: true,
 			index_sats: true,
 			index_transactions: false,
 			integration_test: false,
+			laos_rpc_url: None,
+			max_savepoints: None,
+			max_snapshots: None,
 			no_index_inscriptions: false,
+			savepoint_interval: None,
 			server_password: None,
 			server_url: Some(server_url.into()),
 			server_username: None,
+			snapshot_interval: None,
+			webhook_secret: None,
+			webhook_url: None,
 		}
 	}
 
@@ -340,6 +403,49 @@ impl Settings {
 			None => data_dir.join("index.redb"),
 		};
 
+		let max_savepoints = self.max_savepoints.unwrap_or(2);
+
+		ensure!(max_savepoints > 0, "max savepoints must be at least 1");
+
+		let savepoint_interval = self.savepoint_interval.unwrap_or(10);
+
+		ensure!(savepoint_interval > 0, "savepoint interval must be at least 1");
+
+		// savepoints must be created early enough to span the deepest recoverable reorg
+		let savepoint_span = max_savepoints.saturating_mul(savepoint_interval);
+
+		let chain_tip_distance =
+			self.chain_tip_distance.unwrap_or(savepoint_span.saturating_add(1));
+
+		ensure!(
+			chain_tip_distance >= savepoint_span,
+			"chain tip distance must be at least max savepoints times savepoint interval ({savepoint_span})"
+		);
+
+		let max_snapshots = self.max_snapshots.unwrap_or(2);
+
+		ensure!(max_snapshots > 0, "max snapshots must be at least 1");
+
+		ensure!(self.snapshot_interval != Some(0), "snapshot interval must be at least 1");
+
+		let block_fetch_threads = match self.block_fetch_threads {
+			Some(block_fetch_threads) => block_fetch_threads,
+			None => thread::available_parallelism().map(usize::from).unwrap_or(1).min(8),
+		};
+
+		ensure!(block_fetch_threads > 0, "block fetch threads must be at least 1");
+
+		ensure!(
+			block_fetch_threads <= Self::MAX_BLOCK_FETCH_THREADS,
+			"block fetch threads must be at most {}",
+			Self::MAX_BLOCK_FETCH_THREADS,
+		);
+
+		ensure!(
+			!self.index_brc721_only || !(self.index_addresses || self.index_runes || self.index_sats),
+			"--index-brc721-only cannot be combined with --index-addresses, --index-runes or --index-sats"
+		);
+
 		Ok(Self {
 			bitcoin_data_dir: Some(bitcoin_data_dir),
 			bitcoin_rpc_limit: Some(self.bitcoin_rpc_limit.unwrap_or(12)),
@@ -350,18 +456,22 @@ impl Settings {
 					.unwrap_or_else(|| format!("127.0.0.1:{}", chain.default_rpc_port())),
 			),
 			bitcoin_rpc_username: self.bitcoin_rpc_username,
+			block_fetch_threads: Some(block_fetch_threads),
 			chain: Some(chain),
+			chain_tip_distance: Some(chain_tip_distance),
 			commit_interval: Some(self.commit_interval.unwrap_or(5000)),
 			config: None,
 			config_dir: None,
 			cookie_file: Some(cookie_file),
 			data_dir: Some(data_dir),
+			first_brc721_height: self.first_brc721_height,
 			height_limit: self.height_limit,
 			hidden: self.hidden,
 			http_port: self.http_port,
 			index: Some(index),
 			index_addresses: self.index_addresses,
-			index_brc721: self.index_brc721,
+			index_brc721: self.index_brc721 || self.index_brc721_only,
+			index_brc721_only: self.index_brc721_only,
 			index_cache_size: Some(match self.index_cache_size {
 				Some(index_cache_size) => index_cache_size,
 				None => {
@@ -374,10 +484,17 @@ impl Settings {
 			index_sats: self.index_sats,
 			index_transactions: self.index_transactions,
 			integration_test: self.integration_test,
-			no_index_inscriptions: self.no_index_inscriptions,
+			laos_rpc_url: self.laos_rpc_url,
+			max_savepoints: Some(max_savepoints),
+			max_snapshots: Some(max_snapshots),
+			no_index_inscriptions: self.no_index_inscriptions || self.index_brc721_only,
+			savepoint_interval: Some(savepoint_interval),
 			server_password: self.server_password,
 			server_url: self.server_url,
 			server_username: self.server_username,
+			snapshot_interval: self.snapshot_interval,
+			webhook_secret: self.webhook_secret,
+			webhook_url: self.webhook_url,
 		})
 	}
 
@@ -463,6 +580,14 @@ impl Settings {
 		self.chain.unwrap()
 	}
 
+	pub fn chain_tip_distance(&self) -> u32 {
+		self.chain_tip_distance.unwrap()
+	}
+
+	pub fn block_fetch_threads(&self) -> usize {
+		self.block_fetch_threads.unwrap()
+	}
+
 	pub fn commit_interval(&self) -> usize {
 		self.commit_interval.unwrap()
 	}
@@ -497,6 +622,10 @@ impl Settings {
 		self.data_dir.as_ref().unwrap().into()
 	}
 
+	pub fn first_brc721_height(&self) -> u32 {
+		self.first_brc721_height.unwrap_or_else(|| self.chain().first_brc721_height())
+	}
+
 	pub fn first_inscription_height(&self) -> u32 {
 		if self.integration_test {
 			0
@@ -529,6 +658,10 @@ impl Settings {
 		self.index_brc721
 	}
 
+	pub fn index_brc721_only(&self) -> bool {
+		self.index_brc721_only
+	}
+
 	pub fn index_inscriptions_raw(&self) -> bool {
 		!self.no_index_inscriptions
 	}
@@ -553,6 +686,26 @@ impl Settings {
 		self.integration_test
 	}
 
+	pub(crate) fn laos_client(&self) -> Result<Option<LaosClient>> {
+		self.laos_rpc_url.as_deref().map(LaosClient::new).transpose()
+	}
+
+	pub fn max_savepoints(&self) -> u32 {
+		self.max_savepoints.unwrap()
+	}
+
+	pub fn max_snapshots(&self) -> u32 {
+		self.max_snapshots.unwrap()
+	}
+
+	pub fn savepoint_interval(&self) -> u32 {
+		self.savepoint_interval.unwrap()
+	}
+
+	pub fn snapshot_interval(&self) -> Option<u32> {
+		self.snapshot_interval
+	}
+
 	pub fn is_hidden(&self, inscription_id: InscriptionId) -> bool {
 		self.hidden
 			.as_ref()
@@ -575,6 +728,10 @@ impl Settings {
 	pub fn server_url(&self) -> Option<&str> {
 		self.server_url.as_deref()
 	}
+
+	pub fn webhook(&self) -> Option<(&str, &str)> {
+		self.webhook_url.as_deref().zip(self.webhook_secret.as_deref())
+	}
 }
 
 #[cfg(test)]
@@ -856,6 +1013,78 @@ mod tests {
 		assert_eq!(arguments.options.commit_interval, Some(500));
 	}
 
+	#[test]
+	fn savepoint_settings() {
+		let settings = parse(&["--max-savepoints=4", "--savepoint-interval=5"]);
+		assert_eq!(settings.max_savepoints(), 4);
+		assert_eq!(settings.savepoint_interval(), 5);
+		assert_eq!(settings.chain_tip_distance(), 21);
+
+		assert_eq!(
+			Settings::from_options(Options::try_parse_from(["ord", "--max-savepoints=0"]).unwrap())
+				.or_defaults()
+				.unwrap_err()
+				.to_string(),
+			"max savepoints must be at least 1",
+		);
+
+		assert_eq!(
+			Settings::from_options(
+				Options::try_parse_from(["ord", "--savepoint-interval=0"]).unwrap()
+			)
+			.or_defaults()
+			.unwrap_err()
+			.to_string(),
+			"savepoint interval must be at least 1",
+		);
+
+		assert_eq!(parse(&["--max-savepoints=3"]).chain_tip_distance(), 31);
+		assert_eq!(
+			parse(&["--max-savepoints=4", "--savepoint-interval=5", "--chain-tip-distance=20"])
+				.chain_tip_distance(),
+			20
+		);
+
+		assert_eq!(
+			Settings::from_options(
+				Options::try_parse_from(["ord", "--max-savepoints=4", "--chain-tip-distance=19"])
+					.unwrap()
+			)
+			.or_defaults()
+			.unwrap_err()
+			.to_string(),
+			"chain tip distance must be at least max savepoints times savepoint interval (40)",
+		);
+	}
+
+	#[test]
+	fn block_fetch_threads() {
+		assert_eq!(parse(&["--block-fetch-threads=3"]).block_fetch_threads(), 3);
+		assert!(parse(&[]).block_fetch_threads() > 0);
+
+		assert_eq!(
+			Settings::from_options(
+				Options::try_parse_from(["ord", "--block-fetch-threads=0"]).unwrap()
+			)
+			.or_defaults()
+			.unwrap_err()
+			.to_string(),
+			"block fetch threads must be at least 1",
+		);
+
+		assert_eq!(
+			Settings::from_options(
+				Options::try_parse_from(["ord", "--block-fetch-threads=65"]).unwrap()
+			)
+			.or_defaults()
+			.unwrap_err()
+			.to_string(),
+			"block fetch threads must be at most 64",
+		);
+
+		assert_eq!(parse(&["--block-fetch-threads=64"]).block_fetch_threads(), 64);
+	}
+
 	#[test]
 	fn index_runes() {
 		assert!(parse(&["--chain=signet", "--index-runes"]).index_runes_raw());
@@ -870,6 +1099,37 @@ mod tests {
 		assert!(!parse(&[]).index_brc721_raw());
 	}
 
+	#[test]
+	fn first_brc721_height() {
+		for chain in
+			[Chain::Mainnet, Chain::Regtest, Chain::Signet, Chain::Testnet, Chain::Testnet4]
+		{
+			assert_eq!(
+				parse(&[&format!("--chain={chain}")]).first_brc721_height(),
+				chain.first_brc721_height(),
+			);
+		}
+
+		assert_eq!(parse(&["--first-brc721-height=10"]).first_brc721_height(), 10);
+	}
+
+	#[test]
+	fn index_brc721_only() {
+		let settings = parse(&["--index-brc721-only"]);
+		assert!(settings.index_brc721_raw());
+		assert!(!settings.index_inscriptions_raw());
+
+		assert_eq!(
+			Settings::from_options(
+				Options::try_parse_from(["ord", "--index-brc721-only", "--index-sats"]).unwrap()
+			)
+			.or_defaults()
+			.unwrap_err()
+			.to_string(),
+			"--index-brc721-only cannot be combined with --index-addresses, --index-runes or --index-sats",
+		);
+	}
+
 	#[test]
 	fn bitcoin_rpc_and_pass_setting() {
 		let config = Settings {
@@ -958,27 +1218,38 @@ mod tests {
       ("BITCOIN_RPC_PASSWORD", "bitcoin password"),
       ("BITCOIN_RPC_URL", "url"),
       ("BITCOIN_RPC_USERNAME", "bitcoin username"),
+      ("BLOCK_FETCH_THREADS", "3"),
       ("CHAIN", "signet"),
+      ("CHAIN_TIP_DISTANCE", "30"),
       ("COMMIT_INTERVAL", "1"),
       ("CONFIG", "config"),
       ("CONFIG_DIR", "config dir"),
       ("COOKIE_FILE", "cookie file"),
       ("DATA_DIR", "/data/dir"),
+      ("FIRST_BRC721_HEIGHT", "5"),
       ("HEIGHT_LIMIT", "3"),
       ("HIDDEN", "6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0 703e5f7c49d82aab99e605af306b9a30e991e57d42f982908a962a81ac439832i0"),
     ("HTTP_PORT", "8080"),
       ("INDEX", "index"),
       ("INDEX_CACHE_SIZE", "4"),
       ("INDEX_ADDRESSES", "1"),
-	  ("INDEX_BRC721", "1"),
+      ("INDEX_BRC721", "1"),
+      ("INDEX_BRC721_ONLY", "1"),
       ("INDEX_RUNES", "1"),
       ("INDEX_SATS", "1"),
       ("INDEX_TRANSACTIONS", "1"),
       ("INTEGRATION_TEST", "1"),
+      ("LAOS_RPC_URL", "laos rpc url"),
+      ("MAX_SAVEPOINTS", "4"),
+      ("MAX_SNAPSHOTS", "3"),
       ("NO_INDEX_INSCRIPTIONS", "1"),
+      ("SAVEPOINT_INTERVAL", "5"),
       ("SERVER_PASSWORD", "server password"),
       ("SERVER_URL", "server url"),
       ("SERVER_USERNAME", "server username"),
+      ("SNAPSHOT_INTERVAL", "100"),
+      ("WEBHOOK_SECRET", "webhook secret"),
+      ("WEBHOOK_URL", "webhook url"),
     ]
     .into_iter()
     .map(|(key, value)| (key.into(), value.into()))
@@ -992,12 +1263,15 @@ mod tests {
 				bitcoin_rpc_password: Some("bitcoin password".into()),
 				bitcoin_rpc_url: Some("url".into()),
 				bitcoin_rpc_username: Some("bitcoin username".into()),
+				block_fetch_threads: Some(3),
 				chain: Some(Chain::Signet),
+				chain_tip_distance: Some(30),
 				commit_interval: Some(1),
 				config: Some("config".into()),
 				config_dir: Some("config dir".into()),
 				cookie_file: Some("cookie file".into()),
 				data_dir: Some("/data/dir".into()),
+				first_brc721_height: Some(5),
 				height_limit: Some(3),
 				hidden: Some(
 					vec![
@@ -1015,15 +1289,23 @@ mod tests {
 				index: Some("index".into()),
 				index_addresses: true,
 				index_brc721: true,
+				index_brc721_only: true,
 				index_cache_size: Some(4),
 				index_runes: true,
 				index_sats: true,
 				index_transactions: true,
 				integration_test: true,
+				laos_rpc_url: Some("laos rpc url".into()),
+				max_savepoints: Some(4),
+				max_snapshots: Some(3),
 				no_index_inscriptions: true,
+				savepoint_interval: Some(5),
 				server_password: Some("server password".into()),
 				server_url: Some("server url".into()),
 				server_username: Some("server username".into()),
+				snapshot_interval: Some(100),
+				webhook_secret: Some("webhook secret".into()),
+				webhook_url: Some("webhook url".into()),
 			}
 		);
 	}
@@ -1039,24 +1321,35 @@ mod tests {
 					"--bitcoin-rpc-password=bitcoin password",
 					"--bitcoin-rpc-url=url",
 					"--bitcoin-rpc-username=bitcoin username",
+					"--block-fetch-threads=3",
 					"--chain=signet",
+					"--chain-tip-distance=30",
 					"--commit-interval=1",
 					"--config=config",
 					"--config-dir=config dir",
 					"--cookie-file=cookie file",
 					"--datadir=/data/dir",
+					"--first-brc721-height=5",
 					"--height-limit=3",
 					"--index-addresses",
 					"--index-brc721",
+					"--index-brc721-only",
 					"--index-cache-size=4",
 					"--index-runes",
 					"--index-sats",
 					"--index-transactions",
 					"--index=index",
 					"--integration-test",
+					"--laos-rpc-url=laos rpc url",
+					"--max-savepoints=4",
+					"--max-snapshots=3",
 					"--no-index-inscriptions",
+					"--savepoint-interval=5",
 					"--server-password=server password",
 					"--server-username=server username",
+					"--snapshot-interval=100",
+					"--webhook-secret=webhook secret",
+					"--webhook-url=webhook url",
 				])
 				.unwrap()
 			),
@@ -1066,27 +1359,38 @@ mod tests {
 				bitcoin_rpc_password: Some("bitcoin password".into()),
 				bitcoin_rpc_url: Some("url".into()),
 				bitcoin_rpc_username: Some("bitcoin username".into()),
+				block_fetch_threads: Some(3),
 				chain: Some(Chain::Signet),
+				chain_tip_distance: Some(30),
 				commit_interval: Some(1),
 				config: Some("config".into()),
 				config_dir: Some("config dir".into()),
 				cookie_file: Some("cookie file".into()),
 				data_dir: Some("/data/dir".into()),
+				first_brc721_height: Some(5),
 				height_limit: Some(3),
 				hidden: None,
 				http_port: None,
 				index: Some("index".into()),
 				index_addresses: true,
 				index_brc721: true,
+				index_brc721_only: true,
 				index_cache_size: Some(4),
 				index_runes: true,
 				index_sats: true,
 				index_transactions: true,
 				integration_test: true,
+				laos_rpc_url: Some("laos rpc url".into()),
+				max_savepoints: Some(4),
+				max_snapshots: Some(3),
 				no_index_inscriptions: true,
+				savepoint_interval: Some(5),
 				server_password: Some("server password".into()),
 				server_url: None,
 				server_username: Some("server username".into()),
+				snapshot_interval: Some(100),
+				webhook_secret: Some("webhook secret".into()),
+				webhook_url: Some("webhook url".into()),
 			}
 		);
 	}
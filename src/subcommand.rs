@@ -17,6 +17,7 @@
 use super::*;
 
 pub mod balances;
+pub mod brc721;
 pub mod decode;
 pub mod env;
 pub mod epochs;
@@ -39,6 +40,8 @@ pub mod wallets;
 pub(crate) enum Subcommand {
 	#[command(about = "List all rune balances")]
 	Balances,
+	#[command(subcommand, about = "BRC721 commands")]
+	Brc721(brc721::Brc721Subcommand),
 	#[command(about = "Decode a transaction")]
 	Decode(decode::Decode),
 	#[command(about = "Start a regtest ord and bitcoind instance")]
@@ -79,6 +82,7 @@ impl Subcommand {
 	pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
 		match self {
 			Self::Balances => balances::run(settings),
+			Self::Brc721(brc721) => brc721.run(settings),
 			Self::Decode(decode) => decode.run(settings),
 			Self::Env(env) => env.run(),
 			Self::Epochs => epochs::run(),
@@ -88,17 +92,18 @@ impl Subcommand {
 			Self::Parse(parse) => parse.run(),
 			Self::Runes => runes::run(settings),
 			Self::Server(server) => {
-				let index = Arc::new(Index::open(&settings)?);
+				let (event_sender, event_receiver) = tokio::sync::mpsc::channel(1024);
+				let index = Arc::new(Index::open_with_event_sender(&settings, Some(event_sender))?);
 				let handle = axum_server::Handle::new();
 				LISTENERS.lock().unwrap().push(handle.clone());
-				server.run(settings, index, handle)
+				server.run(settings, index, event_receiver, handle)
 			},
 			Self::Settings => settings::run(settings),
 			Self::Subsidy(subsidy) => subsidy.run(),
 			Self::Supply => supply::run(),
 			Self::Teleburn(teleburn) => teleburn.run(),
 			Self::Traits(traits) => traits.run(),
-			Self::Verify(verify) => verify.run(),
+			Self::Verify(verify) => verify.run(settings),
 			Self::Wallet(wallet) => wallet.run(settings),
 			Self::Wallets => wallets::run(settings),
 		}
@@ -0,0 +1,144 @@
+// Copyright 2023-2024 Freeverse.io
+// This file is part of LAOS.
+
+// LAOS is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// LAOS is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use sp_core::H160;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Audit {
+	#[arg(help = "Audit the tokens of <COLLECTION>.")]
+	collection: Brc721CollectionId,
+	#[arg(
+		long,
+		default_value_t = 0,
+		help = "Read LAOS collection events starting at block <FROM_BLOCK>, such as the block the collection was created in."
+	)]
+	from_block: u64,
+	#[arg(
+		long,
+		default_value_t = 1024,
+		value_parser = clap::value_parser!(u64).range(1..),
+		help = "Request LAOS collection events <LOG_BLOCK_RANGE> blocks at a time."
+	)]
+	log_block_range: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+	pub collection_id: Brc721CollectionId,
+	#[serde(rename = "LAOS_address")]
+	pub laos_address: H160,
+	pub without_laos_metadata: Vec<api::Brc721TokenRange>,
+	pub without_bitcoin_owner: Vec<Brc721TokenId>,
+}
+
+impl Audit {
+	pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+		let Some(laos_client) = settings.laos_client()? else {
+			bail!("audit requires a LAOS RPC endpoint set with `--laos-rpc-url`");
+		};
+
+		let index = Index::open(&settings)?;
+
+		if !index.has_brc721_index() {
+			bail!("audit requires index created with `--index-brc721` flag");
+		}
+
+		index.update()?;
+
+		let Some(collection) = index.get_brc721_collection_by_id(self.collection)? else {
+			bail!("collection {} not found", self.collection);
+		};
+
+		let laos_address = collection.laos_address;
+
+		// slots with metadata on LAOS, by initial owner
+		let mut laos_slots = BTreeMap::<H160, BTreeSet<u128>>::new();
+
+		for (token_id, token_uri) in
+			laos_client.token_uris(laos_address, self.from_block, self.log_block_range)?
+		{
+			if !token_uri.is_empty() {
+				let token_id = Brc721TokenId::from_u256(token_id);
+				laos_slots.entry(token_id.initial_owner).or_default().insert(token_id.slot);
+			}
+		}
+
+		let bitcoin_ranges = index.get_brc721_collection_tokens(self.collection)?;
+
+		let mut without_laos_metadata = Vec::new();
+
+		let no_slots = BTreeSet::new();
+
+		for (initial_owner, slots, output) in &bitcoin_ranges {
+			let mut start = Some(*slots.start());
+
+			for slot in laos_slots.get(initial_owner).unwrap_or(&no_slots).range(slots.clone()) {
+				if let Some(start) = start.filter(|start| start < slot) {
+					without_laos_metadata.push(api::Brc721TokenRange {
+						collection_id: self.collection,
+						initial_owner: *initial_owner,
+						output: *output,
+						slots: start..=slot - 1,
+					});
+				}
+
+				start = slot.checked_add(1);
+			}
+
+			if let Some(start) = start.filter(|start| start <= slots.end()) {
+				without_laos_metadata.push(api::Brc721TokenRange {
+					collection_id: self.collection,
+					initial_owner: *initial_owner,
+					output: *output,
+					slots: start..=*slots.end(),
+				});
+			}
+		}
+
+		// registered slot ranges, by initial owner and first slot, which never overlap
+		let mut registered = BTreeMap::<H160, BTreeMap<u128, u128>>::new();
+
+		for (initial_owner, slots, _) in &bitcoin_ranges {
+			registered
+				.entry(*initial_owner)
+				.or_default()
+				.insert(*slots.start(), *slots.end());
+		}
+
+		let without_bitcoin_owner = laos_slots
+			.into_iter()
+			.flat_map(|(initial_owner, slots)| {
+				slots.into_iter().map(move |slot| Brc721TokenId { slot, initial_owner })
+			})
+			.filter(|token_id| {
+				!registered.get(&token_id.initial_owner).is_some_and(|ranges| {
+					ranges
+						.range(..=token_id.slot)
+						.next_back()
+						.is_some_and(|(_, end)| token_id.slot <= *end)
+				})
+			})
+			.collect();
+
+		Ok(Some(Box::new(Output {
+			collection_id: self.collection,
+			laos_address,
+			without_laos_metadata,
+			without_bitcoin_owner,
+		})))
+	}
+}
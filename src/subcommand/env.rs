@@ -16,9 +16,16 @@
 
 use super::*;
 use crate::wallet::batch;
+use bitcoincore_rpc::Auth;
 use colored::Colorize;
+use ordinals::brc721::address_mapping::btc_address_to_h160;
+use serde::de::DeserializeOwned;
 use std::net::TcpListener;
 
+const BRC721_SAMPLE_LAOS_ADDRESS: &str = "fffffffffffffffffffffffe0000000000000001";
+
+const BRC721_SAMPLE_SLOTS: std::ops::RangeInclusive<u128> = 0..=4;
+
 struct KillOnDrop(process::Child);
 
 impl Drop for KillOnDrop {
@@ -45,16 +52,31 @@ pub(crate) struct Env {
 		help = "Proxy `/content/INSCRIPTION_ID` and other recursive endpoints to `<PROXY>` if the inscription is not present on current chain."
 	)]
 	pub(crate) proxy: Option<Url>,
+	#[arg(
+		long,
+		help = "Index BRC-721 operations, and register a sample BRC-721 collection and the ownership of some of its slots."
+	)]
+	pub(crate) brc721: bool,
 }
 
 #[derive(Serialize)]
 struct Info {
 	bitcoin_cli_command: Vec<String>,
 	bitcoind_port: u16,
+	brc721: Option<Brc721Sample>,
 	ord_port: u16,
 	ord_wallet_command: Vec<String>,
 }
 
+#[derive(Clone, Deserialize, Serialize)]
+struct Brc721Sample {
+	collection_id: Brc721CollectionId,
+	initial_owner: Address<NetworkUnchecked>,
+	register_collection_txid: Txid,
+	register_ownership_txid: Txid,
+	token_ids: Vec<Brc721TokenId>,
+}
+
 impl Env {
 	pub(crate) fn run(self) -> SubcommandResult {
 		let bitcoind_port = TcpListener::bind("127.0.0.1:9000")
@@ -155,9 +177,14 @@ rpcport={bitcoind_port}
 		let proxy = self.proxy.map(|url| url.to_string());
 
 		let mut command = Command::new(&ord);
+
+		command.arg("--datadir").arg(&absolute);
+
+		if self.brc721 {
+			command.arg("--index-brc721");
+		}
+
 		let ord_server = command
-			.arg("--datadir")
-			.arg(&absolute)
 			.arg("server")
 			.arg("--polling-interval=100ms")
 			.arg("--http-port")
@@ -214,10 +241,34 @@ rpcport={bitcoind_port}
 			ensure!(status.success(), "failed to create wallet: {status}");
 		}
 
+		let brc721 = if self.brc721 {
+			let sample = absolute.join("brc721.json");
+
+			if !sample.try_exists()? {
+				let client =
+					Client::new(&rpc_url, Auth::CookieFile(absolute.join("regtest/.cookie")))?;
+
+				fs::write(
+					&sample,
+					serde_json::to_string_pretty(&Self::register_brc721_sample(
+						&ord,
+						&absolute,
+						&client,
+						&server_url,
+					)?)?,
+				)?;
+			}
+
+			Some(serde_json::from_slice::<Brc721Sample>(&fs::read(&sample)?)?)
+		} else {
+			None
+		};
+
 		serde_json::to_writer_pretty(
 			File::create(self.directory.join("env.json"))?,
 			&Info {
 				bitcoind_port,
+				brc721: brc721.clone(),
 				ord_port,
 				bitcoin_cli_command: vec!["bitcoin-cli".into(), format!("-datadir={relative}")],
 				ord_wallet_command: vec![
@@ -248,6 +299,29 @@ bitcoin-cli -datadir={datadir} getblockchaininfo
 			ord.display(),
 		);
 
+		if let Some(brc721) = brc721 {
+			eprintln!(
+				"{}
+{}
+{}
+{}
+{}
+{server_url}/brc721/token/{}/{}",
+				"BRC-721 sample collection:".blue().bold(),
+				brc721.collection_id,
+				"BRC-721 sample tokens:".blue().bold(),
+				brc721
+					.token_ids
+					.iter()
+					.map(|token_id| token_id.to_string())
+					.collect::<Vec<String>>()
+					.join("\n"),
+				"Example BRC-721 URL:".blue().bold(),
+				brc721.collection_id,
+				brc721.token_ids[0],
+			);
+		}
+
 		loop {
 			if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
 				break Ok(None);
@@ -256,4 +330,135 @@ bitcoin-cli -datadir={datadir} getblockchaininfo
 			thread::sleep(Duration::from_millis(100));
 		}
 	}
+
+	/// Registers a sample collection, and the ownership of `BRC721_SAMPLE_SLOTS` of it by a
+	/// freshly funded initial owner, mining a block to confirm each registration.
+	fn register_brc721_sample(
+		ord: &Path,
+		datadir: &Path,
+		client: &Client,
+		server_url: &str,
+	) -> Result<Brc721Sample> {
+		let initial_owner = Self::wallet::<wallet::receive::Output>(ord, datadir, &["receive"])?
+			.addresses
+			.first()
+			.cloned()
+			.unwrap()
+			.require_network(Network::Regtest)?;
+
+		// fund the initial owner with a mature coinbase output
+		client.generate_to_address(101, &initial_owner)?;
+
+		Self::wait_for_server(client, server_url)?;
+
+		let register_collection_txid = Self::wallet::<wallet::brc721::register_collection::Output>(
+			ord,
+			datadir,
+			&[
+				"brc721",
+				"register-collection",
+				"--fee-rate",
+				"1",
+				"--rebaseable",
+				"--address",
+				BRC721_SAMPLE_LAOS_ADDRESS,
+			],
+		)?
+		.tx_id;
+
+		let block_hash = client.generate_to_address(1, &initial_owner)?[0];
+
+		let tx = client
+			.get_block(&block_hash)?
+			.txdata
+			.iter()
+			.position(|tx| tx.compute_txid() == register_collection_txid)
+			.context("register collection transaction was not mined")?;
+
+		let collection_id = Brc721CollectionId {
+			block: client.get_block_info(&block_hash)?.height.try_into().unwrap(),
+			tx: tx.try_into().unwrap(),
+		};
+
+		let file = datadir.join("brc721-ownership.yaml");
+
+		fs::write(
+			&file,
+			format!(
+				"collection_id: {collection_id}
+initial_owner: {initial_owner}
+outputs:
+  - slots_bundle: [[{}, {}]]
+",
+				BRC721_SAMPLE_SLOTS.start(),
+				BRC721_SAMPLE_SLOTS.end(),
+			),
+		)?;
+
+		Self::wait_for_server(client, server_url)?;
+
+		let register_ownership_txid = Self::wallet::<wallet::brc721::register_ownership::Output>(
+			ord,
+			datadir,
+			&[
+				"brc721",
+				"register-ownership",
+				"--fee-rate",
+				"1",
+				"--file",
+				file.to_str().context("ownership file path is not valid unicode")?,
+			],
+		)?
+		.tx_id;
+
+		client.generate_to_address(1, &initial_owner)?;
+
+		let initial_owner_h160 = btc_address_to_h160(initial_owner.clone())?;
+
+		Ok(Brc721Sample {
+			collection_id,
+			initial_owner: initial_owner.into_unchecked(),
+			register_collection_txid,
+			register_ownership_txid,
+			token_ids: BRC721_SAMPLE_SLOTS
+				.map(|slot| Brc721TokenId { slot, initial_owner: initial_owner_h160 })
+				.collect(),
+		})
+	}
+
+	fn wallet<T: DeserializeOwned>(ord: &Path, datadir: &Path, args: &[&str]) -> Result<T> {
+		let output = Command::new(ord)
+			.arg("--datadir")
+			.arg(datadir)
+			.arg("wallet")
+			.args(args)
+			.output()?;
+
+		ensure!(
+			output.status.success(),
+			"`wallet {}` failed: {}",
+			args.join(" "),
+			String::from_utf8_lossy(&output.stderr).trim_end(),
+		);
+
+		Ok(serde_json::from_slice(&output.stdout)?)
+	}
+
+	fn wait_for_server(client: &Client, server_url: &str) -> Result {
+		let block_count = client.get_block_count()? + 1;
+
+		for _ in 0..600 {
+			let server_block_count = reqwest::blocking::get(format!("{server_url}/blockcount"))?
+				.text()?
+				.parse::<u64>()?;
+
+			if server_block_count >= block_count {
+				return Ok(());
+			}
+
+			thread::sleep(Duration::from_millis(100));
+		}
+
+		bail!("`ord server` failed to index {block_count} blocks");
+	}
 }
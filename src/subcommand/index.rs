@@ -18,6 +18,8 @@ use super::*;
 
 mod export;
 pub mod info;
+mod restore;
+mod snapshot;
 mod update;
 
 #[derive(Debug, Parser)]
@@ -26,6 +28,10 @@ pub(crate) enum IndexSubcommand {
 	Export(export::Export),
 	#[command(about = "Print index statistics")]
 	Info(info::Info),
+	#[command(about = "Restore the index from a snapshot")]
+	Restore(restore::Restore),
+	#[command(about = "Write a compressed snapshot of the index")]
+	Snapshot(snapshot::Snapshot),
 	#[command(about = "Update the index", alias = "run")]
 	Update,
 }
@@ -35,6 +41,8 @@ impl IndexSubcommand {
 		match self {
 			Self::Export(export) => export.run(settings),
 			Self::Info(info) => info.run(settings),
+			Self::Restore(restore) => restore.run(settings),
+			Self::Snapshot(snapshot) => snapshot.run(settings),
 			Self::Update => update::run(settings),
 		}
 	}
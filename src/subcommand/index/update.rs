@@ -21,5 +21,7 @@ pub(crate) fn run(settings: Settings) -> SubcommandResult {
 
 	index.update()?;
 
+	index.wait_for_snapshot()?;
+
 	Ok(None)
 }
@@ -18,26 +18,32 @@ use self::{
 	accept_encoding::AcceptEncoding,
 	accept_json::AcceptJson,
 	error::{OptionExt, ServerError, ServerResult},
+	event_feed::EventFeed,
+	webhook::Webhook,
 };
 use super::*;
 use crate::templates::{
-	AddressHtml, BlockHtml, BlocksHtml, Brc721CollectionsHtml, ChildrenHtml, ClockSvg,
-	CollectionsHtml, HomeHtml, InputHtml, InscriptionHtml, InscriptionsBlockHtml, InscriptionsHtml,
-	OutputHtml, PageContent, PageHtml, ParentsHtml, PreviewAudioHtml, PreviewCodeHtml,
-	PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml,
-	PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml, RareTxt, RuneHtml, RuneNotFoundHtml,
-	RunesHtml, SatHtml, TransactionHtml,
+	AddressHtml, BlockHtml, BlocksHtml, Brc721CollectionsHtml, Brc721TokenHtml, ChildrenHtml,
+	ClockSvg, CollectionsHtml, HomeHtml, InputHtml, InscriptionHtml, InscriptionsBlockHtml,
+	InscriptionsHtml, OutputHtml, PageContent, PageHtml, ParentsHtml, PreviewAudioHtml,
+	PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml, PreviewModelHtml,
+	PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml, RareTxt, RuneHtml,
+	RuneNotFoundHtml, RunesHtml, SatHtml, TransactionHtml,
 };
 use axum::{
 	body,
 	extract::{DefaultBodyLimit, Extension, Json, Path, Query},
-	http::{header, HeaderValue, StatusCode, Uri},
-	response::{IntoResponse, Redirect, Response},
+	http::{header, HeaderMap, HeaderValue, StatusCode, Uri},
+	response::{
+		sse::{self, KeepAlive, Sse},
+		IntoResponse, Redirect, Response,
+	},
 	routing::{get, post},
 	Router,
 };
 use axum_server::Handle;
 use brotli::Decompressor;
+use ordinals::brc721::address_mapping::btc_address_to_h160;
 use rust_embed::RustEmbed;
 use rustls_acme::{
 	acme::{LETS_ENCRYPT_PRODUCTION_DIRECTORY, LETS_ENCRYPT_STAGING_DIRECTORY},
@@ -48,7 +54,10 @@ use rustls_acme::{
 use std::{str, sync::Arc};
 use tokio_stream::StreamExt;
 use tower_http::{
-	compression::CompressionLayer,
+	compression::{
+		predicate::{DefaultPredicate, NotForContentType, Predicate},
+		CompressionLayer,
+	},
 	cors::{Any, CorsLayer},
 	set_header::SetResponseHeaderLayer,
 	validate_request::ValidateRequestHeaderLayer,
@@ -59,8 +68,10 @@ pub use server_config::ServerConfig;
 mod accept_encoding;
 mod accept_json;
 mod error;
+mod event_feed;
 pub mod query;
 mod server_config;
+mod webhook;
 
 enum SpawnConfig {
 	Https(AxumAcceptor),
@@ -89,6 +100,11 @@ struct Search {
 	query: String,
 }
 
+#[derive(Deserialize)]
+struct EventsQuery {
+	from: Option<u32>,
+}
+
 #[derive(RustEmbed)]
 #[folder = "static"]
 struct StaticAssets;
@@ -114,6 +130,12 @@ pub struct Server {
 	pub(crate) decompress: bool,
 	#[arg(long, env = "ORD_SERVER_DISABLE_JSON_API", help = "Disable JSON API.")]
 	pub(crate) disable_json_api: bool,
+	#[arg(
+		long,
+		default_value = "100",
+		help = "Keep events of the last <EVENT_HISTORY> blocks for `/events` clients resuming from a height."
+	)]
+	pub(crate) event_history: u32,
 	#[arg(long, help = "Listen on <HTTP_PORT> for incoming HTTP requests. [default: 80]")]
 	pub(crate) http_port: Option<u16>,
 	#[arg(
@@ -144,8 +166,20 @@ pub struct Server {
 }
 
 impl Server {
-	pub fn run(self, settings: Settings, index: Arc<Index>, handle: Handle) -> SubcommandResult {
+	pub fn run(
+		self,
+		settings: Settings,
+		index: Arc<Index>,
+		events: tokio::sync::mpsc::Receiver<Event>,
+		handle: Handle,
+	) -> SubcommandResult {
 		Runtime::new()?.block_on(async {
+			let event_feed = EventFeed::spawn(events, index.block_count()?, self.event_history);
+
+			if let Some((url, secret)) = settings.webhook() {
+				Webhook::spawn(index.clone(), event_feed.listen(), url, secret)?;
+			}
+
 			let index_clone = index.clone();
 			let integration_test = settings.integration_test();
 
@@ -158,6 +192,10 @@ impl Server {
 					if let Err(error) = index_clone.update() {
 						log::warn!("Updating index: {error}");
 					}
+
+					if let Err(error) = index_clone.update_brc721_mempool() {
+						log::warn!("Updating brc721 mempool: {error}");
+					}
 				}
 
 				thread::sleep(if integration_test {
@@ -196,6 +234,7 @@ impl Server {
 				.route("/children/:inscription_id", get(Self::children))
 				.route("/children/:inscription_id/:page", get(Self::children_paginated))
 				.route("/clock", get(Self::clock))
+				.route("/events", get(Self::events))
 				.route("/collections", get(Self::collections))
 				.route("/collections/:page", get(Self::collections_paginated))
 				.route("/content/:inscription_id", get(Self::content))
@@ -260,8 +299,21 @@ impl Server {
 				.route("/update", get(Self::update))
 				.route("/brc721/collections", get(Self::brc721_collections))
 				.route("/brc721/collection/:collection_id", get(Self::brc721_collection))
+				.route("/brc721/collection-outputs", post(Self::brc721_collection_outputs))
+				.route(
+					"/brc721/collection/:collection_id/registered/:initial_owner",
+					get(Self::brc721_registered_slots),
+				)
+				.route("/brc721/initial-owner/:address", get(Self::brc721_initial_owner))
+				.route("/brc721/mempool", get(Self::brc721_mempool))
+				.route("/brc721/outputs", post(Self::brc721_outputs))
+				.route("/brc721/owner/:address", get(Self::brc721_owner))
+				.route("/brc721/owner/:address/:page", get(Self::brc721_owner_paginated))
+				.route("/brc721/token/:collection_id/:token_id", get(Self::brc721_token))
+				.route("/brc721/tx/:txid/collection", get(Self::brc721_tx_collection))
 				.fallback(Self::fallback)
 				.layer(Extension(index))
+				.layer(Extension(event_feed))
 				.layer(Extension(server_config.clone()))
 				.layer(Extension(settings.clone()))
 				.layer(SetResponseHeaderLayer::if_not_present(
@@ -273,7 +325,10 @@ impl Server {
 					HeaderValue::from_static("max-age=31536000; includeSubDomains; preload"),
 				))
 				.layer(CorsLayer::new().allow_methods([http::Method::GET]).allow_origin(Any))
-				.layer(CompressionLayer::new())
+				.layer(CompressionLayer::new().compress_when(
+					// compression buffers event streams
+					DefaultPredicate::new().and(NotForContentType::const_new("text/event-stream")),
+				))
 				.with_state(server_config.clone());
 
 			let router = if server_config.json_api_enabled {
@@ -476,6 +531,50 @@ impl Server {
 		})
 	}
 
+	async fn events(
+		Extension(event_feed): Extension<Arc<EventFeed>>,
+		Query(query): Query<EventsQuery>,
+		headers: HeaderMap,
+	) -> ServerResult {
+		// the id of a block event is its height, so clients reconnecting with `Last-Event-ID`
+		// resume from the next block
+		let from = match query.from {
+			Some(from) => Some(from),
+			None => headers
+				.get("last-event-id")
+				.map(|id| {
+					id.to_str()
+						.ok()
+						.and_then(|id| id.parse::<u32>().ok())
+						.map(|height| height + 1)
+						.ok_or_else(|| ServerError::BadRequest("invalid Last-Event-ID".into()))
+				})
+				.transpose()?,
+		};
+
+		let (history, receiver) = event_feed.subscribe(from).map_err(|first_height| {
+			ServerError::BadRequest(format!(
+				"events before height {first_height} are not available"
+			))
+		})?;
+
+		// end the stream if the subscriber lags behind, so it reconnects and resumes
+		let live = futures::stream::unfold(receiver, |mut receiver| async move {
+			receiver.recv().await.ok().map(|event| (event, receiver))
+		});
+
+		let stream = futures::stream::iter(history).chain(live).map(|event| {
+			let sse_event = sse::Event::default().json_data(&event)?;
+
+			Ok::<sse::Event, serde_json::Error>(match event {
+				Event::BlockIndexed { block_height, .. } => sse_event.id(block_height.to_string()),
+				_ => sse_event,
+			})
+		});
+
+		Ok(Sse::new(stream).keep_alive(KeepAlive::default()).into_response())
+	}
+
 	async fn fallback(Extension(index): Extension<Arc<Index>>, uri: Uri) -> ServerResult<Response> {
 		task::block_in_place(|| {
 			let path = urlencoding::decode(uri.path().trim_matches('/'))
@@ -1010,6 +1109,7 @@ impl Server {
 		task::block_in_place(|| {
 			if settings.integration_test() {
 				index.update()?;
+				index.update_brc721_mempool()?;
 				Ok(index.block_count()?.to_string().into_response())
 			} else {
 				Ok(StatusCode::NOT_FOUND.into_response())
@@ -1901,12 +2001,260 @@ impl Server {
 			.get_brc721_collection_by_id(collection_id)?
 			.ok_or_else(|| ServerError::NotFound("unexistent collection".to_string()))?;
 
-		let response_data = serde_json::to_value(collection).unwrap();
+		let pending = index
+			.get_brc721_pending_operations()
+			.into_iter()
+			.filter(|operation| operation.collection_id() == Some(collection_id))
+			.collect();
+
+		let response_data =
+			serde_json::to_value(api::Brc721Collection { collection, pending }).unwrap();
 
 		// Return the JSON response as an HTTP response.
 		Ok(Json(response_data).into_response())
 	}
 
+	async fn brc721_collection_outputs(
+		Extension(index): Extension<Arc<Index>>,
+		Json(outputs): Json<Vec<OutPoint>>,
+	) -> ServerResult {
+		task::block_in_place(|| {
+			if !index.has_brc721_index() {
+				return Err(ServerError::BadRequest("this server has no brc721 index".to_string()));
+			}
+
+			Ok(Json(
+				index
+					.get_brc721_collection_outputs(&outputs)?
+					.into_iter()
+					.map(|(output, collection_id)| api::Brc721CollectionOutput {
+						collection_id,
+						output,
+					})
+					.collect::<Vec<_>>(),
+			)
+			.into_response())
+		})
+	}
+
+	async fn brc721_tx_collection(
+		Extension(index): Extension<Arc<Index>>,
+		Path(txid): Path<Txid>,
+	) -> ServerResult {
+		task::block_in_place(|| {
+			if !index.has_brc721_index() {
+				return Err(ServerError::BadRequest("this server has no brc721 index".to_string()));
+			}
+
+			let collection = index
+				.get_brc721_collection_by_txid(txid)?
+				.ok_or_not_found(|| format!("brc721 collection registered by {txid}"))?;
+
+			let pending = index
+				.get_brc721_pending_operations()
+				.into_iter()
+				.filter(|operation| operation.collection_id() == Some(collection.id))
+				.collect();
+
+			Ok(Json(api::Brc721Collection { collection, pending }).into_response())
+		})
+	}
+
+	async fn brc721_registered_slots(
+		Extension(server_config): Extension<Arc<ServerConfig>>,
+		Extension(index): Extension<Arc<Index>>,
+		Path((collection_id, initial_owner)): Path<(Brc721CollectionId, Address<NetworkUnchecked>)>,
+	) -> ServerResult {
+		task::block_in_place(|| {
+			if !index.has_brc721_index() {
+				return Err(ServerError::BadRequest("this server has no brc721 index".to_string()));
+			}
+
+			let initial_owner = initial_owner
+				.require_network(server_config.chain.network())
+				.map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+			let initial_owner = btc_address_to_h160(initial_owner)
+				.map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+			let slots = index.get_brc721_registered_slots(collection_id, initial_owner)?;
+
+			Ok(Json(api::Brc721RegisteredSlots { collection_id, initial_owner, slots })
+				.into_response())
+		})
+	}
+
+	async fn brc721_initial_owner(
+		Extension(server_config): Extension<Arc<ServerConfig>>,
+		Extension(index): Extension<Arc<Index>>,
+		Path(address): Path<Address<NetworkUnchecked>>,
+	) -> ServerResult {
+		task::block_in_place(|| {
+			if !index.has_brc721_index() {
+				return Err(ServerError::BadRequest("this server has no brc721 index".to_string()));
+			}
+
+			let address = address
+				.require_network(server_config.chain.network())
+				.map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+			let initial_owner = btc_address_to_h160(address.clone())
+				.map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+			let registrations = index.get_brc721_registrations_by_initial_owner(initial_owner)?;
+
+			Ok(Json(api::Brc721InitialOwner {
+				address: uncheck(&address),
+				initial_owner,
+				registrations,
+			})
+			.into_response())
+		})
+	}
+
+	async fn brc721_mempool(Extension(index): Extension<Arc<Index>>) -> ServerResult {
+		if !index.has_brc721_index() {
+			return Err(ServerError::BadRequest("this server has no brc721 index".to_string()));
+		}
+
+		Ok(Json(index.get_brc721_pending_operations()).into_response())
+	}
+
+	async fn brc721_outputs(
+		Extension(index): Extension<Arc<Index>>,
+		Json(outputs): Json<Vec<OutPoint>>,
+	) -> ServerResult {
+		task::block_in_place(|| {
+			if !index.has_brc721_index() {
+				return Err(ServerError::BadRequest("this server has no brc721 index".to_string()));
+			}
+
+			let mut tokens = Vec::new();
+			for output in outputs {
+				for (collection_id, initial_owner, slots) in
+					index.get_brc721_output_tokens(output)?
+				{
+					tokens.push(api::Brc721TokenRange {
+						collection_id,
+						initial_owner,
+						output,
+						slots,
+					});
+				}
+			}
+
+			Ok(Json(tokens).into_response())
+		})
+	}
+
+	async fn brc721_owner(
+		Extension(server_config): Extension<Arc<ServerConfig>>,
+		Extension(index): Extension<Arc<Index>>,
+		Path(address): Path<Address<NetworkUnchecked>>,
+	) -> ServerResult {
+		Self::brc721_owner_paginated(Extension(server_config), Extension(index), Path((address, 0)))
+			.await
+	}
+
+	async fn brc721_owner_paginated(
+		Extension(server_config): Extension<Arc<ServerConfig>>,
+		Extension(index): Extension<Arc<Index>>,
+		Path((address, page_index)): Path<(Address<NetworkUnchecked>, usize)>,
+	) -> ServerResult {
+		task::block_in_place(|| {
+			if !index.has_brc721_index() {
+				return Err(ServerError::BadRequest("this server has no brc721 index".to_string()));
+			}
+
+			let address = address
+				.require_network(server_config.chain.network())
+				.map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+			let (tokens, more) = index.get_brc721_tokens_by_script_pubkey(
+				&address.script_pubkey(),
+				100,
+				page_index,
+			)?;
+
+			Ok(Json(api::Brc721OwnerTokens {
+				address: uncheck(&address),
+				more,
+				page_index,
+				tokens,
+			})
+			.into_response())
+		})
+	}
+
+	async fn brc721_token(
+		Extension(server_config): Extension<Arc<ServerConfig>>,
+		Extension(index): Extension<Arc<Index>>,
+		Path((collection_id, token_id)): Path<(Brc721CollectionId, Brc721TokenId)>,
+		AcceptJson(accept_json): AcceptJson,
+	) -> ServerResult {
+		task::block_in_place(|| {
+			if !index.has_brc721_index() {
+				return Err(ServerError::BadRequest("this server has no brc721 index".to_string()));
+			}
+
+			let (output, token_uri, burned) =
+				match index.get_brc721_token(collection_id, token_id)? {
+					Some((output, token_uri)) => (Some(output), token_uri, None),
+					None => {
+						let burned = index
+							.get_brc721_token_burn_height(collection_id, token_id)?
+							.ok_or_not_found(|| {
+							format!("token {token_id} in collection {collection_id}")
+						})?;
+
+						(None, None, Some(burned))
+					},
+				};
+
+			let address = match output {
+				Some(output) => index
+					.get_transaction(output.txid)?
+					.and_then(|tx| tx.output.into_iter().nth(output.vout.try_into().unwrap()))
+					.and_then(|tx_out| {
+						server_config.chain.address_from_script(&tx_out.script_pubkey).ok()
+					}),
+				None => None,
+			};
+
+			if !accept_json {
+				return Ok(Brc721TokenHtml {
+					address,
+					burned,
+					collection_id,
+					output,
+					token_id,
+					token_uri,
+				}
+				.page(server_config)
+				.into_response());
+			}
+
+			let pending = index
+				.get_brc721_pending_operations()
+				.into_iter()
+				.filter(|operation| operation.affects_token(collection_id, token_id))
+				.collect();
+
+			Ok(Json(api::Brc721Token {
+				address: address.as_ref().map(uncheck),
+				burned,
+				collection_id,
+				initial_owner: token_id.initial_owner,
+				output,
+				pending,
+				slot: token_id.slot,
+				token_id,
+				token_uri,
+			})
+			.into_response())
+		})
+	}
+
 	async fn inscriptions_paginated(
 		Extension(server_config): Extension<Arc<ServerConfig>>,
 		Extension(index): Extension<Arc<Index>>,
@@ -2100,7 +2448,14 @@ impl Server {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use ordinals::RegisterCollection;
+	use ordinals::{
+		brc721::{
+			burn_token::BurnToken,
+			rebase_token::RebaseToken,
+			register_ownership::{RegisterOwnership, SlotsBundle},
+		},
+		RegisterCollection,
+	};
 	use reqwest::Url;
 	use serde::de::DeserializeOwned;
 	use sp_core::H160;
@@ -2225,13 +2580,17 @@ mod tests {
 				.or_defaults()
 				.unwrap();
 
-			let index = Arc::new(Index::open(&settings).unwrap());
+			let (event_sender, event_receiver) = tokio::sync::mpsc::channel(1024);
+			let index =
+				Arc::new(Index::open_with_event_sender(&settings, Some(event_sender)).unwrap());
 			let ord_server_handle = Handle::new();
 
 			{
 				let index = index.clone();
 				let ord_server_handle = ord_server_handle.clone();
-				thread::spawn(|| server.run(settings, index, ord_server_handle).unwrap());
+				thread::spawn(|| {
+					server.run(settings, index, event_receiver, ord_server_handle).unwrap()
+				});
 			}
 
 			while index.statistic(crate::index::Statistic::Commits) == 0 {
@@ -7093,7 +7452,833 @@ next
 		server.assert_response(
 			"/brc721/collection/2:1",
 			StatusCode::OK,
-			r#"{"id":"2:1","LAOS_address":"0xabcffffffffffffffffffffffffffffffffffcba","rebaseable":false}"#,
+			r#"{"id":"2:1","LAOS_address":"0xabcffffffffffffffffffffffffffffffffffcba","rebaseable":false,"pending":[]}"#,
+		);
+	}
+
+	#[test]
+	fn brc721_collection_by_txid() {
+		let server = TestServer::builder().chain(Chain::Regtest).index_brc721().build();
+
+		server.mine_blocks(1);
+
+		let txid = server.core.broadcast_tx(TransactionTemplate {
+			inputs: &[],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(RegisterCollection::default().as_script()),
+			..default()
+		});
+
+		server.assert_response(
+			format!("/brc721/tx/{txid}/collection"),
+			StatusCode::NOT_FOUND,
+			&format!("brc721 collection registered by {txid} not found"),
+		);
+
+		server.mine_blocks(1);
+
+		assert_eq!(
+			server.get_json::<api::Brc721Collection>(format!("/brc721/tx/{txid}/collection")),
+			api::Brc721Collection {
+				collection: Brc721Collection::new(
+					Brc721CollectionId { block: 2, tx: 1 },
+					RegisterCollection::default().address,
+					RegisterCollection::default().rebaseable,
+				),
+				pending: Vec::new(),
+			}
+		);
+	}
+
+	#[test]
+	fn brc721_collection_outputs() {
+		let server = TestServer::builder().chain(Chain::Regtest).index_brc721().build();
+
+		server.mine_blocks(1);
+
+		let txid = server.core.broadcast_tx(TransactionTemplate {
+			inputs: &[],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(RegisterCollection::default().as_script()),
+			..default()
+		});
+
+		server.mine_blocks(1);
+
+		let outputs = [OutPoint::new(txid, 0), OutPoint::new(txid, 1), OutPoint::null()];
+
+		let response = reqwest::blocking::Client::new()
+			.post(server.join_url("/brc721/collection-outputs"))
+			.json(&outputs)
+			.send()
+			.unwrap();
+
+		assert_eq!(response.status(), StatusCode::OK);
+
+		assert_eq!(
+			response.json::<Vec<api::Brc721CollectionOutput>>().unwrap(),
+			[api::Brc721CollectionOutput {
+				collection_id: Brc721CollectionId { block: 2, tx: 1 },
+				output: OutPoint::new(txid, 1),
+			}]
+		);
+	}
+
+	#[test]
+	fn brc721_registered_slots() {
+		let server = TestServer::builder().chain(Chain::Regtest).index_brc721().build();
+
+		server.mine_blocks(1);
+
+		server.core.broadcast_tx(TransactionTemplate {
+			inputs: &[],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(RegisterCollection::default().as_script()),
+			..default()
+		});
+
+		server.mine_blocks(1);
+
+		let collection_id = Brc721CollectionId { block: 2, tx: 1 };
+
+		let register_ownership = RegisterOwnership {
+			collection_id,
+			slots_bundles: vec![SlotsBundle(vec![0..=3, 10..=10])],
+		};
+
+		server.core.broadcast_tx(TransactionTemplate {
+			inputs: &[(1, 0, 0, Witness::new())],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(register_ownership.into()),
+			..default()
+		});
+
+		server.mine_blocks(1);
+
+		let initial_owner = server
+			.core
+			.address(OutPoint { txid: server.core.tx(1, 0).compute_txid(), vout: 0 });
+
+		pretty_assert_eq!(
+			server.get_json::<api::Brc721RegisteredSlots>(format!(
+				"/brc721/collection/2:1/registered/{initial_owner}"
+			)),
+			api::Brc721RegisteredSlots {
+				collection_id,
+				initial_owner: btc_address_to_h160(initial_owner).unwrap(),
+				slots: vec![0..=3, 10..=10],
+			}
+		);
+
+		let other_owner = server
+			.core
+			.address(OutPoint { txid: server.core.tx(2, 0).compute_txid(), vout: 0 });
+
+		pretty_assert_eq!(
+			server.get_json::<api::Brc721RegisteredSlots>(format!(
+				"/brc721/collection/2:1/registered/{other_owner}"
+			)),
+			api::Brc721RegisteredSlots {
+				collection_id,
+				initial_owner: btc_address_to_h160(other_owner).unwrap(),
+				slots: Vec::new(),
+			}
+		);
+	}
+
+	#[test]
+	fn brc721_token() {
+		let server = TestServer::builder().chain(Chain::Regtest).index_brc721().build();
+
+		server.mine_blocks(1);
+
+		server.core.broadcast_tx(TransactionTemplate {
+			inputs: &[],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(
+				RegisterCollection { address: H160::zero(), rebaseable: true }.as_script(),
+			),
+			..default()
+		});
+
+		server.mine_blocks(1);
+
+		let collection_id = Brc721CollectionId { block: 2, tx: 1 };
+
+		let txid = server.core.broadcast_tx(TransactionTemplate {
+			inputs: &[(1, 0, 0, Witness::new())],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(
+				RegisterOwnership { collection_id, slots_bundles: vec![SlotsBundle(vec![0..=3])] }
+					.into(),
+			),
+			..default()
+		});
+
+		server.mine_blocks(1);
+
+		let initial_owner = btc_address_to_h160(
+			server
+				.core
+				.address(OutPoint { txid: server.core.tx(1, 0).compute_txid(), vout: 0 }),
+		)
+		.unwrap();
+
+		let token_id = Brc721TokenId { slot: 2, initial_owner };
+
+		let output = OutPoint { txid, vout: 1 };
+
+		pretty_assert_eq!(
+			server.get_json::<api::Brc721Token>(format!("/brc721/token/2:1/{token_id}")),
+			api::Brc721Token {
+				address: Some(uncheck(&server.core.address(output))),
+				burned: None,
+				collection_id,
+				initial_owner,
+				output: Some(output),
+				pending: Vec::new(),
+				slot: 2,
+				token_id,
+				token_uri: None,
+			}
+		);
+
+		let txid = server.core.broadcast_tx(TransactionTemplate {
+			inputs: &[(3, 1, 1, Witness::new())],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(
+				RebaseToken { collection_id, token_id, token_uri: "ipfs://foo".into() }.into(),
+			),
+			..default()
+		});
+
+		server.mine_blocks(1);
+
+		let output = OutPoint { txid, vout: 1 };
+
+		pretty_assert_eq!(
+			server.get_json::<api::Brc721Token>(format!("/brc721/token/2:1/{token_id}")),
+			api::Brc721Token {
+				address: Some(uncheck(&server.core.address(output))),
+				burned: None,
+				collection_id,
+				initial_owner,
+				output: Some(output),
+				pending: Vec::new(),
+				slot: 2,
+				token_id,
+				token_uri: Some("ipfs://foo".into()),
+			}
+		);
+
+		server.assert_response(
+			format!("/brc721/token/2:1/{}", Brc721TokenId { slot: 4, initial_owner }),
+			StatusCode::NOT_FOUND,
+			&format!(
+				"token {} in collection 2:1 not found",
+				Brc721TokenId { slot: 4, initial_owner }
+			),
+		);
+	}
+
+	#[test]
+	fn brc721_burned_token() {
+		let server = TestServer::builder().chain(Chain::Regtest).index_brc721().build();
+
+		server.mine_blocks(1);
+
+		server.core.broadcast_tx(TransactionTemplate {
+			inputs: &[],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(RegisterCollection::default().as_script()),
+			..default()
+		});
+
+		server.mine_blocks(1);
+
+		let collection_id = Brc721CollectionId { block: 2, tx: 1 };
+
+		server.core.broadcast_tx(TransactionTemplate {
+			inputs: &[(1, 0, 0, Witness::new())],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(
+				RegisterOwnership { collection_id, slots_bundles: vec![SlotsBundle(vec![0..=3])] }
+					.into(),
+			),
+			..default()
+		});
+
+		server.mine_blocks(1);
+
+		let initial_owner = btc_address_to_h160(
+			server
+				.core
+				.address(OutPoint { txid: server.core.tx(1, 0).compute_txid(), vout: 0 }),
+		)
+		.unwrap();
+
+		let token_id = Brc721TokenId { slot: 2, initial_owner };
+
+		server.core.broadcast_tx(TransactionTemplate {
+			inputs: &[(3, 1, 1, Witness::new())],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(BurnToken { collection_id, token_id }.into()),
+			..default()
+		});
+
+		server.mine_blocks(1);
+
+		pretty_assert_eq!(
+			server.get_json::<api::Brc721Token>(format!("/brc721/token/2:1/{token_id}")),
+			api::Brc721Token {
+				address: None,
+				burned: Some(4),
+				collection_id,
+				initial_owner,
+				output: None,
+				pending: Vec::new(),
+				slot: 2,
+				token_id,
+				token_uri: None,
+			}
+		);
+
+		server.assert_response_regex(
+			format!("/brc721/token/2:1/{token_id}"),
+			StatusCode::OK,
+			".*<h1>Brc721 Token .*<dd>burned at height <a href=/block/4>4</a></dd>.*",
+		);
+	}
+
+	#[test]
+	fn brc721_token_no_index_error() {
+		let server = TestServer::builder().chain(Chain::Regtest).build();
+
+		server.assert_response(
+			"/brc721/token/2:1/0",
+			StatusCode::BAD_REQUEST,
+			"this server has no brc721 index",
+		);
+	}
+
+	#[test]
+	fn brc721_mempool() {
+		let server = TestServer::builder().chain(Chain::Regtest).index_brc721().build();
+
+		server.mine_blocks(1);
+
+		let address = H160::from_str("0xabcffffffffffffffffffffffffffffffffffcba").unwrap();
+
+		let txid = server.core.broadcast_tx(TransactionTemplate {
+			inputs: &[],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(RegisterCollection { address, rebaseable: false }.as_script()),
+			..default()
+		});
+
+		server.index.update_brc721_mempool().unwrap();
+
+		pretty_assert_eq!(
+			server.get_json::<Vec<api::Brc721PendingOperation>>("/brc721/mempool"),
+			[api::Brc721PendingOperation::RegisterCollection {
+				txid,
+				laos_address: address,
+				rebaseable: false,
+			}]
+		);
+
+		server.mine_blocks(1);
+
+		let collection_id = Brc721CollectionId { block: 2, tx: 1 };
+
+		let txid = server.core.broadcast_tx(TransactionTemplate {
+			inputs: &[(1, 0, 0, Witness::new())],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(
+				RegisterOwnership { collection_id, slots_bundles: vec![SlotsBundle(vec![0..=3])] }
+					.into(),
+			),
+			..default()
+		});
+
+		server.index.update_brc721_mempool().unwrap();
+
+		let pending = vec![api::Brc721PendingOperation::RegisterOwnership {
+			txid,
+			collection_id,
+			slots_bundles: vec![vec![0..=3]],
+		}];
+
+		pretty_assert_eq!(
+			server.get_json::<Vec<api::Brc721PendingOperation>>("/brc721/mempool"),
+			pending
+		);
+
+		pretty_assert_eq!(
+			server.get_json::<api::Brc721Collection>("/brc721/collection/2:1"),
+			api::Brc721Collection {
+				collection: Brc721Collection::new(collection_id, address, false),
+				pending,
+			}
+		);
+
+		server.mine_blocks(1);
+
+		server.index.update_brc721_mempool().unwrap();
+
+		pretty_assert_eq!(
+			server.get_json::<Vec<api::Brc721PendingOperation>>("/brc721/mempool"),
+			[]
+		);
+	}
+
+	#[test]
+	fn brc721_mempool_drops_evicted_transactions() {
+		let server = TestServer::builder().chain(Chain::Regtest).index_brc721().build();
+
+		server.mine_blocks(1);
+
+		let txid = server.core.broadcast_tx(TransactionTemplate {
+			inputs: &[],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(RegisterCollection::default().as_script()),
+			..default()
+		});
+
+		server.index.update_brc721_mempool().unwrap();
+
+		pretty_assert_eq!(
+			server.get_json::<Vec<api::Brc721PendingOperation>>("/brc721/mempool"),
+			[api::Brc721PendingOperation::RegisterCollection {
+				txid,
+				laos_address: RegisterCollection::default().address,
+				rebaseable: RegisterCollection::default().rebaseable,
+			}]
+		);
+
+		server.core.state().mempool.clear();
+
+		server.index.update_brc721_mempool().unwrap();
+
+		pretty_assert_eq!(
+			server.get_json::<Vec<api::Brc721PendingOperation>>("/brc721/mempool"),
+			[]
+		);
+	}
+
+	#[test]
+	fn brc721_token_pending() {
+		let server = TestServer::builder().chain(Chain::Regtest).index_brc721().build();
+
+		server.mine_blocks(1);
+
+		server.core.broadcast_tx(TransactionTemplate {
+			inputs: &[],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(
+				RegisterCollection { address: H160::zero(), rebaseable: true }.as_script(),
+			),
+			..default()
+		});
+
+		server.mine_blocks(1);
+
+		let collection_id = Brc721CollectionId { block: 2, tx: 1 };
+
+		let registration = server.core.broadcast_tx(TransactionTemplate {
+			inputs: &[(1, 0, 0, Witness::new())],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(
+				RegisterOwnership { collection_id, slots_bundles: vec![SlotsBundle(vec![0..=3])] }
+					.into(),
+			),
+			..default()
+		});
+
+		server.mine_blocks(1);
+
+		let initial_owner = btc_address_to_h160(
+			server
+				.core
+				.address(OutPoint { txid: server.core.tx(1, 0).compute_txid(), vout: 0 }),
+		)
+		.unwrap();
+
+		let token_id = Brc721TokenId { slot: 2, initial_owner };
+
+		let txid = server.core.broadcast_tx(TransactionTemplate {
+			inputs: &[(3, 1, 1, Witness::new())],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(
+				RebaseToken { collection_id, token_id, token_uri: "ipfs://foo".into() }.into(),
+			),
+			..default()
+		});
+
+		server.index.update_brc721_mempool().unwrap();
+
+		let output = OutPoint { txid: registration, vout: 1 };
+
+		pretty_assert_eq!(
+			server.get_json::<api::Brc721Token>(format!("/brc721/token/2:1/{token_id}")),
+			api::Brc721Token {
+				address: Some(uncheck(&server.core.address(output))),
+				burned: None,
+				collection_id,
+				initial_owner,
+				output: Some(output),
+				pending: vec![
+					api::Brc721PendingOperation::RebaseToken {
+						txid,
+						collection_id,
+						token_id,
+						token_uri: "ipfs://foo".into(),
+					},
+					api::Brc721PendingOperation::Transfer {
+						txid,
+						collection_id,
+						initial_owner,
+						slots: 0..=3,
+						output,
+						destination: Some(OutPoint { txid, vout: 1 }),
+					},
+				],
+				slot: 2,
+				token_id,
+				token_uri: None,
+			}
+		);
+	}
+
+	#[test]
+	fn brc721_mempool_no_index_error() {
+		let server = TestServer::builder().chain(Chain::Regtest).build();
+
+		server.assert_response(
+			"/brc721/mempool",
+			StatusCode::BAD_REQUEST,
+			"this server has no brc721 index",
+		);
+	}
+
+	/// Reads server-sent events until the one with id `last_id`, returning their ids and data.
+	fn read_events(
+		response: reqwest::blocking::Response,
+		last_id: &str,
+	) -> Vec<(Option<String>, Event)> {
+		let mut events = Vec::new();
+		let mut id = None;
+		let mut data = None;
+
+		for line in io::BufRead::lines(io::BufReader::new(response)) {
+			let line = line.unwrap();
+
+			if let Some(value) = line.strip_prefix("id:") {
+				id = Some(value.to_string());
+			} else if let Some(value) = line.strip_prefix("data:") {
+				data = Some(serde_json::from_str::<Event>(value).unwrap());
+			} else if line.is_empty() {
+				if let Some(event) = data.take() {
+					let done = id.as_deref() == Some(last_id);
+					events.push((id.take(), event));
+					if done {
+						break;
+					}
+				}
+			}
+		}
+
+		events
+	}
+
+	#[test]
+	fn events_resume_from_height() {
+		let server = TestServer::builder().chain(Chain::Regtest).index_brc721().build();
+
+		server.mine_blocks(1);
+
+		let address = H160::repeat_byte(1);
+
+		let txid = server.core.broadcast_tx(TransactionTemplate {
+			inputs: &[(1, 0, 0, Witness::new())],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(RegisterCollection { address, rebaseable: false }.as_script()),
+			..default()
+		});
+
+		let blocks = server.mine_blocks(1);
+
+		let response = server.get("/events?from=2");
+		assert_eq!(response.status(), StatusCode::OK);
+		assert_eq!(response.headers().get(header::CONTENT_TYPE).unwrap(), "text/event-stream");
+
+		pretty_assert_eq!(
+			read_events(response, "2"),
+			[
+				(
+					None,
+					Event::Brc721CollectionRegistered {
+						block_height: 2,
+						collection_id: Brc721CollectionId { block: 2, tx: 1 },
+						laos_address: address,
+						rebaseable: false,
+						txid,
+					}
+				),
+				(
+					Some("2".into()),
+					Event::BlockIndexed { block_hash: blocks[0].block_hash(), block_height: 2 }
+				),
+			]
+		);
+	}
+
+	#[test]
+	fn events_resume_from_last_event_id() {
+		let server = TestServer::builder().chain(Chain::Regtest).build();
+
+		let blocks = server.mine_blocks(2);
+
+		let response = reqwest::blocking::Client::new()
+			.get(server.join_url("/events"))
+			.header("Last-Event-ID", "1")
+			.send()
+			.unwrap();
+
+		assert_eq!(response.status(), StatusCode::OK);
+
+		pretty_assert_eq!(
+			read_events(response, "2"),
+			[(
+				Some("2".into()),
+				Event::BlockIndexed { block_hash: blocks[1].block_hash(), block_height: 2 }
+			)]
+		);
+	}
+
+	#[test]
+	fn events_of_old_blocks_are_not_available() {
+		let server = TestServer::builder()
+			.chain(Chain::Regtest)
+			.server_option("--event-history", "1")
+			.build();
+
+		server.mine_blocks(3);
+
+		// wait for the events of the last block to be fed
+		read_events(server.get("/events?from=3"), "3");
+
+		server.assert_response(
+			"/events?from=2",
+			StatusCode::BAD_REQUEST,
+			"events before height 3 are not available",
+		);
+	}
+
+	/// Listens for webhook requests, answering the first ones with `statuses` and the rest with
+	/// 200, and returns the URL and a receiver for the signature and body of each request.
+	fn webhook_listener(
+		statuses: &'static [u16],
+	) -> (String, std::sync::mpsc::Receiver<(String, String)>) {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let url = format!("http://{}/webhook", listener.local_addr().unwrap());
+		let (sender, receiver) = std::sync::mpsc::channel();
+
+		thread::spawn(move || {
+			let mut deliveries = 0;
+
+			for stream in listener.incoming() {
+				let mut stream = stream.unwrap();
+				let mut reader = io::BufReader::new(stream.try_clone().unwrap());
+
+				let mut request_line = String::new();
+				io::BufRead::read_line(&mut reader, &mut request_line).unwrap();
+
+				let mut signature = String::new();
+				let mut content_length = 0;
+
+				loop {
+					let mut line = String::new();
+					io::BufRead::read_line(&mut reader, &mut line).unwrap();
+
+					let Some((name, value)) = line.trim_end().split_once(": ") else {
+						if line.trim_end().is_empty() {
+							break;
+						}
+						continue;
+					};
+
+					match name.to_lowercase().as_str() {
+						"content-length" => content_length = value.parse().unwrap(),
+						"x-signature-256" => signature = value.into(),
+						_ => {},
+					}
+				}
+
+				let mut body = vec![0; content_length];
+				reader.read_exact(&mut body).unwrap();
+
+				// the port may have been used by a server of another test, whose clients may
+				// still send it requests
+				if !request_line.starts_with("POST /webhook ") {
+					io::Write::write_all(
+						&mut stream,
+						b"HTTP/1.1 404 \r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+					)
+					.unwrap();
+					continue;
+				}
+
+				let status = statuses.get(deliveries).copied().unwrap_or(200);
+				deliveries += 1;
+
+				io::Write::write_all(
+					&mut stream,
+					format!("HTTP/1.1 {status} \r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+						.as_bytes(),
+				)
+				.unwrap();
+
+				if sender.send((signature, String::from_utf8(body).unwrap())).is_err() {
+					break;
+				}
+			}
+		});
+
+		(url, receiver)
+	}
+
+	#[test]
+	fn webhook_receives_signed_block_events() {
+		let (url, requests) = webhook_listener(&[]);
+
+		let server = TestServer::builder()
+			.chain(Chain::Regtest)
+			.index_brc721()
+			.ord_option("--webhook-url", &url)
+			.ord_option("--webhook-secret", "foo")
+			.build();
+
+		server.mine_blocks(1);
+
+		let address = H160::repeat_byte(1);
+
+		let txid = server.core.broadcast_tx(TransactionTemplate {
+			inputs: &[(1, 0, 0, Witness::new())],
+			outputs: 1,
+			op_return_index: Some(0),
+			op_return_value: Some(0),
+			op_return: Some(RegisterCollection { address, rebaseable: false }.as_script()),
+			..default()
+		});
+
+		let blocks = server.mine_blocks(1);
+
+		let mut payloads = Vec::new();
+
+		while payloads.last().map(|payload: &api::BlockEvents| payload.block_height) != Some(2) {
+			let (signature, body) = requests.recv_timeout(Duration::from_secs(10)).unwrap();
+			assert_eq!(signature, webhook::sign("foo", &body));
+			payloads.push(serde_json::from_str(&body).unwrap());
+		}
+
+		assert_eq!(
+			payloads.iter().map(|payload| payload.block_height).collect::<Vec<u32>>(),
+			[0, 1, 2]
+		);
+
+		pretty_assert_eq!(
+			payloads[2],
+			api::BlockEvents {
+				block_hash: blocks[0].block_hash(),
+				block_height: 2,
+				events: vec![Event::Brc721CollectionRegistered {
+					block_height: 2,
+					collection_id: Brc721CollectionId { block: 2, tx: 1 },
+					laos_address: address,
+					rebaseable: false,
+					txid,
+				}],
+			}
+		);
+
+		while server.index.statistic(crate::index::Statistic::WebhookCursor) < 3 {
+			thread::sleep(Duration::from_millis(50));
+		}
+	}
+
+	#[test]
+	fn webhook_retries_failed_deliveries() {
+		let (url, requests) = webhook_listener(&[500, 503]);
+
+		let _server = TestServer::builder()
+			.chain(Chain::Regtest)
+			.ord_option("--webhook-url", &url)
+			.ord_option("--webhook-secret", "foo")
+			.build();
+
+		let (_, first) = requests.recv_timeout(Duration::from_secs(10)).unwrap();
+		let (_, second) = requests.recv_timeout(Duration::from_secs(10)).unwrap();
+		let (_, third) = requests.recv_timeout(Duration::from_secs(10)).unwrap();
+
+		assert_eq!(first, second);
+		assert_eq!(second, third);
+		assert_eq!(serde_json::from_str::<api::BlockEvents>(&third).unwrap().block_height, 0);
+	}
+
+	#[test]
+	fn brc721_registered_slots_unsupported_address() {
+		let server = TestServer::builder().chain(Chain::Regtest).index_brc721().build();
+
+		server.assert_response(
+			"/brc721/collection/2:1/registered/2MzQwSSnBHWHqSAqtTVQ6v47XtaisrJa1Vc",
+			StatusCode::BAD_REQUEST,
+			"Invalid BTC address: `2MzQwSSnBHWHqSAqtTVQ6v47XtaisrJa1Vc`. Only P2PKH and P2WPKH supported.",
+		);
+	}
+
+	#[test]
+	fn brc721_registered_slots_no_index_error() {
+		let server = TestServer::builder().chain(Chain::Regtest).build();
+
+		server.assert_response(
+			"/brc721/collection/2:1/registered/bcrt1pswcsgefgmts0esvgvw0hx3w3xf68ce8yf9tmsgu5ltlj5kmrcjlqd402f3",
+			StatusCode::BAD_REQUEST,
+			"this server has no brc721 index",
 		);
 	}
 }
@@ -0,0 +1,216 @@
+// Copyright 2023-2024 Freeverse.io
+// This file is part of LAOS.
+
+// LAOS is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// LAOS is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use std::{collections::VecDeque, sync::mpsc as std_mpsc};
+use tokio::sync::{broadcast, mpsc};
+
+/// Subscribers lagging more than this many events behind are disconnected.
+const BROADCAST_CAPACITY: usize = 10_000;
+
+/// Index events of the latest blocks, broadcast to subscribers as they arrive.
+pub(crate) struct EventFeed {
+	blocks: u32,
+	history: Mutex<History>,
+	sender: broadcast::Sender<Event>,
+}
+
+struct History {
+	events: VecDeque<Event>,
+	first_height: u32,
+	listeners: Vec<std_mpsc::Sender<Event>>,
+}
+
+impl EventFeed {
+	/// Spawns a thread feeding the events received from the index.
+	///
+	/// Events of the last `blocks` blocks are kept for subscribers resuming from a height. Events
+	/// of blocks below `first_height`, indexed before the feed was created, are not available.
+	pub(crate) fn spawn(
+		mut receiver: mpsc::Receiver<Event>,
+		first_height: u32,
+		blocks: u32,
+	) -> Arc<Self> {
+		let (sender, _) = broadcast::channel(BROADCAST_CAPACITY);
+
+		let feed = Arc::new(Self {
+			blocks,
+			history: Mutex::new(History {
+				events: VecDeque::new(),
+				first_height,
+				listeners: Vec::new(),
+			}),
+			sender,
+		});
+
+		{
+			let feed = feed.clone();
+			thread::spawn(move || {
+				while let Some(event) = receiver.blocking_recv() {
+					feed.push(event);
+				}
+			});
+		}
+
+		feed
+	}
+
+	fn push(&self, event: Event) {
+		let mut history = self.history.lock().unwrap();
+
+		let height = event.block_height();
+
+		// events of the blocks rolled back by a reorg never happened
+		if let Event::Reorg { block_height } = event {
+			history.events.retain(|event| event.block_height() < block_height);
+		}
+
+		while let Some(front) = history.events.front() {
+			if front.block_height().saturating_add(self.blocks) > height {
+				break;
+			}
+
+			history.first_height = history.first_height.max(front.block_height() + 1);
+			history.events.pop_front();
+		}
+
+		history.events.push_back(event.clone());
+
+		history.listeners.retain(|listener| listener.send(event.clone()).is_ok());
+
+		// sending fails if there are no subscribers, which is fine
+		self.sender.send(event).ok();
+	}
+
+	/// Returns a receiver for all new events which, unlike subscribers, never lags behind.
+	pub(crate) fn listen(&self) -> std_mpsc::Receiver<Event> {
+		let (sender, receiver) = std_mpsc::channel();
+		self.history.lock().unwrap().listeners.push(sender);
+		receiver
+	}
+
+	/// Returns the kept events of blocks from `height` on, and a receiver for new events.
+	///
+	/// Fails with the first available height if the events of `height` are no longer kept.
+	pub(crate) fn subscribe(
+		&self,
+		height: Option<u32>,
+	) -> Result<(Vec<Event>, broadcast::Receiver<Event>), u32> {
+		let history = self.history.lock().unwrap();
+
+		let receiver = self.sender.subscribe();
+
+		let Some(height) = height else {
+			return Ok((Vec::new(), receiver));
+		};
+
+		if height < history.first_height {
+			return Err(history.first_height);
+		}
+
+		Ok((
+			history
+				.events
+				.iter()
+				.filter(|event| event.block_height() >= height)
+				.cloned()
+				.collect(),
+			receiver,
+		))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn block(height: u32) -> Event {
+		Event::BlockIndexed { block_hash: BlockHash::all_zeros(), block_height: height }
+	}
+
+	fn feed(blocks: u32) -> (mpsc::Sender<Event>, Arc<EventFeed>) {
+		let (sender, receiver) = mpsc::channel(16);
+		(sender, EventFeed::spawn(receiver, 10, blocks))
+	}
+
+	#[test]
+	fn events_before_first_height_are_not_available() {
+		let (_sender, feed) = feed(2);
+		assert_eq!(feed.subscribe(Some(9)).unwrap_err(), 10);
+		assert!(feed.subscribe(Some(10)).unwrap().0.is_empty());
+		assert!(feed.subscribe(None).unwrap().0.is_empty());
+	}
+
+	#[test]
+	fn subscribers_receive_new_events() {
+		let (sender, feed) = feed(2);
+
+		let (_, mut receiver) = feed.subscribe(None).unwrap();
+
+		sender.blocking_send(block(10)).unwrap();
+
+		assert_eq!(receiver.blocking_recv().unwrap(), block(10));
+	}
+
+	#[test]
+	fn listeners_receive_all_new_events() {
+		let (sender, feed) = feed(2);
+
+		let listener = feed.listen();
+
+		for height in 10..13 {
+			sender.blocking_send(block(height)).unwrap();
+		}
+
+		for height in 10..13 {
+			assert_eq!(listener.recv().unwrap(), block(height));
+		}
+	}
+
+	#[test]
+	fn events_of_old_blocks_are_dropped() {
+		let (sender, feed) = feed(2);
+
+		let (_, mut receiver) = feed.subscribe(None).unwrap();
+
+		for height in 10..13 {
+			sender.blocking_send(block(height)).unwrap();
+			receiver.blocking_recv().unwrap();
+		}
+
+		assert_eq!(feed.subscribe(Some(10)).unwrap_err(), 11);
+		assert_eq!(feed.subscribe(Some(11)).unwrap().0, [block(11), block(12)]);
+		assert_eq!(feed.subscribe(Some(12)).unwrap().0, [block(12)]);
+	}
+
+	#[test]
+	fn events_of_reverted_blocks_are_dropped() {
+		let (sender, feed) = feed(5);
+
+		let (_, mut receiver) = feed.subscribe(None).unwrap();
+
+		for event in [block(10), block(11), block(12), Event::Reorg { block_height: 11 }, block(11)]
+		{
+			sender.blocking_send(event).unwrap();
+			receiver.blocking_recv().unwrap();
+		}
+
+		assert_eq!(
+			feed.subscribe(Some(10)).unwrap().0,
+			[block(10), Event::Reorg { block_height: 11 }, block(11)]
+		);
+	}
+}
@@ -0,0 +1,129 @@
+// Copyright 2023-2024 Freeverse.io
+// This file is part of LAOS.
+
+// LAOS is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// LAOS is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use bitcoin::hashes::{hmac, sha256, HashEngine};
+use std::sync::mpsc::{self as std_mpsc, RecvTimeoutError};
+
+/// Header carrying the HMAC-SHA256 of the request body, keyed by the webhook secret.
+pub(crate) const SIGNATURE_HEADER: &str = "X-Signature-256";
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Returns the signature of `body`, formatted as `sha256=<hex>`.
+pub(crate) fn sign(secret: &str, body: &str) -> String {
+	let mut engine = hmac::HmacEngine::<sha256::Hash>::new(secret.as_bytes());
+	engine.input(body.as_bytes());
+	format!("sha256={}", hmac::Hmac::<sha256::Hash>::from_engine(engine))
+}
+
+/// Delivers the events of each indexed block to a webhook.
+///
+/// Payloads are queued in the index outbox in the same write transaction as their block, and
+/// delivered in order, so notifications are not lost when the server restarts or the webhook is
+/// down. Delivery is at least once.
+pub(crate) struct Webhook {
+	client: reqwest::blocking::Client,
+	index: Arc<Index>,
+	secret: String,
+	url: Url,
+}
+
+impl Webhook {
+	/// Spawns a thread delivering the queued payloads to `url`, woken up by new `events`.
+	pub(crate) fn spawn(
+		index: Arc<Index>,
+		events: std_mpsc::Receiver<Event>,
+		url: &str,
+		secret: &str,
+	) -> Result {
+		let url = url.parse::<Url>().with_context(|| format!("invalid webhook url `{url}`"))?;
+		let secret = secret.to_string();
+
+		// the blocking client must be created outside of the server's async runtime
+		thread::spawn(move || {
+			match reqwest::blocking::Client::builder().timeout(Duration::from_secs(30)).build() {
+				Ok(client) => Self { client, index, secret, url }.deliver(events),
+				Err(error) => log::error!("Creating webhook client: {error}"),
+			}
+		});
+
+		Ok(())
+	}
+
+	fn deliver(&self, events: std_mpsc::Receiver<Event>) {
+		let mut backoff = INITIAL_BACKOFF;
+
+		while !SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+			let result = self.index.next_webhook_payload().and_then(|next| {
+				let Some((sequence_number, payload)) = next else {
+					return Ok(false);
+				};
+
+				self.post(&payload)?;
+
+				self.index.complete_webhook_delivery(sequence_number)?;
+
+				Ok(true)
+			});
+
+			match result {
+				Ok(true) => backoff = INITIAL_BACKOFF,
+				Ok(false) =>
+					if let Err(RecvTimeoutError::Disconnected) =
+						events.recv_timeout(Duration::from_secs(1))
+					{
+						break;
+					},
+				Err(error) => {
+					log::warn!(
+						"Delivering webhook payload failed, retrying in {}: {error}",
+						humantime::format_duration(backoff)
+					);
+					thread::sleep(backoff);
+					backoff = (backoff * 2).min(MAX_BACKOFF);
+				},
+			}
+		}
+	}
+
+	fn post(&self, payload: &str) -> Result {
+		self.client
+			.post(self.url.clone())
+			.header(header::CONTENT_TYPE, "application/json")
+			.header(SIGNATURE_HEADER, sign(&self.secret, payload))
+			.body(payload.to_string())
+			.send()?
+			.error_for_status()?;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn signature() {
+		// RFC 4231 test case 2
+		assert_eq!(
+			sign("Jefe", "what do ya want for nothing?"),
+			"sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+		);
+	}
+}
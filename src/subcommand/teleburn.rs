@@ -17,9 +17,17 @@
 use super::*;
 
 #[derive(Debug, Parser)]
+#[clap(
+group(
+  ArgGroup::new("source")
+    .required(true)
+    .args(&["destination", "brc721"]))
+)]
 pub(crate) struct Teleburn {
 	#[arg(help = "Generate teleburn addresses for inscription <DESTINATION>.")]
-	destination: InscriptionId,
+	destination: Option<InscriptionId>,
+	#[arg(long, help = "Generate teleburn addresses for brc721 token <COLLECTION_ID>:<TOKEN_ID>.")]
+	brc721: Option<Brc721TokenRef>,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -29,6 +37,12 @@ pub struct Output {
 
 impl Teleburn {
 	pub(crate) fn run(self) -> SubcommandResult {
-		Ok(Some(Box::new(Output { ethereum: self.destination.into() })))
+		let ethereum = match (self.destination, self.brc721) {
+			(Some(destination), _) => destination.into(),
+			(None, Some(token)) => (token.collection_id, token.token_id).into(),
+			(None, None) => unreachable!(),
+		};
+
+		Ok(Some(Box::new(Output { ethereum })))
 	}
 }
@@ -26,11 +26,26 @@ group(
 group(
   ArgGroup::new("signature")
     .required(true)
-    .args(&["transaction", "witness"]))
+    .args(&["transaction", "witness"])),
+group(
+  ArgGroup::new("signer")
+    .required(true)
+    .args(&["address", "brc721"]))
 )]
 pub(crate) struct Verify {
 	#[arg(long, help = "Verify signature made by <ADDRESS>.")]
-	address: Address<NetworkUnchecked>,
+	address: Option<Address<NetworkUnchecked>>,
+	#[arg(
+		long,
+		help = "Verify signature made by current owner of brc721 token <COLLECTION_ID>:<TOKEN_ID>."
+	)]
+	brc721: Option<Brc721TokenRef>,
+	#[arg(
+		long,
+		requires = "brc721",
+		help = "Look up brc721 token owner with ord running at <SERVER_URL>. [default: http://127.0.0.1:80]"
+	)]
+	server_url: Option<Url>,
 	#[arg(long, help = "Verify signature over <TEXT>.")]
 	text: Option<String>,
 	#[arg(long, help = "Verify signature over contents of <FILE>.")]
@@ -42,7 +57,13 @@ pub(crate) struct Verify {
 }
 
 impl Verify {
-	pub(crate) fn run(self) -> SubcommandResult {
+	pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+		let address = match (self.address, self.brc721) {
+			(Some(address), _) => address.require_network(settings.chain().network())?,
+			(None, Some(token)) => Self::brc721_owner(&settings, self.server_url, token)?,
+			(None, None) => unreachable!(),
+		};
+
 		let message = if let Some(text) = &self.text {
 			text.as_bytes()
 		} else if let Some(file) = &self.file {
@@ -54,16 +75,53 @@ impl Verify {
 		if let Some(witness) = self.witness {
 			let mut cursor = bitcoin::io::Cursor::new(general_purpose::STANDARD.decode(witness)?);
 			let witness = Witness::consensus_decode_from_finite_reader(&mut cursor)?;
-			bip322::verify_simple(&self.address.assume_checked(), message, witness)?;
+			bip322::verify_simple(&address, message, witness)?;
 		} else if let Some(transaction) = self.transaction {
 			let mut cursor =
 				bitcoin::io::Cursor::new(general_purpose::STANDARD.decode(transaction)?);
 			let transaction = Transaction::consensus_decode_from_finite_reader(&mut cursor)?;
-			bip322::verify_full(&self.address.assume_checked(), message, transaction)?;
+			bip322::verify_full(&address, message, transaction)?;
 		} else {
 			unreachable!();
 		}
 
 		Ok(None)
 	}
+
+	fn brc721_owner(
+		settings: &Settings,
+		server_url: Option<Url>,
+		token: Brc721TokenRef,
+	) -> Result<Address> {
+		let server_url = match server_url {
+			Some(server_url) => server_url,
+			None => settings
+				.server_url()
+				.unwrap_or("http://127.0.0.1:80")
+				.parse::<Url>()
+				.context("invalid server URL")?,
+		};
+
+		let mut request = reqwest::blocking::Client::new()
+			.get(
+				server_url
+					.join(&format!("/brc721/token/{}/{}", token.collection_id, token.token_id))?,
+			)
+			.header(reqwest::header::ACCEPT, "application/json");
+
+		if let Some((username, password)) = settings.credentials() {
+			request = request.basic_auth(username, Some(password));
+		}
+
+		let response = request.send()?;
+
+		ensure!(response.status() != StatusCode::NOT_FOUND, "brc721 token {token} not found");
+
+		let token_info = response.error_for_status()?.json::<api::Brc721Token>()?;
+
+		Ok(token_info
+			.address
+			.ok_or_else(|| anyhow!("brc721 token {token} is held by output without address"))?
+			.require_network(settings.chain().network())?)
+	}
 }
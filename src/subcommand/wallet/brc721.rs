@@ -17,6 +17,8 @@
 use super::*;
 use crate::wallet::Wallet;
 
+pub mod burn_token;
+pub mod rebase_token;
 pub mod register_collection;
 pub mod register_ownership;
 
@@ -33,6 +35,10 @@ pub(crate) enum Subcommand {
 	RegisterCollection(register_collection::RegisterCollectionCmd),
 	#[command(about = "Register Ownership", visible_alias = "ro")]
 	RegisterOwnership(register_ownership::RegisterOwnershipCmd),
+	#[command(about = "Rebase Token", visible_alias = "rt")]
+	RebaseToken(rebase_token::RebaseTokenCmd),
+	#[command(about = "Burn Token", visible_alias = "burn")]
+	BurnToken(burn_token::BurnTokenCmd),
 }
 
 impl Brc721Command {
@@ -40,6 +46,8 @@ impl Brc721Command {
 		match self.subcommand {
 			Subcommand::RegisterCollection(register) => register.run(wallet),
 			Subcommand::RegisterOwnership(cmd) => cmd.run(wallet),
+			Subcommand::RebaseToken(cmd) => cmd.run(wallet),
+			Subcommand::BurnToken(cmd) => cmd.run(wallet),
 		}
 	}
 }
@@ -0,0 +1,83 @@
+// Copyright 2023-2024 Freeverse.io
+// This file is part of LAOS.
+
+// LAOS is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// LAOS is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::wallet::calculate_postage;
+use ordinals::brc721::burn_token::BurnToken;
+
+#[derive(Debug, Parser)]
+pub(crate) struct BurnTokenCmd {
+	#[arg(long, help = "Burn token of collection <COLLECTION_ID>.")]
+	collection_id: Brc721CollectionId,
+	#[arg(long, help = "Burn token <TOKEN_ID>.")]
+	token_id: Brc721TokenId,
+	#[clap(long, help = "Use <FEE_RATE> sats/vbyte for burn token transaction.")]
+	fee_rate: FeeRate,
+	#[clap(
+		long,
+		help = "Include <AMOUNT> postage with output holding the other tokens of the burned token output. [default: 10000sat]"
+	)]
+	postage: Option<Amount>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Output {
+	pub tx_id: Txid,
+}
+
+impl BurnTokenCmd {
+	pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+		ensure!(
+			wallet.has_brc721_index(),
+			"burning brc721 tokens with `laos-btc wallet brc721 burn` requires index created with `--index-brc721` flag",
+		);
+
+		let token =
+			wallet.get_brc721_token(self.collection_id, self.token_id)?.ok_or_else(|| {
+				anyhow!("token {} not found in collection {}", self.token_id, self.collection_id)
+			})?;
+
+		if let Some(height) = token.burned {
+			bail!(
+				"token {} of collection {} was already burned at height {height}",
+				self.token_id,
+				self.collection_id
+			);
+		}
+
+		let output = token.output.ok_or_else(|| {
+			anyhow!("token {} of collection {} has no output", self.token_id, self.collection_id)
+		})?;
+
+		ensure!(
+			wallet.utxos().contains_key(&output),
+			"token {} of collection {} is not owned by wallet",
+			self.token_id,
+			self.collection_id
+		);
+
+		let postage = calculate_postage(self.postage, wallet.get_change_address()?)?;
+
+		let burn_token = BurnToken { collection_id: self.collection_id, token_id: self.token_id };
+
+		let bitcoin_tx =
+			wallet.build_brc721_token_tx(burn_token, output, self.fee_rate, postage)?;
+
+		let tx_id = wallet.bitcoin_client().send_raw_transaction(&bitcoin_tx)?;
+
+		Ok(Some(Box::new(Output { tx_id })))
+	}
+}
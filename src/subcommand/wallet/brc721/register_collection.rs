@@ -40,6 +40,13 @@ pub(crate) struct RegisterCollectionCmd {
 		help = "Include <AMOUNT> postage with register collection output. [default: 10000sat]"
 	)]
 	postage: Option<Amount>,
+	#[arg(long, help = "Send register collection postage to <DESTINATION>.")]
+	destination: Option<Address<NetworkUnchecked>>,
+	#[arg(
+		long,
+		help = "Register <COLLECTION_ADDRESS> even if the configured LAOS node does not report it as a collection."
+	)]
+	force: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -49,7 +56,17 @@ pub struct Output {
 
 impl RegisterCollectionCmd {
 	pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
-		let destination = wallet.get_change_address()?;
+		let destination = match self.destination {
+			Some(destination) => destination.require_network(wallet.chain().network())?,
+			None => wallet.get_change_address()?,
+		};
+
+		if let Some(laos_client) = wallet.laos_client()? {
+			if let Err(err) = laos_client.check_collection(self.address) {
+				ensure!(self.force, "{err:#}, use --force to register it anyway");
+				eprintln!("warning: {err:#}");
+			}
+		}
 
 		let postage = calculate_postage(self.postage, destination)?;
 
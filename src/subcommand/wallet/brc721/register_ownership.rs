@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::wallet::calculate_postage;
+use crate::wallet::{calculate_postage, Postage};
 
 use super::*;
 use ordinals::brc721::register_ownership::{RegisterOwnership, SlotsBundle};
@@ -32,9 +32,15 @@ pub(crate) struct RegisterOwnershipCmd {
 	fee_rate: FeeRate,
 	#[clap(
 		long,
-		help = "Include <AMOUNT> postage with register collection output. [default: 10000sat]"
+		help = "Include <AMOUNT> postage with each recipient output without `postage` in <OWNERSHIP_FILE>. [default: 10000sat]"
 	)]
 	postage: Option<Amount>,
+	#[arg(
+		long = "utxo",
+		help = "Spend initial owner <UTXO>. May be given multiple times. [default: largest cardinal UTXO of the initial owner]",
+		value_name = "UTXO"
+	)]
+	utxos: Vec<OutPoint>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -47,7 +53,7 @@ impl RegisterOwnershipCmd {
 		let file = File::load(&self.file)?;
 
 		let mut slots_bundles = Vec::<SlotsBundle>::new();
-		let mut recipients = Vec::<Address>::new();
+		let mut recipients = Vec::<Postage>::new();
 
 		let initial_owner = file.initial_owner.clone().require_network(wallet.chain().into())?;
 
@@ -57,11 +63,10 @@ impl RegisterOwnershipCmd {
 				Some(recipient) => recipient.clone().require_network(wallet.chain().into())?,
 				None => initial_owner.clone(),
 			};
-			recipients.push(recipient);
+			let postage = output.postage.or(self.postage);
+			recipients.push(calculate_postage(postage, recipient)?);
 		}
 
-		let postage = calculate_postage(self.postage, wallet.get_change_address()?)?;
-
 		let register_ownership =
 			RegisterOwnership { collection_id: file.collection_id, slots_bundles };
 
@@ -69,8 +74,8 @@ impl RegisterOwnershipCmd {
 			register_ownership,
 			recipients,
 			initial_owner,
+			self.utxos,
 			self.fee_rate,
-			postage,
 		)?;
 
 		let tx_id = wallet.bitcoin_client().send_raw_transaction(&bitcoin_tx)?;
@@ -94,6 +99,8 @@ pub struct SlotsOwnership {
 	slots_bundle: SlotsBundle,
 	#[serde(default, deserialize_with = "deserialize_recipient")]
 	recipient: Option<Address<NetworkUnchecked>>,
+	#[serde(default, deserialize_with = "deserialize_postage")]
+	postage: Option<Amount>,
 }
 
 impl File {
@@ -117,6 +124,32 @@ impl File {
 				));
 			}
 		}
+
+		// Check overlapping ranges across outputs. Ranges of the same output are known to be
+		// disjoint at this point, so any overlapping pair belongs to different outputs.
+		let mut ranges = file
+			.outputs
+			.iter()
+			.enumerate()
+			.flat_map(|(index, output)| {
+				output.slots_bundle.0.iter().map(move |range| (index, range))
+			})
+			.collect::<Vec<_>>();
+		ranges.sort_by_key(|(_, range)| *range.start());
+
+		if let Some(pair) = ranges.windows(2).find(|pair| ranges_overlap(pair[0].1, pair[1].1)) {
+			let ((first, first_range), (second, second_range)) =
+				if pair[0].0 < pair[1].0 { (pair[0], pair[1]) } else { (pair[1], pair[0]) };
+
+			return Err(anyhow::anyhow!(
+				"overlapping ranges detected between output {} and output {}: {:?} and {:?}",
+				first,
+				second,
+				first_range,
+				second_range
+			));
+		}
+
 		Ok(file)
 	}
 }
@@ -184,6 +217,16 @@ where
 	}
 }
 
+fn deserialize_postage<'de, D>(deserializer: D) -> Result<Option<Amount>, D::Error>
+where
+	D: Deserializer<'de>,
+{
+	match Option::<String>::deserialize(deserializer)? {
+		Some(postage) => postage.parse::<Amount>().map(Some).map_err(D::Error::custom),
+		None => Ok(None),
+	}
+}
+
 fn deserialize_initial_owner<'de, D>(deserializer: D) -> Result<Address<NetworkUnchecked>, D::Error>
 where
 	D: Deserializer<'de>,
@@ -307,6 +350,29 @@ outputs:
 		);
 	}
 
+	#[test]
+	fn load_file_overlapping_slots_across_outputs() {
+		let tempdir = TempDir::new().unwrap();
+		let batch_file = tempdir.path().join("temp.yaml");
+		fs::write(
+			batch_file.clone(),
+			r#"
+collection_id: 1:1
+initial_owner: 1BitcoinEaterAddressDontSendf59kuE
+outputs:
+  - slots_bundle: [[0,9],[20,30]]
+  - slots_bundle: [[10,19]]
+  - slots_bundle: [[31],[5]]
+"#,
+		)
+		.unwrap();
+
+		assert_eq!(
+			File::load(batch_file.as_path()).unwrap_err().to_string(),
+			"overlapping ranges detected between output 0 and output 2: 0..=9 and 5..=5"
+		);
+	}
+
 	#[test]
 	fn load_file_slot_bundle_one_element_no_recipient() {
 		let tempdir = TempDir::new().unwrap();
@@ -400,7 +466,7 @@ initial_owner: 1BitcoinEaterAddressDontSendf59kuE
 outputs:
   - slots_bundle: [[0]]
     recipient: 1BitcoinEaterAddressDontSendf59kuE
-  - slots_bundle: [[0],[2], [4,6]]
+  - slots_bundle: [[1],[2], [4,6]]
     recipient: 1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa
 "#,
 		)
@@ -425,8 +491,8 @@ outputs:
 		// OUTPUT 1
 		assert_eq!(file.outputs[1].slots_bundle.0.len(), 3);
 		let bundle0 = &file.outputs[1].slots_bundle.0[0];
-		assert_eq!(*bundle0.start(), 0);
-		assert_eq!(*bundle0.end(), 0);
+		assert_eq!(*bundle0.start(), 1);
+		assert_eq!(*bundle0.end(), 1);
 		let bundle1 = &file.outputs[1].slots_bundle.0[1];
 		assert_eq!(*bundle1.start(), 2);
 		assert_eq!(*bundle1.end(), 2);
@@ -434,4 +500,45 @@ outputs:
 		assert_eq!(*bundle3.start(), 4);
 		assert_eq!(*bundle3.end(), 6);
 	}
+
+	#[test]
+	fn load_file_postage_per_output() {
+		let tempdir = TempDir::new().unwrap();
+		let batch_file = tempdir.path().join("temp.yaml");
+		fs::write(
+			batch_file.clone(),
+			r#"
+collection_id: 1:1
+initial_owner: 1BitcoinEaterAddressDontSendf59kuE
+outputs:
+  - slots_bundle: [[0]]
+    postage: 50000 sat
+  - slots_bundle: [[1]]
+"#,
+		)
+		.unwrap();
+
+		let file = File::load(batch_file.as_path()).unwrap();
+		assert_eq!(file.outputs[0].postage, Some(Amount::from_sat(50000)));
+		assert_eq!(file.outputs[1].postage, None);
+	}
+
+	#[test]
+	fn load_file_postage_without_denomination() {
+		let tempdir = TempDir::new().unwrap();
+		let batch_file = tempdir.path().join("temp.yaml");
+		fs::write(
+			batch_file.clone(),
+			r#"
+collection_id: 1:1
+initial_owner: 1BitcoinEaterAddressDontSendf59kuE
+outputs:
+  - slots_bundle: [[0]]
+    postage: 50000
+"#,
+		)
+		.unwrap();
+
+		assert!(File::load(batch_file.as_path()).is_err());
+	}
 }
@@ -36,7 +36,10 @@ pub(crate) fn run(wallet: Wallet) -> SubcommandResult {
 	let cardinal_utxos = unspent_outputs
 		.iter()
 		.filter_map(|(output, txout)| {
-			if inscribed_utxos.contains(output) || runic_utxos.contains(output) {
+			if inscribed_utxos.contains(output) ||
+				runic_utxos.contains(output) ||
+				wallet.brc721_outputs().contains(output)
+			{
 				None
 			} else {
 				Some(CardinalUtxo { output: *output, amount: txout.value.to_sat() })
@@ -39,7 +39,22 @@ struct Line {
 pub(crate) fn run(wallet: Wallet) -> SubcommandResult {
 	let mut lines: Vec<Line> = Vec::new();
 
-	let sat_ranges = wallet.get_wallet_sat_ranges()?;
+	let brc721_collection_outputs = wallet.get_brc721_collection_outputs()?;
+
+	for (output, collection_id) in &brc721_collection_outputs {
+		lines.push(Line {
+			label: format!("brc721 collection {collection_id}"),
+			r#ref: output.to_string(),
+			r#type: "output".into(),
+		});
+	}
+
+	// A wallet with a brc721 index can still export its collection labels without sat ranges.
+	let sat_ranges = if wallet.has_sat_index() || !wallet.has_brc721_index() {
+		wallet.get_wallet_sat_ranges()?
+	} else {
+		Vec::new()
+	};
 
 	let mut inscriptions_by_output: BTreeMap<OutPoint, BTreeMap<u64, Vec<InscriptionId>>> =
 		BTreeMap::new();
@@ -52,6 +67,10 @@ pub(crate) fn run(wallet: Wallet) -> SubcommandResult {
 	}
 
 	for (output, ranges) in sat_ranges {
+		if brc721_collection_outputs.contains_key(&output) {
+			continue;
+		}
+
 		let sat = Sat(ranges[0].0);
 		let mut inscriptions = BTreeMap::<u64, BTreeSet<InscriptionId>>::new();
 
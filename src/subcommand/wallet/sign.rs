@@ -28,11 +28,20 @@ pub struct Output {
 group(
   ArgGroup::new("input")
     .required(true)
-    .args(&["text", "file"])))
-]
+    .args(&["text", "file"])),
+group(
+  ArgGroup::new("key")
+    .required(true)
+    .args(&["signer", "brc721"]))
+)]
 pub(crate) struct Sign {
 	#[arg(long, help = "Sign with public key associated with address, output, or inscription.")]
-	signer: Signer,
+	signer: Option<Signer>,
+	#[arg(
+		long,
+		help = "Sign with public key of the output holding brc721 token <COLLECTION_ID>:<TOKEN_ID>."
+	)]
+	brc721: Option<Brc721TokenRef>,
 	#[arg(long, help = "Sign <TEXT>.")]
 	text: Option<String>,
 	#[arg(long, help = "Sign contents of <FILE>.")]
@@ -42,9 +51,9 @@ pub(crate) struct Sign {
 impl Sign {
 	pub(crate) fn run(&self, wallet: Wallet) -> SubcommandResult {
 		let address = match &self.signer {
-			Signer::Address(address) =>
+			Some(Signer::Address(address)) =>
 				address.clone().require_network(wallet.chain().network())?,
-			Signer::Inscription(inscription) => Address::from_str(
+			Some(Signer::Inscription(inscription)) => Address::from_str(
 				&wallet
 					.inscription_info()
 					.get(inscription)
@@ -56,13 +65,35 @@ impl Sign {
 					})?,
 			)?
 			.require_network(wallet.chain().network())?,
-			Signer::Output(output) => wallet.chain().address_from_script(
+			Some(Signer::Output(output)) => wallet.chain().address_from_script(
 				&wallet
 					.utxos()
 					.get(output)
 					.ok_or_else(|| anyhow!("output {output} has no address"))?
 					.script_pubkey,
 			)?,
+			None => {
+				let token = self.brc721.unwrap();
+
+				ensure!(
+					wallet.has_brc721_index(),
+					"signing with brc721 tokens requires index created with `--index-brc721` flag",
+				);
+
+				let output = wallet
+					.get_brc721_token(token.collection_id, token.token_id)?
+					.ok_or_else(|| anyhow!("brc721 token {token} not found"))?
+					.output
+					.ok_or_else(|| anyhow!("brc721 token {token} was burned"))?;
+
+				wallet.chain().address_from_script(
+					&wallet
+						.utxos()
+						.get(&output)
+						.ok_or_else(|| anyhow!("brc721 token {token} not owned by wallet"))?
+						.script_pubkey,
+				)?
+			},
 		};
 
 		let message = if let Some(text) = &self.text {
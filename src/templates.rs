@@ -20,6 +20,7 @@ use boilerplate::Boilerplate;
 pub(crate) use crate::subcommand::server::ServerConfig;
 pub(crate) use address::AddressHtml;
 pub(crate) use block::BlockHtml;
+pub(crate) use brc721_token::Brc721TokenHtml;
 pub(crate) use children::ChildrenHtml;
 pub(crate) use clock::ClockSvg;
 pub(crate) use collections::CollectionsHtml;
@@ -51,6 +52,7 @@ pub mod address;
 pub mod block;
 pub mod blocks;
 pub mod brc721_collections;
+mod brc721_token;
 mod children;
 mod clock;
 pub mod collections;
@@ -0,0 +1,103 @@
+// Copyright 2023-2024 Freeverse.io
+// This file is part of LAOS.
+
+// LAOS is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// LAOS is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct Brc721TokenHtml {
+	pub(crate) address: Option<Address>,
+	pub(crate) burned: Option<u32>,
+	pub(crate) collection_id: Brc721CollectionId,
+	pub(crate) output: Option<OutPoint>,
+	pub(crate) token_id: Brc721TokenId,
+	pub(crate) token_uri: Option<String>,
+}
+
+impl PageContent for Brc721TokenHtml {
+	fn title(&self) -> String {
+		format!("Brc721 Token {}", self.token_id)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::H160;
+
+	fn token_id() -> Brc721TokenId {
+		Brc721TokenId { slot: 2, initial_owner: H160::repeat_byte(0x2a) }
+	}
+
+	#[test]
+	fn owned() {
+		assert_regex_match!(
+			Brc721TokenHtml {
+				address: Some(address(0)),
+				burned: None,
+				collection_id: Brc721CollectionId { block: 2, tx: 1 },
+				output: Some(outpoint(1)),
+				token_id: token_id(),
+				token_uri: Some("ipfs://foo".into()),
+			},
+			"
+				<h1>Brc721 Token \\d+</h1>
+				<dl>
+				  <dt>collection</dt>
+				  <dd><a href=/brc721/collection/2:1>2:1</a></dd>
+				  <dt>initial owner</dt>
+				  <dd class=monospace>0x(2a){20}</dd>
+				  <dt>slot</dt>
+				  <dd>2</dd>
+				  <dt>output</dt>
+				  <dd><a class=collapse href=/output/1{64}:1>1{64}:1</a></dd>
+				  <dt>address</dt>
+				  <dd><a class=collapse href=/address/bc1.*>bc1.*</a></dd>
+				  <dt>token uri</dt>
+				  <dd>ipfs://foo</dd>
+				</dl>
+			"
+			.unindent()
+		);
+	}
+
+	#[test]
+	fn burned() {
+		assert_regex_match!(
+			Brc721TokenHtml {
+				address: None,
+				burned: Some(7),
+				collection_id: Brc721CollectionId { block: 2, tx: 1 },
+				output: None,
+				token_id: token_id(),
+				token_uri: None,
+			},
+			"
+				<h1>Brc721 Token \\d+</h1>
+				<dl>
+				  <dt>collection</dt>
+				  <dd><a href=/brc721/collection/2:1>2:1</a></dd>
+				  <dt>initial owner</dt>
+				  <dd class=monospace>0x(2a){20}</dd>
+				  <dt>slot</dt>
+				  <dd>2</dd>
+				  <dt>burned</dt>
+				  <dd>burned at height <a href=/block/7>7</a></dd>
+				</dl>
+			"
+			.unindent()
+		);
+	}
+}
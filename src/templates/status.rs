@@ -33,6 +33,7 @@ pub struct StatusHtml {
 	pub rune_index: bool,
 	pub runes: u64,
 	pub sat_index: bool,
+	pub snapshot_recovery: Option<u32>,
 	pub started: DateTime<Utc>,
 	pub transaction_index: bool,
 	pub unrecoverably_reorged: bool,
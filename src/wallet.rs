@@ -88,6 +88,7 @@ pub(crate) enum Maturity {
 
 pub(crate) struct Wallet {
 	bitcoin_client: Client,
+	brc721_outputs: BTreeSet<OutPoint>,
 	database: Database,
 	has_brc721_index: bool,
 	has_rune_index: bool,
@@ -184,6 +185,7 @@ impl Wallet {
 			.keys()
 			.filter(|utxo| inscriptions.contains(utxo))
 			.chain(self.get_runic_outputs()?.unwrap_or_default().iter())
+			.chain(self.brc721_outputs.iter())
 			.cloned()
 			.filter(|utxo| !locked.contains(utxo))
 			.collect::<Vec<OutPoint>>();
@@ -195,6 +197,11 @@ impl Wallet {
 		Ok(())
 	}
 
+	/// Returns the wallet outputs holding brc721 tokens.
+	pub(crate) fn brc721_outputs(&self) -> &BTreeSet<OutPoint> {
+		&self.brc721_outputs
+	}
+
 	pub(crate) fn inscriptions(&self) -> &BTreeMap<SatPoint, Vec<InscriptionId>> {
 		&self.inscriptions
 	}
@@ -300,6 +307,94 @@ impl Wallet {
 		Ok(Some((rune_json.id, rune_json.entry, rune_json.parent)))
 	}
 
+	pub(crate) fn get_brc721_registered_slots(
+		&self,
+		collection_id: Brc721CollectionId,
+		initial_owner: &Address,
+	) -> Result<Vec<std::ops::RangeInclusive<u128>>> {
+		let response = self
+			.ord_client
+			.get(
+				self.rpc_url
+					.join(&format!("/brc721/collection/{collection_id}/registered/{initial_owner}"))
+					.unwrap(),
+			)
+			.send()?;
+
+		if !response.status().is_success() {
+			bail!("wallet failed to get registered brc721 slots: {}", response.text()?);
+		}
+
+		let registered_slots: api::Brc721RegisteredSlots = serde_json::from_str(&response.text()?)?;
+
+		Ok(registered_slots.slots)
+	}
+
+	pub(crate) fn get_brc721_collection(
+		&self,
+		collection_id: Brc721CollectionId,
+	) -> Result<Option<Brc721Collection>> {
+		let response = self
+			.ord_client
+			.get(self.rpc_url.join(&format!("/brc721/collection/{collection_id}")).unwrap())
+			.send()?;
+
+		if response.status() == StatusCode::NOT_FOUND {
+			return Ok(None);
+		}
+
+		let response = response.error_for_status()?;
+
+		Ok(Some(serde_json::from_str(&response.text()?)?))
+	}
+
+	pub(crate) fn get_brc721_token(
+		&self,
+		collection_id: Brc721CollectionId,
+		token_id: Brc721TokenId,
+	) -> Result<Option<api::Brc721Token>> {
+		let response = self
+			.ord_client
+			.get(self.rpc_url.join(&format!("/brc721/token/{collection_id}/{token_id}")).unwrap())
+			.send()?;
+
+		if response.status() == StatusCode::NOT_FOUND {
+			return Ok(None);
+		}
+
+		let response = response.error_for_status()?;
+
+		Ok(Some(serde_json::from_str(&response.text()?)?))
+	}
+
+	/// Returns the wallet outputs holding the postage of a confirmed brc721 collection
+	/// registration, along with the id of the registered collection.
+	pub(crate) fn get_brc721_collection_outputs(
+		&self,
+	) -> Result<BTreeMap<OutPoint, Brc721CollectionId>> {
+		if !self.has_brc721_index {
+			return Ok(BTreeMap::new());
+		}
+
+		let response = self
+			.ord_client
+			.post(self.rpc_url.join("/brc721/collection-outputs").unwrap())
+			.json(&self.utxos.keys().collect::<Vec<_>>())
+			.send()?;
+
+		if !response.status().is_success() {
+			bail!("wallet failed to get brc721 collection outputs: {}", response.text()?);
+		}
+
+		let collection_outputs: Vec<api::Brc721CollectionOutput> =
+			serde_json::from_str(&response.text()?)?;
+
+		Ok(collection_outputs
+			.into_iter()
+			.map(|collection_output| (collection_output.output, collection_output.collection_id))
+			.collect())
+	}
+
 	pub(crate) fn get_change_address(&self) -> Result<Address> {
 		Ok(self
 			.bitcoin_client
@@ -335,6 +430,10 @@ impl Wallet {
 		self.settings.integration_test()
 	}
 
+	pub(crate) fn laos_client(&self) -> Result<Option<LaosClient>> {
+		self.settings.laos_client()
+	}
+
 	fn is_above_minimum_at_height(&self, rune: Rune) -> Result<bool> {
 		Ok(rune >=
 			Rune::minimum_at_height(
@@ -842,13 +941,21 @@ impl Wallet {
 			}
 		}
 
-		let runic_outputs = self.get_runic_outputs()?.unwrap_or_default();
+		let mut runic_outputs = self.get_runic_outputs()?.unwrap_or_default();
 
 		ensure!(
 			!runic_outputs.contains(&satpoint.outpoint),
 			"runic outpoints may not be sent by satpoint"
 		);
 
+		ensure!(
+			!self.brc721_outputs.contains(&satpoint.outpoint),
+			"brc721 token outpoints may not be sent by satpoint"
+		);
+
+		// outputs holding brc721 tokens are never used to pay for the transaction
+		runic_outputs.extend(&self.brc721_outputs);
+
 		let change = [self.get_change_address()?, self.get_change_address()?];
 
 		let postage = if let Some(postage) = postage {
@@ -1066,13 +1173,50 @@ impl Wallet {
 		Ok(signed_transaction)
 	}
 
+	pub(crate) fn build_brc721_token_tx<T: Into<ScriptBuf>>(
+		&self,
+		tx: T,
+		token_output: OutPoint,
+		fee_rate: FeeRate,
+		postage: Postage,
+	) -> Result<Transaction> {
+		self.lock_non_cardinal_outputs()?;
+
+		// Spending the output holding the token proves the operation is sent by its owner.
+		let unfunded_tx = Transaction {
+			version: Version(2),
+			lock_time: LockTime::ZERO,
+			input: vec![TxIn {
+				previous_output: token_output,
+				script_sig: ScriptBuf::new(),
+				sequence: Sequence::MAX,
+				witness: Witness::new(),
+			}],
+			output: vec![
+				TxOut { value: Amount::from_sat(0), script_pubkey: tx.into() },
+				TxOut { value: postage.amount, script_pubkey: postage.destination.script_pubkey() },
+			],
+		};
+
+		let unsigned_transaction =
+			fund_raw_transaction(self.bitcoin_client(), fee_rate, &unfunded_tx)?;
+
+		let signed_transaction = self
+			.bitcoin_client()
+			.sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
+			.hex;
+		let signed_transaction = consensus::encode::deserialize(&signed_transaction)?;
+
+		Ok(signed_transaction)
+	}
+
 	pub(crate) fn build_brc721_register_ownership_tx(
 		&self,
 		tx: RegisterOwnership,
-		recipients: Vec<Address>,
+		recipients: Vec<Postage>,
 		initial_owner: Address,
+		utxos: Vec<OutPoint>,
 		fee_rate: FeeRate,
-		postage: Postage,
 	) -> Result<Transaction> {
 		ensure!(
 			self.has_brc721_index(),
@@ -1087,15 +1231,54 @@ impl Wallet {
 			initial_owner
 		);
 
+		let registered_slots =
+			self.get_brc721_registered_slots(tx.collection_id, &initial_owner)?;
+
+		for (index, slots_bundle) in tx.slots_bundles.iter().enumerate() {
+			for range in &slots_bundle.0 {
+				if let Some(registered) = registered_slots.iter().find(|registered| {
+					range.start() <= registered.end() && registered.start() <= range.end()
+				}) {
+					bail!(
+						"slots {range:?} in output {index} conflict with slots {registered:?} already registered by initial owner {initial_owner} in collection {}",
+						tx.collection_id
+					);
+				}
+			}
+		}
+
 		let cardinal_utxos = self.get_cardinal_utxos(initial_owner.clone())?;
 		if cardinal_utxos.is_empty() {
 			return Err(anyhow!("No available UTXOs found for address {}", initial_owner));
 		}
 
+		// The first input identifies the initial owner, so a single UTXO of the initial owner is
+		// enough. Any additional input needed to pay for outputs and fees is selected by
+		// `fund_raw_transaction`.
+		let inputs = if utxos.is_empty() {
+			cardinal_utxos
+				.into_iter()
+				.max_by_key(|outpoint| self.utxos[outpoint].value)
+				.into_iter()
+				.collect::<Vec<OutPoint>>()
+		} else {
+			let mut chosen = HashSet::new();
+
+			for utxo in &utxos {
+				ensure!(chosen.insert(utxo), "utxo {utxo} given more than once");
+
+				ensure!(
+					cardinal_utxos.contains(utxo),
+					"utxo {utxo} is not a cardinal output of initial owner {initial_owner}"
+				);
+			}
+			utxos
+		};
+
 		let unfunded_tx = Transaction {
 			version: Version(2),
 			lock_time: LockTime::ZERO,
-			input: cardinal_utxos
+			input: inputs
 				.into_iter()
 				.map(|outpoint| TxIn {
 					previous_output: outpoint,
@@ -1108,8 +1291,8 @@ impl Wallet {
 				let mut output =
 					vec![TxOut { value: Amount::from_sat(0), script_pubkey: tx.clone().into() }];
 				output.extend(recipients.iter().map(|recipient| TxOut {
-					value: postage.amount,
-					script_pubkey: recipient.script_pubkey(),
+					value: recipient.amount,
+					script_pubkey: recipient.destination.script_pubkey(),
 				}));
 				output
 			},
@@ -1146,7 +1329,9 @@ impl Wallet {
 					.unwrap_or(false)
 			})
 			.filter(|(output, _)| {
-				!inscribed_utxos.contains(output) && !runic_utxos.contains(output)
+				!inscribed_utxos.contains(output) &&
+					!runic_utxos.contains(output) &&
+					!self.brc721_outputs.contains(output)
 			})
 			.map(|(output, _)| *output)
 			.collect::<Vec<_>>();
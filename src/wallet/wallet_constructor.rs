@@ -141,8 +141,15 @@ impl WalletConstructor {
 
 		let status = self.get_server_status()?;
 
+		let brc721_outputs = if status.brc721_index {
+			self.get_brc721_outputs(utxos.clone().into_keys().collect())?
+		} else {
+			BTreeSet::new()
+		};
+
 		Ok(Wallet {
 			bitcoin_client,
+			brc721_outputs,
 			database,
 			has_brc721_index: status.brc721_index,
 			has_rune_index: status.rune_index,
@@ -179,6 +186,19 @@ impl WalletConstructor {
 		Ok(output_info)
 	}
 
+	fn get_brc721_outputs(&self, outputs: Vec<OutPoint>) -> Result<BTreeSet<OutPoint>> {
+		let response = self.post("/brc721/outputs", &outputs)?;
+
+		if !response.status().is_success() {
+			bail!("wallet failed get brc721 outputs: {}", response.text()?);
+		}
+
+		Ok(serde_json::from_str::<Vec<api::Brc721TokenRange>>(&response.text()?)?
+			.into_iter()
+			.map(|range| range.output)
+			.collect())
+	}
+
 	fn get_inscriptions(
 		&self,
 		inscriptions: &Vec<InscriptionId>,
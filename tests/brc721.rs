@@ -0,0 +1,386 @@
+// Copyright 2023-2024 Freeverse.io
+// This file is part of LAOS.
+
+// LAOS is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// LAOS is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use bitcoin::ScriptBuf;
+use mockcore::brc721_tx;
+use ord::{
+	api::{Brc721InitialOwner, Brc721OwnerTokens, Brc721RegisteredSlots, Brc721TokenRange},
+	subcommand::brc721::audit::Output,
+};
+use ordinals::{
+	brc721::{
+		address_mapping,
+		register_ownership::{RegisterOwnership, SlotsBundle},
+	},
+	Brc721TokenId,
+};
+use sp_core::H160;
+
+#[test]
+fn audit_requires_laos_rpc_url() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+
+	CommandBuilder::new("--regtest --index-brc721 brc721 audit 1:1")
+		.core(&core)
+		.expected_exit_code(1)
+		.expected_stderr("error: audit requires a LAOS RPC endpoint set with `--laos-rpc-url`\n")
+		.run_and_extract_stdout();
+}
+
+#[test]
+fn audit_requires_brc721_index() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let laos = mocklaos::spawn();
+
+	CommandBuilder::new(format!("--regtest --laos-rpc-url {} brc721 audit 1:1", laos.url()))
+		.core(&core)
+		.expected_exit_code(1)
+		.expected_stderr("error: audit requires index created with `--index-brc721` flag\n")
+		.run_and_extract_stdout();
+}
+
+#[test]
+fn audit_unknown_collection() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let laos = mocklaos::spawn();
+
+	CommandBuilder::new(format!(
+		"--regtest --index-brc721 --laos-rpc-url {} brc721 audit 1:1",
+		laos.url()
+	))
+	.core(&core)
+	.expected_exit_code(1)
+	.expected_stderr("error: collection 1:1 not found\n")
+	.run_and_extract_stdout();
+}
+
+/// Registers a collection pointing to a new LAOS collection and slots 0 to 3 of the owner of a
+/// coinbase output, returning the LAOS collection, the initial owner and the output holding the
+/// slots.
+fn register_audited_collection(
+	core: &mockcore::Handle,
+	laos: &mocklaos::Handle,
+) -> (H160, H160, OutPoint) {
+	core.mine_blocks(2);
+
+	let collection = laos.create_collection(H160::repeat_byte(1));
+
+	core.broadcast_raw_tx(brc721_tx(
+		&[core.outpoint(1, 0, 0)],
+		RegisterCollection { address: collection, rebaseable: false }.as_script(),
+		&[],
+	));
+
+	core.mine_blocks(1);
+
+	let funding = core.outpoint(2, 0, 0);
+
+	let initial_owner = address_mapping::btc_address_to_h160(core.address(funding)).unwrap();
+
+	let registration = core.broadcast_raw_tx(brc721_tx(
+		&[funding],
+		ScriptBuf::from(RegisterOwnership {
+			collection_id: Brc721CollectionId { block: 3, tx: 1 },
+			slots_bundles: vec![SlotsBundle(vec![0..=3])],
+		}),
+		&[(&core.address(funding), 10_000)],
+	));
+
+	core.mine_blocks(1);
+
+	(collection, initial_owner, OutPoint { txid: registration, vout: 1 })
+}
+
+#[test]
+fn audit_reports_tokens_missing_on_either_chain() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let laos = mocklaos::spawn();
+
+	let (collection, initial_owner, output) = register_audited_collection(&core, &laos);
+
+	laos.mint(collection, initial_owner, 1, "ipfs://1");
+	laos.mint(collection, initial_owner, 7, "ipfs://7");
+
+	let collection_id = Brc721CollectionId { block: 3, tx: 1 };
+
+	pretty_assert_eq!(
+		CommandBuilder::new(format!(
+			"--regtest --index-brc721 --laos-rpc-url {} brc721 audit 3:1",
+			laos.url()
+		))
+		.core(&core)
+		.run_and_deserialize_output::<Output>(),
+		Output {
+			collection_id,
+			laos_address: collection,
+			without_laos_metadata: vec![
+				Brc721TokenRange { collection_id, initial_owner, output, slots: 0..=0 },
+				Brc721TokenRange { collection_id, initial_owner, output, slots: 2..=3 },
+			],
+			without_bitcoin_owner: vec![Brc721TokenId { slot: 7, initial_owner }],
+		}
+	);
+}
+
+#[test]
+fn audit_reads_logs_across_block_ranges() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let laos = mocklaos::builder().max_log_block_range(10).build();
+
+	let (collection, initial_owner, output) = register_audited_collection(&core, &laos);
+
+	laos.mint(collection, initial_owner, 1, "ipfs://1");
+	laos.mine_blocks(10);
+	laos.mint(collection, initial_owner, 2, "ipfs://2");
+	let token_id = laos.mint(collection, initial_owner, 3, "ipfs://3");
+	laos.mine_blocks(20);
+	laos.evolve(collection, token_id, "");
+
+	let collection_id = Brc721CollectionId { block: 3, tx: 1 };
+
+	let audit = CommandBuilder::new(format!(
+		"--regtest --index-brc721 --laos-rpc-url {} brc721 audit 3:1 --log-block-range 10",
+		laos.url()
+	))
+	.core(&core)
+	.run_and_deserialize_output::<Output>();
+
+	pretty_assert_eq!(
+		audit.without_laos_metadata,
+		vec![
+			Brc721TokenRange { collection_id, initial_owner, output, slots: 0..=0 },
+			Brc721TokenRange { collection_id, initial_owner, output, slots: 3..=3 },
+		],
+	);
+
+	pretty_assert_eq!(audit.without_bitcoin_owner, Vec::new());
+
+	CommandBuilder::new(format!(
+		"--regtest --index-brc721 --laos-rpc-url {} brc721 audit 3:1 --log-block-range 11",
+		laos.url()
+	))
+	.core(&core)
+	.expected_exit_code(1)
+	.stderr_regex("error: LAOS RPC error .*: block range is too wide \\(maximum 10\\)\n")
+	.run_and_extract_stdout();
+}
+
+#[test]
+fn audit_reads_logs_from_block() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let laos = mocklaos::spawn();
+
+	let (collection, initial_owner, output) = register_audited_collection(&core, &laos);
+
+	laos.mint(collection, initial_owner, 1, "ipfs://1");
+	laos.mint(collection, initial_owner, 2, "ipfs://2");
+
+	let collection_id = Brc721CollectionId { block: 3, tx: 1 };
+
+	pretty_assert_eq!(
+		CommandBuilder::new(format!(
+			"--regtest --index-brc721 --laos-rpc-url {} brc721 audit 3:1 --from-block 2",
+			laos.url()
+		))
+		.core(&core)
+		.run_and_deserialize_output::<Output>()
+		.without_laos_metadata,
+		vec![
+			Brc721TokenRange { collection_id, initial_owner, output, slots: 0..=1 },
+			Brc721TokenRange { collection_id, initial_owner, output, slots: 3..=3 },
+		],
+	);
+}
+
+#[test]
+fn owner_lists_tokens_held_by_address() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(2);
+
+	core.broadcast_raw_tx(brc721_tx(
+		&[core.outpoint(1, 0, 0)],
+		RegisterCollection { address: H160::zero(), rebaseable: false }.as_script(),
+		&[],
+	));
+
+	core.mine_blocks(1);
+
+	let collection_id = Brc721CollectionId { block: 3, tx: 1 };
+
+	let funding = core.outpoint(2, 0, 0);
+
+	let initial_owner = address_mapping::btc_address_to_h160(core.address(funding)).unwrap();
+
+	let alice = "bcrt1pe3p3nce9x258cuttetd4jl5f7398xge4mmafs3kxcfuqvuxec8rq63wsae"
+		.parse::<Address<NetworkUnchecked>>()
+		.unwrap()
+		.assume_checked();
+
+	let registration = core.broadcast_raw_tx(brc721_tx(
+		&[funding],
+		ScriptBuf::from(RegisterOwnership {
+			collection_id,
+			slots_bundles: vec![SlotsBundle(vec![0..=3, 10..=10]), SlotsBundle(vec![4..=9])],
+		}),
+		&[(&alice, 10_000), (&core.address(funding), 10_000)],
+	));
+
+	core.mine_blocks(1);
+
+	let owner_tokens = |address: &Address, page_index: usize| {
+		serde_json::from_str::<Brc721OwnerTokens>(
+			&ord.json_request(format!("/brc721/owner/{address}/{page_index}"))
+				.text()
+				.unwrap(),
+		)
+		.unwrap()
+	};
+
+	let range = |vout, slots| Brc721TokenRange {
+		collection_id,
+		initial_owner,
+		output: OutPoint { txid: registration, vout },
+		slots,
+	};
+
+	pretty_assert_eq!(
+		owner_tokens(&alice, 0),
+		Brc721OwnerTokens {
+			address: alice.as_unchecked().clone(),
+			more: false,
+			page_index: 0,
+			tokens: vec![range(1, 0..=3), range(1, 10..=10)],
+		}
+	);
+
+	pretty_assert_eq!(
+		owner_tokens(&alice, 1),
+		Brc721OwnerTokens {
+			address: alice.as_unchecked().clone(),
+			more: false,
+			page_index: 1,
+			tokens: Vec::new(),
+		}
+	);
+
+	let transfer = core.broadcast_raw_tx(brc721_tx(
+		&[OutPoint { txid: registration, vout: 1 }],
+		ScriptBuf::new_op_return([]),
+		&[(&core.address(funding), 9_000)],
+	));
+
+	core.mine_blocks(1);
+
+	assert!(owner_tokens(&alice, 0).tokens.is_empty());
+
+	let owner = core.address(funding);
+
+	let mut tokens = owner_tokens(&owner, 0).tokens;
+	tokens.sort_by_key(|range| *range.slots.start());
+
+	pretty_assert_eq!(
+		tokens,
+		vec![
+			Brc721TokenRange { output: OutPoint { txid: transfer, vout: 1 }, ..range(1, 0..=3) },
+			range(2, 4..=9),
+			Brc721TokenRange { output: OutPoint { txid: transfer, vout: 1 }, ..range(1, 10..=10) },
+		]
+	);
+}
+
+#[test]
+fn owner_requires_brc721_index() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest"], &[]);
+
+	let response = ord.json_request("/brc721/owner/bcrt1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqdku202");
+
+	assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn initial_owner_lists_registered_slots_across_collections() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(3);
+
+	for block in 1..=2 {
+		core.broadcast_raw_tx(brc721_tx(
+			&[core.outpoint(block, 0, 0)],
+			RegisterCollection { address: H160::zero(), rebaseable: false }.as_script(),
+			&[],
+		));
+	}
+
+	core.mine_blocks(1);
+
+	let funding = core.outpoint(3, 0, 0);
+
+	let owner = core.address(funding);
+
+	let registration = core.broadcast_raw_tx(brc721_tx(
+		&[funding],
+		ScriptBuf::from(RegisterOwnership {
+			collection_id: Brc721CollectionId { block: 4, tx: 2 },
+			slots_bundles: vec![SlotsBundle(vec![0..=3, 10..=10])],
+		}),
+		&[(&owner, 10_000)],
+	));
+
+	core.mine_blocks(1);
+
+	core.broadcast_raw_tx(brc721_tx(
+		&[OutPoint { txid: registration, vout: 1 }],
+		ScriptBuf::from(RegisterOwnership {
+			collection_id: Brc721CollectionId { block: 4, tx: 1 },
+			slots_bundles: vec![SlotsBundle(vec![5..=5])],
+		}),
+		&[(&owner, 9_000)],
+	));
+
+	core.mine_blocks(1);
+
+	let initial_owner = address_mapping::btc_address_to_h160(owner.clone()).unwrap();
+
+	let response = serde_json::from_str::<Brc721InitialOwner>(
+		&ord.json_request(format!("/brc721/initial-owner/{owner}")).text().unwrap(),
+	)
+	.unwrap();
+
+	pretty_assert_eq!(
+		response,
+		Brc721InitialOwner {
+			address: owner.as_unchecked().clone(),
+			initial_owner,
+			registrations: vec![
+				Brc721RegisteredSlots {
+					collection_id: Brc721CollectionId { block: 4, tx: 1 },
+					initial_owner,
+					slots: vec![5..=5],
+				},
+				Brc721RegisteredSlots {
+					collection_id: Brc721CollectionId { block: 4, tx: 2 },
+					initial_owner,
+					slots: vec![0..=3, 10..=10],
+				},
+			],
+		}
+	);
+}
@@ -103,3 +103,186 @@ fn export_inscription_number_to_id_tsv() {
 
 	assert_eq!(entries.get(&2).unwrap(), &ord::Object::InscriptionId(inscription),);
 }
+
+#[test]
+fn snapshot_and_restore() {
+	let core = mockcore::spawn();
+	let tip = core.mine_blocks(3).pop().unwrap();
+
+	let (tempdir, _) = CommandBuilder::new("--index-brc721 index update").core(&core).run();
+
+	let snapshot = CommandBuilder::new("--index-brc721 index snapshot --out snapshot.laos")
+		.core(&core)
+		.temp_dir(tempdir.clone())
+		.run_and_deserialize_output::<SnapshotArchive>();
+
+	assert_eq!(snapshot.chain, Chain::Mainnet);
+	assert_eq!(snapshot.height, 3);
+	assert_eq!(snapshot.hash, tip.block_hash());
+	assert!(snapshot.index_brc721);
+	assert!(!snapshot.index_runes);
+
+	let restored = CommandBuilder::new(
+		"--index-brc721 --index restored.redb index restore --input snapshot.laos",
+	)
+	.core(&core)
+	.temp_dir(tempdir.clone())
+	.run_and_deserialize_output::<SnapshotArchive>();
+
+	assert_eq!(restored, snapshot);
+
+	core.mine_blocks(1);
+
+	CommandBuilder::new("--index-brc721 --index restored.redb index update")
+		.core(&core)
+		.temp_dir(tempdir.clone())
+		.run_and_extract_stdout();
+
+	CommandBuilder::new("--index-brc721 --index restored.redb index info")
+		.core(&core)
+		.temp_dir(tempdir)
+		.stdout_regex(r#"(?s)\{\s+"blocks_indexed": 5,.*"#)
+		.run_and_extract_stdout();
+}
+
+#[test]
+fn restore_requires_matching_index_flags() {
+	let core = mockcore::spawn();
+	core.mine_blocks(1);
+
+	let (tempdir, _) = CommandBuilder::new("--index-brc721 index snapshot --out snapshot.laos")
+		.core(&core)
+		.stdout_regex(".*")
+		.run();
+
+	CommandBuilder::new("--index restored.redb index restore --input snapshot.laos")
+		.temp_dir(tempdir.clone())
+		.expected_stderr("error: snapshot was built with `--index-brc721`\n")
+		.expected_exit_code(1)
+		.run_and_extract_stdout();
+
+	CommandBuilder::new(
+		"--index-brc721 --index-runes --index restored.redb index restore --input snapshot.laos",
+	)
+	.temp_dir(tempdir.clone())
+	.expected_stderr("error: snapshot was built without `--index-runes`\n")
+	.expected_exit_code(1)
+	.run_and_extract_stdout();
+
+	CommandBuilder::new(
+		"--index-brc721-only --index restored.redb index restore --input snapshot.laos",
+	)
+	.temp_dir(tempdir.clone())
+	.expected_stderr("error: snapshot was built without `--index-brc721-only`\n")
+	.expected_exit_code(1)
+	.run_and_extract_stdout();
+
+	CommandBuilder::new(
+		"--index-brc721 --first-brc721-height 1 --index restored.redb index restore --input snapshot.laos",
+	)
+	.temp_dir(tempdir.clone())
+	.expected_stderr("error: snapshot was built with first brc721 height 0, not 1\n")
+	.expected_exit_code(1)
+	.run_and_extract_stdout();
+
+	assert!(!tempdir.path().join("restored.redb").exists());
+}
+
+#[test]
+fn restore_requires_snapshot_block_in_best_chain() {
+	let core = mockcore::spawn();
+	let tip = core.mine_blocks(1).pop().unwrap();
+
+	let (tempdir, _) = CommandBuilder::new("index snapshot --out snapshot.laos")
+		.core(&core)
+		.stdout_regex(".*")
+		.run();
+
+	core.invalidate_tip();
+	core.mine_blocks(2);
+
+	CommandBuilder::new("--index restored.redb index restore --input snapshot.laos")
+		.core(&core)
+		.temp_dir(tempdir.clone())
+		.expected_stderr(format!(
+			"error: snapshot block {} at height 1 is not in the best chain of the connected node\n",
+			tip.block_hash()
+		))
+		.expected_exit_code(1)
+		.run_and_extract_stdout();
+
+	assert!(!tempdir.path().join("restored.redb").exists());
+}
+
+#[test]
+fn restore_requires_matching_chain() {
+	let core = mockcore::spawn();
+	core.mine_blocks(1);
+
+	let (tempdir, _) = CommandBuilder::new("index snapshot --out snapshot.laos")
+		.core(&core)
+		.stdout_regex(".*")
+		.run();
+
+	CommandBuilder::new("--signet --index restored.redb index restore --input snapshot.laos")
+		.temp_dir(tempdir)
+		.expected_stderr("error: snapshot is for mainnet, not signet\n")
+		.expected_exit_code(1)
+		.run_and_extract_stdout();
+}
+
+#[test]
+fn restore_does_not_overwrite_index() {
+	let core = mockcore::spawn();
+	core.mine_blocks(1);
+
+	let (tempdir, _) = CommandBuilder::new("index snapshot --out snapshot.laos")
+		.core(&core)
+		.stdout_regex(".*")
+		.run();
+
+	CommandBuilder::new("index restore --input snapshot.laos")
+		.temp_dir(tempdir)
+		.stderr_regex("error: index already exists at `.*index.redb`, remove it before restoring a snapshot\n")
+		.expected_exit_code(1)
+		.run_and_extract_stdout();
+}
+
+#[test]
+fn restore_verifies_checksum() {
+	let core = mockcore::spawn();
+	core.mine_blocks(1);
+
+	let (tempdir, _) = CommandBuilder::new("index snapshot --out snapshot.laos")
+		.core(&core)
+		.stdout_regex(".*")
+		.run();
+
+	let path = tempdir.path().join("snapshot.laos");
+
+	let contents = fs::read(&path).unwrap();
+
+	let newline = contents.iter().position(|byte| *byte == b'\n').unwrap();
+
+	let mut header = serde_json::from_slice::<serde_json::Value>(&contents[..newline]).unwrap();
+
+	header["sha256"] = "0".repeat(64).into();
+
+	let mut corrupted = serde_json::to_vec(&header).unwrap();
+	corrupted.extend_from_slice(&contents[newline..]);
+
+	fs::write(&path, corrupted).unwrap();
+
+	CommandBuilder::new("--index restored.redb index restore --input snapshot.laos")
+		.core(&core)
+		.temp_dir(tempdir.clone())
+		.stderr_regex(format!(
+			"error: snapshot checksum mismatch: expected {}, got [[:xdigit:]]{{64}}\n",
+			"0".repeat(64)
+		))
+		.expected_exit_code(1)
+		.run_and_extract_stdout();
+
+	assert!(!tempdir.path().join("restored.redb").exists());
+	assert!(!tempdir.path().join("restored.redb.partial").exists());
+}
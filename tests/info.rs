@@ -30,14 +30,17 @@ fn json_with_satoshi_index() {
 			r#"\{
   "blocks_indexed": 1,
   "branch_pages": \d+,
+  "chain_tip_distance": 21,
   "fragmented_bytes": \d+,
   "index_file_size": \d+,
   "index_path": ".*\.redb",
   "leaf_pages": \d+,
+  "max_savepoints": 2,
   "metadata_bytes": \d+,
   "outputs_traversed": 1,
   "page_size": \d+,
   "sat_ranges": 1,
+  "savepoint_interval": 10,
   "stored_bytes": \d+,
   "tables": .*,
   "total_bytes": \d+,
@@ -68,14 +71,17 @@ fn json_without_satoshi_index() {
 			r#"\{
   "blocks_indexed": 1,
   "branch_pages": \d+,
+  "chain_tip_distance": 21,
   "fragmented_bytes": \d+,
   "index_file_size": \d+,
   "index_path": ".*\.redb",
   "leaf_pages": \d+,
+  "max_savepoints": 2,
   "metadata_bytes": \d+,
   "outputs_traversed": 0,
   "page_size": \d+,
   "sat_ranges": 0,
+  "savepoint_interval": 10,
   "stored_bytes": \d+,
   "tables": .*,
   "total_bytes": \d+,
@@ -579,6 +579,7 @@ fn get_status() {
 			rune_index: true,
 			runes: 0,
 			sat_index: true,
+			snapshot_recovery: None,
 			started: dummy_started,
 			transaction_index: false,
 			unrecoverably_reorged: false,
@@ -919,3 +920,42 @@ fn outputs_address_returns_400_for_missing_rune_index() {
 	let cardinal_response = ord.json_request(format!("/outputs/{}?type=runic", address));
 	assert_eq!(cardinal_response.status(), StatusCode::BAD_REQUEST);
 }
+
+#[test]
+fn get_brc721_mempool() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(1);
+
+	let register_collection = RegisterCollection::default();
+
+	let txid = core.broadcast_tx(TransactionTemplate {
+		inputs: &[(1, 0, 0, Default::default())],
+		outputs: 1,
+		op_return_index: Some(0),
+		op_return_value: Some(0),
+		op_return: Some(register_collection.as_script()),
+		..default()
+	});
+
+	let response = ord.json_request("/brc721/mempool");
+	assert_eq!(response.status(), StatusCode::OK);
+
+	pretty_assert_eq!(
+		serde_json::from_str::<Vec<api::Brc721PendingOperation>>(&response.text().unwrap())
+			.unwrap(),
+		[api::Brc721PendingOperation::RegisterCollection {
+			txid,
+			laos_address: register_collection.address,
+			rebaseable: register_collection.rebaseable,
+		}]
+	);
+
+	core.mine_blocks(1);
+
+	let response = ord.json_request("/brc721/mempool");
+	assert_eq!(response.status(), StatusCode::OK);
+	assert_eq!(response.text().unwrap(), "[]");
+}
@@ -32,7 +32,7 @@ use ord::{
 	subcommand::runes::RuneInfo,
 	templates::InscriptionHtml,
 	wallet::{batch, ListDescriptorsResult},
-	Inscription, InscriptionId, RuneEntry,
+	Inscription, InscriptionId, RuneEntry, SnapshotArchive,
 };
 use ordinals::{
 	brc721::register_collection::RegisterCollection, Artifact, Brc721CollectionId, Charm, Edict,
@@ -74,6 +74,7 @@ mod expected;
 mod test_server;
 
 mod balances;
+mod brc721;
 mod decode;
 mod epochs;
 mod find;
@@ -87,6 +88,7 @@ mod server;
 mod settings;
 mod subsidy;
 mod supply;
+mod teleburn;
 mod traits;
 mod verify;
 mod version;
@@ -27,27 +27,38 @@ fn default() {
   "bitcoin_rpc_password": null,
   "bitcoin_rpc_url": "127.0.0.1:8332",
   "bitcoin_rpc_username": null,
+  "block_fetch_threads": \d+,
   "chain": "mainnet",
+  "chain_tip_distance": 21,
   "commit_interval": 5000,
   "config": null,
   "config_dir": null,
   "cookie_file": ".*\.cookie",
   "data_dir": ".*",
+  "first_brc721_height": null,
   "height_limit": null,
   "hidden": \[\],
   "http_port": null,
   "index": ".*index\.redb",
   "index_addresses": false,
   "index_brc721": false,
+  "index_brc721_only": false,
   "index_cache_size": \d+,
   "index_runes": false,
   "index_sats": false,
   "index_transactions": false,
   "integration_test": false,
+  "laos_rpc_url": null,
+  "max_savepoints": 2,
+  "max_snapshots": 2,
   "no_index_inscriptions": false,
+  "savepoint_interval": 10,
   "server_password": null,
   "server_url": null,
-  "server_username": null
+  "server_username": null,
+  "snapshot_interval": null,
+  "webhook_secret": null,
+  "webhook_url": null
 \}
 "#,
 		)
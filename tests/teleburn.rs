@@ -0,0 +1,42 @@
+// Copyright 2023-2024 Freeverse.io
+// This file is part of LAOS.
+
+// LAOS is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// LAOS is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use ord::subcommand::teleburn::Output;
+use ordinals::Brc721TokenId;
+use sp_core::H160;
+
+#[test]
+fn teleburn_brc721_token() {
+	assert_eq!(
+		CommandBuilder::new(format!(
+			"teleburn --brc721 874123:5:{}",
+			Brc721TokenId { slot: 42, initial_owner: H160::repeat_byte(0x2a) }
+		))
+		.run_and_deserialize_output::<Output>()
+		.ethereum
+		.to_string(),
+		"0xa8B3F2aA7F090dB5e3899747F3831534728874f5",
+	);
+}
+
+#[test]
+fn teleburn_requires_inscription_or_brc721_token() {
+	CommandBuilder::new("teleburn")
+		.stderr_regex(".*the following required arguments were not provided.*")
+		.expected_exit_code(2)
+		.run_and_extract_stdout();
+}
@@ -80,3 +80,19 @@ fn verify_with_transaction() {
 		""
 	);
 }
+
+#[test]
+fn verify_rejects_address_of_other_network() {
+	CommandBuilder::new([
+      "--regtest",
+      "verify",
+      "--address", "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l",
+      "--text", "Hello World",
+      "--witness", "AkcwRAIgZRfIY3p7/DoVTty6YZbWS71bc5Vct9p9Fia83eRmw2QCICK/ENGfwLtptFluMGs2KsqoNSk89pO7F29zJLUx9a/sASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI="
+  ])
+  .expected_exit_code(1)
+  .expected_stderr(
+    "error: validation error\n\nbecause:\n- address bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l is not valid on regtest\n",
+  )
+  .run_and_extract_stdout();
+}
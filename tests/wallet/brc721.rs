@@ -1,3 +1,60 @@
+mod burn_token;
+mod crafted_transactions;
+mod rebase_token;
 mod register_collection;
 mod register_ownership;
 use super::*;
+use ord::subcommand::wallet::{brc721 as brc721_cmd, receive};
+use ordinals::{brc721::address_mapping, Brc721TokenId};
+use sp_core::H160;
+
+/// Registers collection 2:1 and a token of it owned by the wallet, returning the token id.
+pub(crate) fn register_token(
+	core: &mockcore::Handle,
+	ord: &TestServer,
+	rebaseable: bool,
+) -> Brc721TokenId {
+	core.mine_blocks(1);
+
+	create_wallet(core, ord);
+
+	let initial_owner = CommandBuilder::new("--regtest wallet receive")
+		.core(core)
+		.ord(ord)
+		.run_and_deserialize_output::<receive::Output>()
+		.addresses
+		.first()
+		.unwrap()
+		.clone()
+		.require_network(Network::Regtest)
+		.unwrap();
+
+	CommandBuilder::new(format!(
+		"--regtest wallet brc721 register-collection --fee-rate 1 --address {:x}{}",
+		H160::zero(),
+		if rebaseable { " --rebaseable" } else { "" }
+	))
+	.core(core)
+	.ord(ord)
+	.run_and_deserialize_output::<brc721_cmd::register_collection::Output>();
+
+	core.mine_blocks(1);
+
+	core.mine_blocks_to(1, initial_owner.clone());
+
+	CommandBuilder::new("--regtest wallet brc721 register-ownership --fee-rate 1 --file tmp.yml")
+		.write(
+			"tmp.yml",
+			format!("collection_id: 2:1\ninitial_owner: {initial_owner}\noutputs:\n  - slots_bundle: [[0, 3]]"),
+		)
+		.core(core)
+		.ord(ord)
+		.run_and_deserialize_output::<brc721_cmd::register_ownership::Output>();
+
+	core.mine_blocks(1);
+
+	Brc721TokenId {
+		slot: 2,
+		initial_owner: address_mapping::btc_address_to_h160(initial_owner).unwrap(),
+	}
+}
@@ -0,0 +1,120 @@
+use super::*;
+use bitcoin::ScriptBuf;
+use ord::subcommand::wallet::brc721::burn_token;
+
+fn get_token(ord: &TestServer, token_id: Brc721TokenId) -> api::Brc721Token {
+	serde_json::from_str(&ord.json_request(format!("/brc721/token/2:1/{token_id}")).text().unwrap())
+		.unwrap()
+}
+
+#[test]
+fn burn_token() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	let token_id = register_token(&core, &ord, false);
+
+	let output = CommandBuilder::new(format!(
+		"--regtest wallet brc721 burn --fee-rate 1 --collection-id 2:1 --token-id {token_id}"
+	))
+	.core(&core)
+	.ord(&ord)
+	.run_and_deserialize_output::<burn_token::Output>();
+
+	core.mine_blocks(1);
+
+	let token = get_token(&ord, token_id);
+
+	assert_eq!(token.burned, Some(core.height().try_into().unwrap()));
+	assert_eq!(token.output, None);
+	assert_eq!(token.address, None);
+
+	ord.assert_response_regex(
+		format!("/brc721/token/2:1/{token_id}"),
+		format!(".*<dd>burned at height <a href=/block/{0}>{0}</a></dd>.*", core.height()),
+	);
+
+	assert_eq!(
+		get_token(&ord, Brc721TokenId { slot: 3, ..token_id }).output,
+		Some(OutPoint { txid: output.tx_id, vout: 1 })
+	);
+}
+
+#[test]
+fn burn_token_twice() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	let token_id = register_token(&core, &ord, false);
+
+	CommandBuilder::new(format!(
+		"--regtest wallet brc721 burn --fee-rate 1 --collection-id 2:1 --token-id {token_id}"
+	))
+	.core(&core)
+	.ord(&ord)
+	.run_and_deserialize_output::<burn_token::Output>();
+
+	core.mine_blocks(1);
+
+	CommandBuilder::new(format!(
+		"--regtest wallet brc721 burn --fee-rate 1 --collection-id 2:1 --token-id {token_id}"
+	))
+	.core(&core)
+	.ord(&ord)
+	.expected_stderr(format!(
+		"error: token {token_id} of collection 2:1 was already burned at height {}\n",
+		core.height()
+	))
+	.expected_exit_code(1)
+	.run_and_extract_stdout();
+}
+
+#[test]
+fn burn_unregistered_token() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	let token_id = Brc721TokenId { slot: 4, ..register_token(&core, &ord, false) };
+
+	CommandBuilder::new(format!(
+		"--regtest wallet brc721 burn --fee-rate 1 --collection-id 2:1 --token-id {token_id}"
+	))
+	.core(&core)
+	.ord(&ord)
+	.expected_stderr(format!("error: token {token_id} not found in collection 2:1\n"))
+	.expected_exit_code(1)
+	.run_and_extract_stdout();
+}
+
+#[test]
+fn tokens_sent_to_op_return_are_burned() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	let token_id = register_token(&core, &ord, false);
+
+	let output = get_token(&ord, token_id).output.unwrap();
+
+	core.broadcast_tx(TransactionTemplate {
+		inputs: &[(
+			usize::try_from(core.height()).unwrap(),
+			1,
+			output.vout.try_into().unwrap(),
+			Default::default(),
+		)],
+		outputs: 0,
+		op_return: Some(ScriptBuf::new_op_return([])),
+		op_return_index: Some(0),
+		op_return_value: Some(0),
+		..default()
+	});
+
+	core.mine_blocks(1);
+
+	for slot in 0..=3 {
+		assert_eq!(
+			get_token(&ord, Brc721TokenId { slot, ..token_id }).burned,
+			Some(core.height().try_into().unwrap())
+		);
+	}
+}
@@ -0,0 +1,243 @@
+use super::*;
+use bitcoin::{
+	absolute::LockTime, opcodes::all::OP_PUSHNUM_15, transaction::Version, ScriptBuf, Transaction,
+	TxIn,
+};
+use mockcore::brc721_tx;
+use ord::{subcommand::wallet::brc721::register_collection, templates::Brc721CollectionsHtml};
+use ordinals::{
+	brc721::register_ownership::{RegisterOwnership, SlotsBundle},
+	Brc721Collection,
+};
+
+fn register_collection_script(address: H160, rebaseable: bool) -> ScriptBuf {
+	RegisterCollection { address, rebaseable }.as_script()
+}
+
+fn get_collections(ord: &TestServer) -> Vec<Brc721Collection> {
+	serde_json::from_str::<Brc721CollectionsHtml>(
+		&ord.json_request("/brc721/collections").text().unwrap(),
+	)
+	.unwrap()
+	.entries
+}
+
+fn get_token(
+	ord: &TestServer,
+	collection_id: Brc721CollectionId,
+	token_id: Brc721TokenId,
+) -> api::Brc721Token {
+	serde_json::from_str(
+		&ord.json_request(format!("/brc721/token/{collection_id}/{token_id}"))
+			.text()
+			.unwrap(),
+	)
+	.unwrap()
+}
+
+fn address(address: &str) -> Address {
+	address.parse::<Address<NetworkUnchecked>>().unwrap().assume_checked()
+}
+
+#[test]
+fn multiple_collections_in_one_block() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(3);
+
+	for block in 1..=3 {
+		core.broadcast_raw_tx(brc721_tx(
+			&[core.outpoint(block, 0, 0)],
+			register_collection_script(H160::repeat_byte(block.try_into().unwrap()), block == 2),
+			&[],
+		));
+	}
+
+	core.mine_blocks(1);
+
+	assert_eq!(
+		get_collections(&ord),
+		(1..=3)
+			.rev()
+			.map(|tx| Brc721Collection::new(
+				Brc721CollectionId { block: 4, tx },
+				H160::repeat_byte(tx.try_into().unwrap()),
+				tx == 2,
+			))
+			.collect::<Vec<Brc721Collection>>()
+	);
+}
+
+#[test]
+fn malformed_payloads_are_ignored() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(4);
+
+	let truncated = ScriptBuf::from_bytes(
+		register_collection_script(H160::zero(), false).as_bytes()[..10].to_vec(),
+	);
+
+	let unknown_operation = script::Builder::new()
+		.push_opcode(opcodes::all::OP_RETURN)
+		.push_opcode(OP_PUSHNUM_15)
+		.push_slice([0xff])
+		.into_script();
+
+	let unknown_collection = ScriptBuf::from(RegisterOwnership {
+		collection_id: Brc721CollectionId { block: 1, tx: 1 },
+		slots_bundles: vec![SlotsBundle(vec![0..=0])],
+	});
+
+	let alice = address("mrEqurom3cKudH7FaDrF3j1DJePLcjAU3m");
+
+	for (i, op_return) in [truncated, unknown_operation, unknown_collection].into_iter().enumerate()
+	{
+		core.broadcast_raw_tx(brc721_tx(
+			&[core.outpoint(i + 1, 0, 0)],
+			op_return,
+			&[(&alice, 10_000)],
+		));
+	}
+
+	core.broadcast_raw_tx(brc721_tx(
+		&[core.outpoint(4, 0, 0)],
+		register_collection_script(H160::repeat_byte(1), true),
+		&[],
+	));
+
+	core.mine_blocks(1);
+
+	assert_eq!(
+		get_collections(&ord),
+		vec![Brc721Collection::new(
+			Brc721CollectionId { block: 5, tx: 4 },
+			H160::repeat_byte(1),
+			true
+		)]
+	);
+}
+
+#[test]
+fn registration_to_multiple_outputs_and_spend_of_slot_bearing_output() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(2);
+
+	core.broadcast_raw_tx(brc721_tx(
+		&[core.outpoint(1, 0, 0)],
+		register_collection_script(H160::zero(), false),
+		&[],
+	));
+
+	core.mine_blocks(1);
+
+	let collection_id = Brc721CollectionId { block: 3, tx: 1 };
+
+	let funding = core.outpoint(2, 0, 0);
+
+	let initial_owner = address_mapping::btc_address_to_h160(core.address(funding)).unwrap();
+
+	let alice = address("mrEqurom3cKudH7FaDrF3j1DJePLcjAU3m");
+	let bob = address("bcrt1pe3p3nce9x258cuttetd4jl5f7398xge4mmafs3kxcfuqvuxec8rq63wsae");
+
+	let registration = core.broadcast_raw_tx(brc721_tx(
+		&[funding],
+		ScriptBuf::from(RegisterOwnership {
+			collection_id,
+			slots_bundles: vec![SlotsBundle(vec![0..=0]), SlotsBundle(vec![1..=2])],
+		}),
+		&[(&alice, 10_000), (&bob, 10_000)],
+	));
+
+	core.mine_blocks(1);
+
+	let token_id = |slot| Brc721TokenId { slot, initial_owner };
+
+	assert_eq!(
+		get_token(&ord, collection_id, token_id(0)).output,
+		Some(OutPoint { txid: registration, vout: 1 })
+	);
+
+	for slot in 1..=2 {
+		assert_eq!(
+			get_token(&ord, collection_id, token_id(slot)).output,
+			Some(OutPoint { txid: registration, vout: 2 })
+		);
+	}
+
+	let transfer = core.broadcast_raw_tx(Transaction {
+		version: Version(2),
+		lock_time: LockTime::ZERO,
+		input: vec![TxIn {
+			previous_output: OutPoint { txid: registration, vout: 2 },
+			script_sig: ScriptBuf::new(),
+			sequence: Sequence::MAX,
+			witness: Witness::new(),
+		}],
+		output: vec![TxOut {
+			value: Amount::from_sat(9_000),
+			script_pubkey: alice.script_pubkey(),
+		}],
+	});
+
+	core.mine_blocks(1);
+
+	assert_eq!(
+		get_token(&ord, collection_id, token_id(0)).output,
+		Some(OutPoint { txid: registration, vout: 1 })
+	);
+
+	for slot in 1..=2 {
+		assert_eq!(
+			get_token(&ord, collection_id, token_id(slot)).output,
+			Some(OutPoint { txid: transfer, vout: 0 })
+		);
+	}
+}
+
+#[test]
+fn op_return_over_datacarriersize_is_rejected() {
+	// OP_RETURN, BRC721_INIT_CODE, a push opcode and a 22 byte payload
+	let core = mockcore::builder().network(Network::Regtest).datacarriersize(24).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(1);
+
+	create_wallet(&core, &ord);
+
+	CommandBuilder::new(format!(
+		"--regtest wallet brc721 register-collection --fee-rate 1 --address {:x}",
+		H160::zero()
+	))
+	.core(&core)
+	.ord(&ord)
+	.stderr_regex(".*scriptpubkey.*")
+	.expected_exit_code(1)
+	.run_and_extract_stdout();
+
+	assert!(core.mempool().is_empty());
+}
+
+#[test]
+fn op_return_at_datacarriersize_is_accepted() {
+	let core = mockcore::builder().network(Network::Regtest).datacarriersize(25).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(1);
+
+	create_wallet(&core, &ord);
+
+	let output = CommandBuilder::new(format!(
+		"--regtest wallet brc721 register-collection --fee-rate 1 --address {:x}",
+		H160::zero()
+	))
+	.core(&core)
+	.ord(&ord)
+	.run_and_deserialize_output::<register_collection::Output>();
+
+	assert_eq!(core.mempool()[0].compute_txid(), output.tx_id);
+}
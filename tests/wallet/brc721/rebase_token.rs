@@ -0,0 +1,61 @@
+use super::*;
+use ord::subcommand::wallet::brc721::rebase_token;
+
+#[test]
+fn rebase_token() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	let token_id = register_token(&core, &ord, true);
+
+	let output = CommandBuilder::new(format!(
+		"--regtest wallet brc721 rebase-token --fee-rate 1 --collection-id 2:1 --token-id {token_id} --token-uri ipfs://foo"
+	))
+	.core(&core)
+	.ord(&ord)
+	.run_and_deserialize_output::<rebase_token::Output>();
+
+	core.mine_blocks(1);
+
+	let token = serde_json::from_str::<api::Brc721Token>(
+		&ord.json_request(format!("/brc721/token/2:1/{token_id}")).text().unwrap(),
+	)
+	.unwrap();
+
+	assert_eq!(token.token_uri, Some("ipfs://foo".into()));
+	assert_eq!(token.output, Some(OutPoint { txid: output.tx_id, vout: 1 }));
+}
+
+#[test]
+fn rebase_token_of_non_rebaseable_collection() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	let token_id = register_token(&core, &ord, false);
+
+	CommandBuilder::new(format!(
+		"--regtest wallet brc721 rebase-token --fee-rate 1 --collection-id 2:1 --token-id {token_id} --token-uri ipfs://foo"
+	))
+	.core(&core)
+	.ord(&ord)
+	.expected_stderr("error: collection 2:1 is not rebaseable\n")
+	.expected_exit_code(1)
+	.run_and_extract_stdout();
+}
+
+#[test]
+fn rebase_unregistered_token() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	let token_id = Brc721TokenId { slot: 4, ..register_token(&core, &ord, true) };
+
+	CommandBuilder::new(format!(
+		"--regtest wallet brc721 rebase-token --fee-rate 1 --collection-id 2:1 --token-id {token_id} --token-uri ipfs://foo"
+	))
+	.core(&core)
+	.ord(&ord)
+	.expected_stderr(format!("error: token {token_id} not found in collection 2:1\n"))
+	.expected_exit_code(1)
+	.run_and_extract_stdout();
+}
@@ -100,3 +100,241 @@ fn register_collection_command_indexer_integration() {
 		},
 	);
 }
+
+#[test]
+fn register_collection_with_destination() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(1);
+
+	create_wallet(&core, &ord);
+
+	let output = CommandBuilder::new(format!(
+		"--regtest wallet brc721 register-collection --fee-rate 1 --address {:x} --destination bcrt1pe3p3nce9x258cuttetd4jl5f7398xge4mmafs3kxcfuqvuxec8rq63wsae",
+		H160::zero()
+	))
+	.core(&core)
+	.ord(&ord)
+	.run_and_deserialize_output::<register_collection::Output>();
+
+	core.mine_blocks(1);
+
+	let tx = core.tx_by_id(output.tx_id);
+	assert_eq!(
+		Address::from_script(&tx.output[1].script_pubkey, Network::Regtest)
+			.unwrap()
+			.to_string(),
+		"bcrt1pe3p3nce9x258cuttetd4jl5f7398xge4mmafs3kxcfuqvuxec8rq63wsae"
+	);
+}
+
+#[test]
+fn register_collection_with_destination_on_wrong_network() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(1);
+
+	create_wallet(&core, &ord);
+
+	CommandBuilder::new(format!(
+		"--regtest wallet brc721 register-collection --fee-rate 1 --address {:x} --destination 1BitcoinEaterAddressDontSendf59kuE",
+		H160::zero()
+	))
+	.core(&core)
+	.ord(&ord)
+	.stderr_regex("(?s).*address 1BitcoinEaterAddressDontSendf59kuE is not valid on regtest.*")
+	.expected_exit_code(1)
+	.run_and_extract_stdout();
+}
+
+#[test]
+fn registered_collection_output_is_labeled() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(1);
+
+	create_wallet(&core, &ord);
+
+	let output = CommandBuilder::new(format!(
+		"--regtest wallet brc721 register-collection --fee-rate 1 --address {:x}",
+		H160::zero()
+	))
+	.core(&core)
+	.ord(&ord)
+	.run_and_deserialize_output::<register_collection::Output>();
+
+	let postage = OutPoint::new(output.tx_id, 1);
+
+	let label = |labels: &str| {
+		labels
+			.lines()
+			.map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+			.find(|line| line["ref"] == postage.to_string())
+			.map(|line| line["label"].as_str().unwrap().to_string())
+	};
+
+	let labels = CommandBuilder::new("--regtest wallet label")
+		.core(&core)
+		.ord(&ord)
+		.stdout_regex(".*")
+		.run_and_extract_stdout();
+
+	assert_eq!(label(&labels), None);
+
+	core.mine_blocks(1);
+
+	let labels = CommandBuilder::new("--regtest wallet label")
+		.core(&core)
+		.ord(&ord)
+		.stdout_regex(".*")
+		.run_and_extract_stdout();
+
+	assert_eq!(label(&labels).as_deref(), Some("brc721 collection 2:1"));
+}
+
+#[test]
+fn register_collection_checks_laos_collection() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let laos = mocklaos::spawn();
+
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(1);
+
+	create_wallet(&core, &ord);
+
+	let collection = laos.create_collection(H160::repeat_byte(1));
+
+	let output = CommandBuilder::new(format!(
+		"--regtest --laos-rpc-url {} wallet brc721 register-collection --fee-rate 1 --address {:x}",
+		laos.url(),
+		collection
+	))
+	.core(&core)
+	.ord(&ord)
+	.run_and_deserialize_output::<register_collection::Output>();
+
+	assert_eq!(output.tx_id, core.mempool()[0].compute_txid());
+}
+
+#[test]
+fn register_collection_refuses_address_without_bytecode() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let laos = mocklaos::spawn();
+
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(1);
+
+	create_wallet(&core, &ord);
+
+	CommandBuilder::new(format!(
+		"--regtest --laos-rpc-url {} wallet brc721 register-collection --fee-rate 1 --address {:x}",
+		laos.url(),
+		H160::zero()
+	))
+	.core(&core)
+	.ord(&ord)
+	.expected_stderr(
+		"error: LAOS address 0x0000000000000000000000000000000000000000 has no deployed bytecode, \
+		 use --force to register it anyway\n",
+	)
+	.expected_exit_code(1)
+	.run_and_extract_stdout();
+
+	assert!(core.mempool().is_empty());
+}
+
+#[test]
+fn register_collection_refuses_contract_that_is_not_a_collection() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let laos = mocklaos::spawn();
+
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(1);
+
+	create_wallet(&core, &ord);
+
+	let contract = mocklaos::collection_address(99);
+
+	laos.deploy(contract, &mocklaos::REVERT_BYTECODE);
+
+	CommandBuilder::new(format!(
+		"--regtest --laos-rpc-url {} wallet brc721 register-collection --fee-rate 1 --address {:x}",
+		laos.url(),
+		contract
+	))
+	.core(&core)
+	.ord(&ord)
+	.stderr_regex("error: LAOS address 0xff.*63 does not implement the collection interface.*")
+	.expected_exit_code(1)
+	.run_and_extract_stdout();
+
+	assert!(core.mempool().is_empty());
+}
+
+#[test]
+fn register_collection_refuses_ownable_contract_that_is_not_a_collection() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let laos = mocklaos::spawn();
+
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(1);
+
+	create_wallet(&core, &ord);
+
+	let contract = H160::repeat_byte(2);
+
+	laos.deploy_ownable(contract, H160::repeat_byte(1));
+
+	CommandBuilder::new(format!(
+		"--regtest --laos-rpc-url {} wallet brc721 register-collection --fee-rate 1 --address {:x}",
+		laos.url(),
+		contract
+	))
+	.core(&core)
+	.ord(&ord)
+	.expected_stderr(
+		"error: LAOS address 0x0202020202020202020202020202020202020202 is not a collection address, \
+		 use --force to register it anyway\n",
+	)
+	.expected_exit_code(1)
+	.run_and_extract_stdout();
+
+	assert!(core.mempool().is_empty());
+}
+
+#[test]
+fn register_collection_with_force_only_warns() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let laos = mocklaos::spawn();
+
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(1);
+
+	create_wallet(&core, &ord);
+
+	let output = CommandBuilder::new(format!(
+		"--regtest --laos-rpc-url {} wallet brc721 register-collection --fee-rate 1 --address {:x} \
+		 --force",
+		laos.url(),
+		H160::zero()
+	))
+	.core(&core)
+	.ord(&ord)
+	.expected_stderr(
+		"warning: LAOS address 0x0000000000000000000000000000000000000000 has no deployed bytecode\n",
+	)
+	.run_and_deserialize_output::<register_collection::Output>();
+
+	assert_eq!(output.tx_id, core.mempool()[0].compute_txid());
+}
@@ -1,5 +1,8 @@
 use super::*;
-use ord::subcommand::wallet::{brc721::register_ownership, receive};
+use ord::subcommand::wallet::{
+	brc721::{register_collection, register_ownership},
+	receive,
+};
 use ordinals::brc721::{
 	address_mapping,
 	register_ownership::{RegisterOwnership, SlotsBundle},
@@ -184,3 +187,310 @@ fn initial_owner_not_in_wallet() {
 		.expected_exit_code(1)
 		.run_and_extract_stdout();
 }
+
+#[test]
+fn already_registered_slots_are_refused() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(1);
+
+	create_wallet(&core, &ord);
+
+	let initial_owner = CommandBuilder::new("--regtest wallet receive")
+		.core(&core)
+		.ord(&ord)
+		.run_and_deserialize_output::<receive::Output>()
+		.addresses
+		.first()
+		.unwrap()
+		.clone()
+		.require_network(Network::Regtest)
+		.unwrap();
+
+	CommandBuilder::new(format!(
+		"--regtest wallet brc721 register-collection --fee-rate 1 --address {:x}",
+		H160::zero()
+	))
+	.core(&core)
+	.ord(&ord)
+	.run_and_deserialize_output::<register_collection::Output>();
+
+	core.mine_blocks(1);
+
+	core.mine_blocks_to(3, initial_owner.clone());
+
+	let file_path =
+		format!("{}/tests/fixtures/brc721_register_ownership.yml", env!("CARGO_MANIFEST_DIR"));
+
+	CommandBuilder::new(format!(
+		"--regtest wallet brc721 register-ownership --fee-rate 1 --file {}",
+		file_path
+	))
+	.core(&core)
+	.ord(&ord)
+	.run_and_deserialize_output::<register_ownership::Output>();
+
+	core.mine_blocks(1);
+
+	let registered_slots = serde_json::from_str::<api::Brc721RegisteredSlots>(
+		&ord.json_request(format!("/brc721/collection/2:1/registered/{initial_owner}"))
+			.text()
+			.unwrap(),
+	)
+	.unwrap();
+
+	assert_eq!(
+		registered_slots.slots,
+		vec![0..=3, 4..=10, 79228162514264337593543950335..=79228162514264337593543950335]
+	);
+
+	CommandBuilder::new(format!(
+		"--regtest wallet brc721 register-ownership --fee-rate 1 --file {}",
+		file_path
+	))
+	.core(&core)
+	.ord(&ord)
+	.expected_stderr(format!("error: slots 0..=3 in output 0 conflict with slots 0..=3 already registered by initial owner {initial_owner} in collection 2:1\n"))
+	.expected_exit_code(1)
+	.run_and_extract_stdout();
+}
+
+#[test]
+fn overlapping_slots_across_outputs_are_refused() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(1);
+
+	create_wallet(&core, &ord);
+
+	CommandBuilder::new("--regtest wallet brc721 register-ownership --fee-rate 1 --file tmp.yml")
+	.write("tmp.yml", "collection_id: 2:1\ninitial_owner: bcrt1pswcsgefgmts0esvgvw0hx3w3xf68ce8yf9tmsgu5ltlj5kmrcjlqd402f3\noutputs:\n  - slots_bundle: [[0, 5]]\n  - slots_bundle: [[5, 10]]")
+	.core(&core)
+	.ord(&ord)
+	.expected_stderr("error: overlapping ranges detected between output 0 and output 1: 0..=5 and 5..=10\n")
+	.expected_exit_code(1)
+	.run_and_extract_stdout();
+}
+
+#[test]
+fn postage_per_output() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(1);
+
+	create_wallet(&core, &ord);
+
+	let initial_owner = CommandBuilder::new("--regtest wallet receive")
+		.core(&core)
+		.ord(&ord)
+		.run_and_deserialize_output::<receive::Output>()
+		.addresses
+		.first()
+		.unwrap()
+		.clone()
+		.require_network(Network::Regtest)
+		.unwrap();
+
+	core.mine_blocks_to(3, initial_owner.clone());
+
+	let output = CommandBuilder::new(
+		"--regtest wallet brc721 register-ownership --fee-rate 1 --postage 20000sat --file tmp.yml",
+	)
+	.write("tmp.yml", format!("collection_id: 2:1\ninitial_owner: {initial_owner}\noutputs:\n  - slots_bundle: [[0]]\n    recipient: mrEqurom3cKudH7FaDrF3j1DJePLcjAU3m\n    postage: 50000 sat\n  - slots_bundle: [[1]]"))
+	.core(&core)
+	.ord(&ord)
+	.run_and_deserialize_output::<register_ownership::Output>();
+
+	core.mine_blocks(1);
+
+	let tx = core.tx_by_id(output.tx_id);
+	assert_eq!(tx.output[1].value, Amount::from_sat(50_000));
+	assert_eq!(tx.output[2].value, Amount::from_sat(20_000));
+}
+
+#[test]
+fn postage_below_dust_limit_of_recipient() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(1);
+
+	create_wallet(&core, &ord);
+
+	CommandBuilder::new("--regtest wallet brc721 register-ownership --fee-rate 1 --file tmp.yml")
+	.write("tmp.yml", "collection_id: 2:1\ninitial_owner: bcrt1pswcsgefgmts0esvgvw0hx3w3xf68ce8yf9tmsgu5ltlj5kmrcjlqd402f3\noutputs:\n  - slots_bundle: [[0]]\n    recipient: mrEqurom3cKudH7FaDrF3j1DJePLcjAU3m\n    postage: 500 sat")
+	.core(&core)
+	.ord(&ord)
+	.expected_stderr("error: postage below dust limit of 546sat\n")
+	.expected_exit_code(1)
+	.run_and_extract_stdout();
+}
+
+#[test]
+fn spends_single_initial_owner_utxo_by_default() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(1);
+
+	create_wallet(&core, &ord);
+
+	let initial_owner = CommandBuilder::new("--regtest wallet receive")
+		.core(&core)
+		.ord(&ord)
+		.run_and_deserialize_output::<receive::Output>()
+		.addresses
+		.first()
+		.unwrap()
+		.clone()
+		.require_network(Network::Regtest)
+		.unwrap();
+
+	core.mine_blocks_to(3, initial_owner.clone());
+
+	let output = CommandBuilder::new(
+		"--regtest wallet brc721 register-ownership --fee-rate 1 --file tmp.yml",
+	)
+	.write(
+		"tmp.yml",
+		format!(
+			"collection_id: 2:1\ninitial_owner: {initial_owner}\noutputs:\n  - slots_bundle: [[0]]"
+		),
+	)
+	.core(&core)
+	.ord(&ord)
+	.run_and_deserialize_output::<register_ownership::Output>();
+
+	core.mine_blocks(1);
+
+	let tx = core.tx_by_id(output.tx_id);
+	assert_eq!(tx.input.len(), 1);
+	assert_eq!(core.address(tx.input[0].previous_output), initial_owner);
+}
+
+#[test]
+fn spends_chosen_utxos() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(1);
+
+	create_wallet(&core, &ord);
+
+	let initial_owner = CommandBuilder::new("--regtest wallet receive")
+		.core(&core)
+		.ord(&ord)
+		.run_and_deserialize_output::<receive::Output>()
+		.addresses
+		.first()
+		.unwrap()
+		.clone()
+		.require_network(Network::Regtest)
+		.unwrap();
+
+	let blocks = core.mine_blocks_to(3, initial_owner.clone());
+
+	let first = OutPoint::new(blocks[2].txdata[0].compute_txid(), 0);
+	let second = OutPoint::new(blocks[0].txdata[0].compute_txid(), 0);
+
+	let output = CommandBuilder::new(format!(
+		"--regtest wallet brc721 register-ownership --fee-rate 1 --file tmp.yml --utxo {first} --utxo {second}",
+	))
+	.write("tmp.yml", format!("collection_id: 2:1\ninitial_owner: {initial_owner}\noutputs:\n  - slots_bundle: [[0]]"))
+	.core(&core)
+	.ord(&ord)
+	.run_and_deserialize_output::<register_ownership::Output>();
+
+	core.mine_blocks(1);
+
+	let tx = core.tx_by_id(output.tx_id);
+	assert_eq!(
+		tx.input.iter().map(|txin| txin.previous_output).collect::<Vec<OutPoint>>(),
+		vec![first, second]
+	);
+}
+
+#[test]
+fn chosen_utxo_must_belong_to_initial_owner() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	let blocks = core.mine_blocks(1);
+
+	create_wallet(&core, &ord);
+
+	let initial_owner = CommandBuilder::new("--regtest wallet receive")
+		.core(&core)
+		.ord(&ord)
+		.run_and_deserialize_output::<receive::Output>()
+		.addresses
+		.first()
+		.unwrap()
+		.clone()
+		.require_network(Network::Regtest)
+		.unwrap();
+
+	core.mine_blocks_to(1, initial_owner.clone());
+
+	let outpoint = OutPoint::new(blocks[0].txdata[0].compute_txid(), 0);
+
+	CommandBuilder::new(format!(
+		"--regtest wallet brc721 register-ownership --fee-rate 1 --file tmp.yml --utxo {outpoint}",
+	))
+	.write(
+		"tmp.yml",
+		format!(
+			"collection_id: 2:1\ninitial_owner: {initial_owner}\noutputs:\n  - slots_bundle: [[0]]"
+		),
+	)
+	.core(&core)
+	.ord(&ord)
+	.expected_stderr(format!(
+		"error: utxo {outpoint} is not a cardinal output of initial owner {initial_owner}\n"
+	))
+	.expected_exit_code(1)
+	.run_and_extract_stdout();
+}
+
+#[test]
+fn chosen_utxos_must_be_unique() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	core.mine_blocks(1);
+
+	create_wallet(&core, &ord);
+
+	let initial_owner = CommandBuilder::new("--regtest wallet receive")
+		.core(&core)
+		.ord(&ord)
+		.run_and_deserialize_output::<receive::Output>()
+		.addresses
+		.first()
+		.unwrap()
+		.clone()
+		.require_network(Network::Regtest)
+		.unwrap();
+
+	let blocks = core.mine_blocks_to(1, initial_owner.clone());
+
+	let outpoint = OutPoint::new(blocks[0].txdata[0].compute_txid(), 0);
+
+	CommandBuilder::new(format!(
+		"--regtest wallet brc721 register-ownership --fee-rate 1 --file tmp.yml --utxo {outpoint} --utxo {outpoint}",
+	))
+	.write(
+		"tmp.yml",
+		format!(
+			"collection_id: 2:1\ninitial_owner: {initial_owner}\noutputs:\n  - slots_bundle: [[0]]"
+		),
+	)
+	.core(&core)
+	.ord(&ord)
+	.expected_stderr(format!("error: utxo {outpoint} given more than once\n"))
+	.expected_exit_code(1)
+	.run_and_extract_stdout();
+}
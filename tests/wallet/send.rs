@@ -1205,3 +1205,42 @@ fn error_messages_use_spaced_runes() {
     .expected_stderr("error: rune `FOO` has not been etched\n")
     .run_and_extract_stdout();
 }
+
+#[test]
+fn send_btc_locks_brc721_token_outputs() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	let token_id = super::brc721::register_token(&core, &ord, false);
+
+	let token_output = || {
+		serde_json::from_str::<api::Brc721Token>(
+			&ord.json_request(format!("/brc721/token/2:1/{token_id}")).text().unwrap(),
+		)
+		.unwrap()
+		.output
+		.unwrap()
+	};
+
+	let output = token_output();
+
+	CommandBuilder::new(
+		"--regtest wallet send --fee-rate 1 mrEqurom3cKudH7FaDrF3j1DJePLcjAU3m 1btc",
+	)
+	.core(&core)
+	.ord(&ord)
+	.run_and_deserialize_output::<Send>();
+
+	assert!(core.get_locked().contains(&output));
+
+	let mempool = core.mempool();
+
+	assert_eq!(mempool.len(), 1);
+
+	assert!(mempool[0].input.iter().all(|input| input.previous_output != output));
+
+	core.mine_blocks(1);
+
+	assert_eq!(token_output(), output);
+}
@@ -14,8 +14,11 @@
 // You should have received a copy of the GNU General Public License
 // along with LAOS.  If not, see <http://www.gnu.org/licenses/>.
 
-use super::*;
-use ord::subcommand::wallet::{addresses::Output as AddressesOutput, sign::Output as SignOutput};
+use super::{brc721::register_token, *};
+use ord::subcommand::wallet::{
+	addresses::Output as AddressesOutput, receive::Output as ReceiveOutput,
+	sign::Output as SignOutput,
+};
 
 #[test]
 fn sign() {
@@ -160,3 +163,97 @@ fn sign_for_output() {
 
 	assert!(addresses.contains_key(&sign.address));
 }
+
+#[test]
+fn sign_for_brc721_token() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	let token_id = register_token(&core, &ord, false);
+
+	let text = "HelloWorld";
+
+	let sign =
+		CommandBuilder::new(format!("--regtest wallet sign --brc721 2:1:{token_id} --text {text}"))
+			.core(&core)
+			.ord(&ord)
+			.run_and_deserialize_output::<SignOutput>();
+
+	let token = serde_json::from_str::<api::Brc721Token>(
+		&ord.json_request(format!("/brc721/token/2:1/{token_id}")).text().unwrap(),
+	)
+	.unwrap();
+
+	assert_eq!(token.address, Some(sign.address));
+
+	CommandBuilder::new(format!(
+		"--regtest verify --brc721 2:1:{token_id} --server-url {} --text {text} --witness {}",
+		ord.url(),
+		sign.witness,
+	))
+	.core(&core)
+	.run_and_extract_stdout();
+
+	CommandBuilder::new(format!(
+		"--regtest verify --brc721 2:1:{token_id} --server-url {} --text FAIL --witness {}",
+		ord.url(),
+		sign.witness,
+	))
+	.core(&core)
+	.expected_exit_code(1)
+	.stderr_regex("error: Invalid signature.*")
+	.run_and_extract_stdout();
+}
+
+#[test]
+fn verify_brc721_token_rejects_signature_of_other_address() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	let token_id = register_token(&core, &ord, false);
+
+	let address = CommandBuilder::new("--regtest wallet receive")
+		.core(&core)
+		.ord(&ord)
+		.run_and_deserialize_output::<ReceiveOutput>()
+		.addresses
+		.remove(0);
+
+	let text = "HelloWorld";
+
+	let sign = CommandBuilder::new(format!(
+		"--regtest wallet sign --signer {} --text {text}",
+		address.assume_checked(),
+	))
+	.core(&core)
+	.ord(&ord)
+	.run_and_deserialize_output::<SignOutput>();
+
+	CommandBuilder::new(format!(
+		"--regtest verify --brc721 2:1:{token_id} --server-url {} --text {text} --witness {}",
+		ord.url(),
+		sign.witness,
+	))
+	.core(&core)
+	.expected_exit_code(1)
+	.stderr_regex("error: Invalid signature.*")
+	.run_and_extract_stdout();
+}
+
+#[test]
+fn sign_for_unregistered_brc721_token() {
+	let core = mockcore::builder().network(Network::Regtest).build();
+
+	let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-brc721"], &[]);
+
+	create_wallet(&core, &ord);
+
+	CommandBuilder::new("--regtest wallet sign --brc721 2:1:42 --text HelloWorld")
+		.core(&core)
+		.ord(&ord)
+		.expected_exit_code(1)
+		.expected_stderr("error: brc721 token 2:1:42 not found\n")
+		.run_and_extract_stdout();
+}